@@ -0,0 +1,158 @@
+#!/usr/bin/env python3
+"""Writes assets/base-skin.wsz, the skin built into the binary.
+
+Every sprite listed in src/skin_generated.rs gets a plain bevelled box, except for the
+digit and font bitmaps, which get seven-segment digits and a 4x6 pixel font on black.
+
+Run from the repository root after changing the sprite list in build.rs:
+
+    cargo build && python3 assets/make_base_skin.py
+"""
+import re
+import struct
+import zipfile
+
+SPRITES = 'src/skin_generated.rs'
+OUTPUT = 'assets/base-skin.wsz'
+
+BG = (0, 0, 0)
+FG = (0, 0xe0, 0)
+LIGHT = (0x8a, 0x8a, 0xa0)
+FACE = (0x4a, 0x4a, 0x5c)
+SHADOW = (0x20, 0x20, 0x2c)
+
+
+def bmp(px):
+    """24-bit bottom-up BMP."""
+    h = len(px)
+    w = len(px[0])
+    row = (w * 3 + 3) // 4 * 4
+    data = b''.join(b''.join(bytes((b, g, r)) for (r, g, b) in px[y]) + b'\0' * (row - w * 3) for y in reversed(range(h)))
+    header = b'BM' + struct.pack('<IHHI', 54 + len(data), 0, 0, 54) + struct.pack('<IiiHHIIiiII', 40, w, h, 1, 24, 0, len(data), 2835, 2835, 0, 0)
+    return header + data
+
+
+def canvas(w, h, color=BG):
+    return [[color] * w for _ in range(h)]
+
+
+def sprite_files():
+    """(filename, [(x0, y0, x1, y1)]) for every file the skin loader reads, in its order."""
+    source = open(SPRITES).read()
+    body = source[source.index('fn get_skin_load_specs'):]
+    for spec in re.split(r'crate::skin::FileLoadSpec \{', body)[1:]:
+        name = re.search(r'filename: "([^"]+)"', spec).group(1)
+        rects = [tuple(int(n) for n in m) for m in re.findall(r'top_left_x: (\d+),\s*top_left_y: (\d+),\s*bottom_right_x: (\d+),\s*bottom_right_y: (\d+)', spec)]
+        yield name, rects
+
+
+def boxes(rects):
+    px = canvas(max(r[2] for r in rects), max(r[3] for r in rects))
+    for (x0, y0, x1, y1) in rects:
+        for y in range(y0, y1):
+            for x in range(x0, x1):
+                if y == y0 or x == x0:
+                    px[y][x] = LIGHT
+                elif y == y1 - 1 or x == x1 - 1:
+                    px[y][x] = SHADOW
+                else:
+                    px[y][x] = FACE
+    return px
+
+
+# Seven segment digits in a 9x13 cell: a top, b top right, c bottom right, d bottom, e bottom left, f top left, g middle.
+SEGMENTS = {0: 'abcdef', 1: 'bc', 2: 'abged', 3: 'abgcd', 4: 'fgbc', 5: 'afgcd', 6: 'afgedc', 7: 'abc', 8: 'abcdefg', 9: 'abcdfg'}
+
+
+def segment(px, x0, seg):
+    top, mid, bottom, left, right = 1, 6, 11, 1, 7
+    cells = {
+        'a': [(x, top) for x in range(left, right + 1)],
+        'g': [(x, mid) for x in range(left, right + 1)],
+        'd': [(x, bottom) for x in range(left, right + 1)],
+        'f': [(left, y) for y in range(top, mid + 1)],
+        'b': [(right, y) for y in range(top, mid + 1)],
+        'e': [(left, y) for y in range(mid, bottom + 1)],
+        'c': [(right, y) for y in range(mid, bottom + 1)],
+    }[seg]
+    for x, y in cells:
+        px[y][x0 + x] = FG
+
+
+def digit(px, x0, d):
+    for seg in SEGMENTS[d]:
+        segment(px, x0, seg)
+
+
+def numbers():
+    px = canvas(90, 13)
+    for d in range(10):
+        digit(px, d * 9, d)
+    return px
+
+
+def nums_ex():
+    px = canvas(108, 13)
+    for d in range(10):
+        digit(px, d * 9, d)
+    # 90 is the blank cell, 99 the minus
+    segment(px, 99, 'g')
+    return px
+
+
+FONT = {
+    'A': '.##. #..# #### #..# #..#', 'B': '###. #..# ###. #..# ###.', 'C': '.### #... #... #... .###',
+    'D': '###. #..# #..# #..# ###.', 'E': '#### #... ###. #... ####', 'F': '#### #... ###. #... #...',
+    'G': '.### #... #.## #..# .###', 'H': '#..# #..# #### #..# #..#', 'I': '###. .#.. .#.. .#.. ###.',
+    'J': '..## ...# ...# #..# .##.', 'K': '#..# #.#. ##.. #.#. #..#', 'L': '#... #... #... #... ####',
+    'M': '#..# #### #### #..# #..#', 'N': '#..# ##.# #.## #..# #..#', 'O': '.##. #..# #..# #..# .##.',
+    'P': '###. #..# ###. #... #...', 'Q': '.##. #..# #..# #.#. .#.#', 'R': '###. #..# ###. #.#. #..#',
+    'S': '.### #... .##. ...# ###.', 'T': '###. .#.. .#.. .#.. .#..', 'U': '#..# #..# #..# #..# .##.',
+    'V': '#.#. #.#. #.#. #.#. .#..', 'W': '#..# #..# #..# #### #..#', 'X': '#..# #..# .##. #..# #..#',
+    'Y': '#.#. #.#. .#.. .#.. .#..', 'Z': '#### ..#. .#.. #... ####', '"': '#.#. #.#. .... .... ....',
+    '@': '.##. #.## #.## #... .###',
+    '0': '.##. #.## ##.# #..# .##.', '1': '.#.. ##.. .#.. .#.. ###.', '2': '###. ...# .##. #... ####',
+    '3': '###. ...# .##. ...# ###.', '4': '#..# #..# #### ...# ...#', '5': '#### #... ###. ...# ###.',
+    '6': '.##. #... ###. #..# .##.', '7': '#### ...# ..#. .#.. .#..', '8': '.##. #..# .##. #..# .##.',
+    '9': '.##. #..# .### ...# .##.', '…': '.... .... .... .... #.#.', '.': '.... .... .... .... .#..',
+    ':': '.... .#.. .... .#.. ....', '(': '..#. .#.. .#.. .#.. ..#.', ')': '.#.. ..#. ..#. ..#. .#..',
+    '-': '.... .... ###. .... ....', "'": '.#.. .#.. .... .... ....', '!': '.#.. .#.. .#.. .... .#..',
+    '_': '.... .... .... .... ####', '+': '.... .#.. ###. .#.. ....', '\\': '#... #... .#.. ..#. ..#.',
+    '/': '..#. ..#. .#.. #... #...', '[': '##.. #... #... #... ##..', ']': '.##. ..#. ..#. ..#. .##.',
+    '^': '.#.. #.#. .... .... ....', '&': '.#.. #.#. .#.. #.#. .#.#', '%': '#..# ..#. .#.. #... #..#',
+    ',': '.... .... .... .#.. #...', '=': '.... ###. .... ###. ....', '$': '.### ##.. .##. ..## ###.',
+    '#': '#.#. #### #.#. #### #.#.',
+    'Å': '.##. .##. #..# #### #..#', 'Ö': '#..# .##. #..# #..# .##.', 'Ä': '#..# .##. #..# #### #..#',
+    '?': '###. ...# .##. .... .#..', '*': '#.#. .#.. ###. .#.. #.#.',
+}
+# The same rows as TEXT.BMP, and as build.rs maps them.
+ROWS = ['ABCDEFGHIJKLMNOPQRSTUVWXYZ"@', '0123456789….:()-\'!_+\\/[]^&%,=$#', 'ÅÖÄ?*']
+
+
+def text():
+    # 155 wide: the second row runs to 155, and 150 on the first row is left empty for the space.
+    px = canvas(155, 6 * len(ROWS))
+    for row, chars in enumerate(ROWS):
+        for i, c in enumerate(chars):
+            for y, line in enumerate(FONT[c].split()):
+                for x, bit in enumerate(line):
+                    if bit == '#':
+                        px[row * 6 + y][i * 5 + x] = FG
+    return px
+
+
+DRAWN = {'NUMBERS.BMP': numbers, 'NUMS_EX.BMP': nums_ex, 'TEXT.BMP': text}
+
+
+def main():
+    out = zipfile.ZipFile(OUTPUT, 'w', zipfile.ZIP_DEFLATED)
+    for name, rects in sprite_files():
+        px = DRAWN[name]() if name in DRAWN else boxes(rects)
+        # A fixed date, so the archive only changes when its contents do.
+        info = zipfile.ZipInfo(name, (2021, 1, 1, 0, 0, 0))
+        info.compress_type = zipfile.ZIP_DEFLATED
+        out.writestr(info, bmp(px))
+    out.close()
+
+
+main()
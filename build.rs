@@ -5,6 +5,8 @@ use codegen::{Scope, Type};
 
 struct SkinFile {
     filename: String,
+    /// Whether a skin without this file should fail to load, rather than borrowing it from the base skin
+    required: bool,
     regions: Vec<Vec<SkinFileRegion>> 
 }
 
@@ -21,11 +23,16 @@ const IMAGE_ENUM_NAME: &str = "SkinImage";
 
 fn regions(scope: &mut Scope) -> Vec<SkinFile>{
     let volume_slider_bar_names = numbered_enum_names("VolumeSliderBar", 28, scope);
-    let small_font_names = char_mapped_enum_names("SmallFont", "ABCDEFGHIJKLMNOPQRSTUVWXYZ\"@0123456789….:()-'!_+\\/[]^&%,=$#".to_string(), scope);
+    let balance_slider_bar_names = numbered_enum_names("BalanceSliderBar", 28, scope);
+    let eq_slider_bar_names = numbered_enum_names("EqSliderBar", 28, scope);
+    let numbers_digit_names = numbered_enum_names("NumbersDigit", 10, scope);
+    let numbers_ex_digit_names = numbered_enum_names("NumbersExDigit", 10, scope);
+    let small_font_names = char_mapped_enum_names("SmallFont", "ABCDEFGHIJKLMNOPQRSTUVWXYZ\"@0123456789….:()-'!_+\\/[]^&%,=$#ÅÖÄ?*".to_string(), scope);
 
     vec![
     SkinFile {
         filename: "CBUTTONS.BMP".to_string(),
+        required: true,
         regions: vec![
             map_repeated(0, 0, 22, 18, 23, 0, vec![
                 "ButtonPrev",
@@ -45,6 +52,7 @@ fn regions(scope: &mut Scope) -> Vec<SkinFile>{
     },
     SkinFile {
         filename: "MAIN.BMP".to_string(),
+        required: true,
         regions: vec![vec![
             SkinFileRegion {
                 top_left_x: 0,
@@ -57,6 +65,7 @@ fn regions(scope: &mut Scope) -> Vec<SkinFile>{
     },
     SkinFile {
         filename: "VOLUME.BMP".to_string(),
+        required: true,
         regions: vec![
             map_repeated(0, 0, 68, 13, 0, 15, volume_slider_bar_names.iter().map(|s| s.as_str()).collect()),
             vec![
@@ -79,9 +88,302 @@ fn regions(scope: &mut Scope) -> Vec<SkinFile>{
     },
     SkinFile {
         filename: "TEXT.BMP".to_string(),
+        required: true,
         regions: vec![
             map_repeated(0, 0, 4, 6, 5, 0, small_font_names.iter().take(28).map(|s| s.as_str()).collect()),
             map_repeated(0, 6, 4, 6, 5, 0, small_font_names.iter().skip(28).take(31).map(|s| s.as_str()).collect()),
+            map_repeated(0, 12, 4, 6, 5, 0, small_font_names.iter().skip(59).map(|s| s.as_str()).collect()),
+            // A whole empty cell, including the gap between glyphs
+            vec![region(150, 0, 5, 6, "SmallFontSpace")],
+        ]
+    },
+    SkinFile {
+        filename: "TITLEBAR.BMP".to_string(),
+        required: true,
+        regions: vec![vec![
+            region(27, 0, 275, 14, "TitleBarSelected"),
+            region(27, 15, 275, 14, "TitleBar"),
+            region(27, 57, 275, 14, "TitleBarEasterEggSelected"),
+            region(27, 72, 275, 14, "TitleBarEasterEgg"),
+            region(0, 0, 9, 9, "TitleBarOptions"),
+            region(0, 9, 9, 9, "TitleBarOptionsPressed"),
+            region(9, 0, 9, 9, "TitleBarMinimize"),
+            region(9, 9, 9, 9, "TitleBarMinimizePressed"),
+            region(0, 18, 9, 9, "TitleBarShade"),
+            region(9, 18, 9, 9, "TitleBarShadePressed"),
+            region(0, 27, 9, 9, "TitleBarUnshade"),
+            region(9, 27, 9, 9, "TitleBarUnshadePressed"),
+            region(18, 0, 9, 9, "TitleBarClose"),
+            region(18, 9, 9, 9, "TitleBarClosePressed"),
+            region(304, 0, 8, 43, "ClutterBar"),
+            region(312, 0, 8, 43, "ClutterBarDisabled"),
+            region(27, 29, 275, 14, "MainShadeSelected"),
+            region(27, 42, 275, 14, "MainShade"),
+            region(0, 36, 17, 7, "MainShadePositionBackground"),
+            region(17, 36, 3, 7, "MainShadePositionThumbLeft"),
+            region(20, 36, 3, 7, "MainShadePositionThumb"),
+            region(23, 36, 3, 7, "MainShadePositionThumbRight"),
+        ]]
+    },
+    SkinFile {
+        filename: "POSBAR.BMP".to_string(),
+        required: true,
+        regions: vec![vec![
+            region(0, 0, 248, 10, "PositionBar"),
+            region(248, 0, 29, 10, "PositionBarThumb"),
+            region(278, 0, 29, 10, "PositionBarThumbPressed"),
+        ]]
+    },
+    SkinFile {
+        filename: "BALANCE.BMP".to_string(),
+        required: false,
+        regions: vec![
+            map_repeated(9, 0, 38, 13, 0, 15, balance_slider_bar_names.iter().map(|s| s.as_str()).collect()),
+            vec![
+                region(15, 422, 14, 11, "BalanceSliderButton"),
+                region(0, 422, 14, 11, "BalanceSliderButtonPressed"),
+            ]
+        ]
+    },
+    SkinFile {
+        filename: "SHUFREP.BMP".to_string(),
+        required: true,
+        regions: vec![
+            map_repeated(0, 0, 28, 15, 0, 15, vec![
+                "Repeat",
+                "RepeatPressed",
+                "RepeatOn",
+                "RepeatOnPressed" ]),
+            map_repeated(28, 0, 47, 15, 0, 15, vec![
+                "Shuffle",
+                "ShufflePressed",
+                "ShuffleOn",
+                "ShuffleOnPressed" ]),
+            map_repeated(0, 61, 23, 12, 46, 0, vec![
+                "EqToggle",
+                "EqTogglePressed" ]),
+            map_repeated(0, 73, 23, 12, 46, 0, vec![
+                "EqToggleOn",
+                "EqToggleOnPressed" ]),
+            map_repeated(23, 61, 23, 12, 46, 0, vec![
+                "PlaylistToggle",
+                "PlaylistTogglePressed" ]),
+            map_repeated(23, 73, 23, 12, 46, 0, vec![
+                "PlaylistToggleOn",
+                "PlaylistToggleOnPressed" ]),
+        ]
+    },
+    SkinFile {
+        filename: "MONOSTER.BMP".to_string(),
+        required: true,
+        regions: vec![vec![
+            region(0, 0, 29, 12, "StereoOn"),
+            region(0, 12, 29, 12, "Stereo"),
+            region(29, 0, 27, 12, "MonoOn"),
+            region(29, 12, 27, 12, "Mono"),
+        ]]
+    },
+    SkinFile {
+        filename: "PLAYPAUS.BMP".to_string(),
+        required: true,
+        regions: vec![
+            map_repeated(0, 0, 9, 9, 9, 0, vec![
+                "IndicatorPlaying",
+                "IndicatorPaused",
+                "IndicatorStopped" ]),
+            map_repeated(36, 0, 3, 9, 3, 0, vec![
+                "IndicatorNotWorking",
+                "IndicatorWorking" ]),
+        ]
+    },
+    SkinFile {
+        filename: "NUMBERS.BMP".to_string(),
+        required: false,
+        regions: vec![
+            map_repeated(0, 0, 9, 13, 9, 0, numbers_digit_names.iter().map(|s| s.as_str()).collect()),
+            // NUMBERS.BMP has no minus sign of its own, Winamp draws a bit of the middle of a digit instead.
+            vec![
+                region(20, 6, 5, 1, "NumbersMinus"),
+                region(9, 6, 5, 1, "NumbersNoMinus"),
+            ],
+        ]
+    },
+    SkinFile {
+        filename: "NUMS_EX.BMP".to_string(),
+        required: false,
+        regions: vec![
+            map_repeated(0, 0, 9, 13, 9, 0, numbers_ex_digit_names.iter().map(|s| s.as_str()).collect()),
+            map_repeated(90, 0, 9, 13, 9, 0, vec![
+                "NumbersExBlank",
+                "NumbersExMinus" ]),
+        ]
+    },
+    SkinFile {
+        filename: "EQMAIN.BMP".to_string(),
+        required: false,
+        regions: vec![
+            vec![
+                region(0, 0, 275, 116, "EqWindow"),
+                region(0, 134, 275, 14, "EqTitleBarSelected"),
+                region(0, 149, 275, 14, "EqTitleBar"),
+                region(0, 164, 11, 11, "EqSliderThumb"),
+                region(0, 176, 11, 11, "EqSliderThumbPressed"),
+                region(0, 116, 9, 9, "EqClose"),
+                region(0, 125, 9, 9, "EqClosePressed"),
+                region(224, 164, 44, 12, "EqPresets"),
+                region(224, 176, 44, 12, "EqPresetsPressed"),
+                region(0, 294, 113, 19, "EqGraph"),
+                region(115, 294, 1, 19, "EqGraphLineColors"),
+                region(0, 314, 113, 1, "EqPreampLine"),
+            ],
+            map_repeated(10, 119, 26, 12, 118, 0, vec![
+                "EqOn",
+                "EqOnPressed" ]),
+            map_repeated(69, 119, 26, 12, 118, 0, vec![
+                "EqOnOn",
+                "EqOnOnPressed" ]),
+            map_repeated(36, 119, 32, 12, 118, 0, vec![
+                "EqAuto",
+                "EqAutoPressed" ]),
+            map_repeated(95, 119, 32, 12, 118, 0, vec![
+                "EqAutoOn",
+                "EqAutoOnPressed" ]),
+            map_repeated(13, 164, 14, 63, 15, 0, eq_slider_bar_names.iter().take(14).map(|s| s.as_str()).collect()),
+            map_repeated(13, 229, 14, 63, 15, 0, eq_slider_bar_names.iter().skip(14).map(|s| s.as_str()).collect()),
+        ]
+    },
+    SkinFile {
+        filename: "EQ_EX.BMP".to_string(),
+        required: false,
+        regions: vec![
+            vec![
+                region(0, 0, 275, 14, "EqShadeSelected"),
+                region(0, 15, 275, 14, "EqShade"),
+                region(1, 38, 9, 9, "EqUnshadePressed"),
+                region(1, 47, 9, 9, "EqShadePressed"),
+                region(11, 38, 9, 9, "EqShadeClose"),
+                region(11, 47, 9, 9, "EqShadeClosePressed"),
+            ],
+            map_repeated(1, 30, 3, 7, 3, 0, vec![
+                "EqShadeVolumeThumbLeft",
+                "EqShadeVolumeThumb",
+                "EqShadeVolumeThumbRight" ]),
+            map_repeated(11, 30, 3, 7, 3, 0, vec![
+                "EqShadeBalanceThumbLeft",
+                "EqShadeBalanceThumb",
+                "EqShadeBalanceThumbRight" ]),
+        ]
+    },
+    SkinFile {
+        filename: "PLEDIT.BMP".to_string(),
+        required: false,
+        regions: vec![
+            map_repeated(0, 0, 25, 20, 0, 21, vec![
+                "PlaylistTopLeftSelected",
+                "PlaylistTopLeft" ]),
+            map_repeated(26, 0, 100, 20, 0, 21, vec![
+                "PlaylistTitleSelected",
+                "PlaylistTitle" ]),
+            map_repeated(127, 0, 25, 20, 0, 21, vec![
+                "PlaylistTopTileSelected",
+                "PlaylistTopTile" ]),
+            map_repeated(153, 0, 25, 20, 0, 21, vec![
+                "PlaylistTopRightSelected",
+                "PlaylistTopRight" ]),
+            vec![
+                region(0, 42, 12, 29, "PlaylistLeftTile"),
+                region(31, 42, 20, 29, "PlaylistRightTile"),
+                region(0, 72, 125, 38, "PlaylistBottomLeft"),
+                region(126, 72, 150, 38, "PlaylistBottomRight"),
+                region(179, 0, 25, 38, "PlaylistBottomTile"),
+                region(205, 0, 75, 38, "PlaylistVisualizer"),
+                region(52, 53, 8, 18, "PlaylistScrollHandle"),
+                region(61, 53, 8, 18, "PlaylistScrollHandlePressed"),
+                region(52, 42, 9, 9, "PlaylistClosePressed"),
+                region(62, 42, 9, 9, "PlaylistShadePressed"),
+                region(150, 42, 9, 9, "PlaylistUnshadePressed"),
+                region(72, 42, 25, 14, "PlaylistShadeLeft"),
+                region(72, 57, 25, 14, "PlaylistShadeTile"),
+                region(99, 42, 50, 14, "PlaylistShadeRightSelected"),
+                region(99, 57, 50, 14, "PlaylistShadeRight"),
+                region(48, 111, 3, 54, "PlaylistAddMenuBar"),
+                region(100, 111, 3, 72, "PlaylistRemoveMenuBar"),
+                region(150, 111, 3, 54, "PlaylistSelectMenuBar"),
+                region(200, 111, 3, 54, "PlaylistMiscMenuBar"),
+                region(250, 111, 3, 54, "PlaylistListMenuBar"),
+            ],
+            map_repeated(0, 111, 22, 18, 0, 19, vec![
+                "PlaylistAddUrl",
+                "PlaylistAddDir",
+                "PlaylistAddFile" ]),
+            map_repeated(23, 111, 22, 18, 0, 19, vec![
+                "PlaylistAddUrlPressed",
+                "PlaylistAddDirPressed",
+                "PlaylistAddFilePressed" ]),
+            map_repeated(54, 111, 22, 18, 0, 19, vec![
+                "PlaylistRemoveAll",
+                "PlaylistRemoveCrop",
+                "PlaylistRemoveSelected",
+                "PlaylistRemoveMisc" ]),
+            map_repeated(77, 111, 22, 18, 0, 19, vec![
+                "PlaylistRemoveAllPressed",
+                "PlaylistRemoveCropPressed",
+                "PlaylistRemoveSelectedPressed",
+                "PlaylistRemoveMiscPressed" ]),
+            map_repeated(104, 111, 22, 18, 0, 19, vec![
+                "PlaylistSelectInvert",
+                "PlaylistSelectNone",
+                "PlaylistSelectAll" ]),
+            map_repeated(127, 111, 22, 18, 0, 19, vec![
+                "PlaylistSelectInvertPressed",
+                "PlaylistSelectNonePressed",
+                "PlaylistSelectAllPressed" ]),
+            map_repeated(154, 111, 22, 18, 0, 19, vec![
+                "PlaylistMiscSort",
+                "PlaylistMiscInfo",
+                "PlaylistMiscOptions" ]),
+            map_repeated(177, 111, 22, 18, 0, 19, vec![
+                "PlaylistMiscSortPressed",
+                "PlaylistMiscInfoPressed",
+                "PlaylistMiscOptionsPressed" ]),
+            map_repeated(204, 111, 22, 18, 0, 19, vec![
+                "PlaylistListNew",
+                "PlaylistListSave",
+                "PlaylistListLoad" ]),
+            map_repeated(227, 111, 22, 18, 0, 19, vec![
+                "PlaylistListNewPressed",
+                "PlaylistListSavePressed",
+                "PlaylistListLoadPressed" ]),
+        ]
+    },
+    SkinFile {
+        filename: "GEN.BMP".to_string(),
+        required: false,
+        regions: vec![
+            map_repeated(0, 0, 25, 20, 26, 0, vec![
+                "GenTopLeftSelected",
+                "GenTopLeftEndSelected",
+                "GenTopCenterFillSelected",
+                "GenTopRightEndSelected",
+                "GenTopLeftRightFillSelected",
+                "GenTopRightSelected" ]),
+            map_repeated(0, 21, 25, 20, 26, 0, vec![
+                "GenTopLeft",
+                "GenTopLeftEnd",
+                "GenTopCenterFill",
+                "GenTopRightEnd",
+                "GenTopLeftRightFill",
+                "GenTopRight" ]),
+            vec![
+                region(0, 42, 125, 14, "GenBottomLeft"),
+                region(0, 57, 125, 14, "GenBottomRight"),
+                region(127, 72, 25, 14, "GenBottomFill"),
+                region(127, 42, 11, 29, "GenMiddleLeft"),
+                region(158, 42, 11, 24, "GenMiddleLeftBottom"),
+                region(139, 42, 8, 29, "GenMiddleRight"),
+                region(170, 42, 8, 24, "GenMiddleRightBottom"),
+                region(148, 42, 9, 9, "GenClosePressed"),
+            ],
         ]
     },
     ]
@@ -109,6 +411,7 @@ fn main() {
         for file in &regions {
             load_function.line("crate::skin::FileLoadSpec {");
             load_function.line(format!("filename: \"{}\",", file.filename));
+            load_function.line(format!("required: {},", file.required));
             load_function.line("regions: vec![");
             for region in file.regions.iter().flatten() {
                 load_function.line("crate::skin::RectLoadSpec {");
@@ -145,6 +448,16 @@ fn map_repeated(top_left_x: u32, top_left_y: u32, width: u32, height: u32, strid
 
 }
 
+fn region(top_left_x: u32, top_left_y: u32, width: u32, height: u32, name: &str) -> SkinFileRegion {
+    SkinFileRegion {
+        top_left_x,
+        top_left_y,
+        bottom_right_x: top_left_x + width,
+        bottom_right_y: top_left_y + height,
+        enum_name: name.to_string()
+    }
+}
+
 /// Generates a set of numbered enum names, and a function which will iterate them
 fn numbered_enum_names(prefix: &str, count: usize, scope: &mut Scope) -> Vec<String> {
     let names = (0..count).into_iter().map(|i| format!("{}{}", prefix, i)).collect();
@@ -205,6 +518,11 @@ fn map_special_chars_to_names(c: char) -> String {
         '$' => "Dollar".to_string(),
         '#' => "Hash".to_string(),
         '…' => "Ellipsis".to_string(),
+        'Å' => "ARing".to_string(),
+        'Ö' => "ODiaeresis".to_string(),
+        'Ä' => "ADiaeresis".to_string(),
+        '?' => "Question".to_string(),
+        '*' => "Asterisk".to_string(),
         _ => c.to_string()
 
     }
@@ -4,10 +4,26 @@ use std::path::PathBuf;
 use eframe::egui::{Pos2, Rect};
 use eframe::{egui, epi};
 
-use crate::skin::{self, LoadedImage, WinampSkin};
-use crate::skin_generated::{SkinImage, iter_VolumeSliderBar};
+use crate::audio::{EqSettings, OutputKind, PlaybackEngine, PlaybackState};
+use crate::eq_presets::PresetStore;
+use crate::playlist::{Location, Playlist, PlaylistEntry, PlaylistFormat};
+use crate::playlist::order::{PlayOrder, RepeatMode};
+use crate::scale::SkinScale;
+use crate::tags::{TagReader, TitleFormat};
+use crate::skin::{self, LoadedImage, SkinSource, WinampSkin};
+use crate::skin_atlas::SkinAtlas;
+use crate::skin_generated::{SkinImage, iter_BalanceSliderBar, iter_NumbersDigit, iter_NumbersExDigit, iter_VolumeSliderBar};
+use crate::widgets::bitmap_text::paint_text;
 use crate::widgets::button::MultiImageButton;
+use crate::widgets::marquee::{Marquee, MarqueeState};
+use crate::widgets::seek_bar::{SeekBar, SeekBarGraphics, SeekBarState};
+use crate::widgets::shade_slider::ShadeSliderGraphics;
 use crate::widgets::slider::{SliderGraphics, WinampSlider};
+use crate::widgets::time_display::{DigitGraphics, TimeDisplay};
+use crate::windows::equalizer::{equalizer_shade, equalizer_window};
+use crate::windows::layout::{SkinWindow, WindowLayout, TITLE_BAR_HEIGHT};
+use crate::windows::playlist::{playlist_shade, playlist_window, PlaylistDialog, PlaylistEditor, PlaylistPrompt, PlaylistWindowState};
+use crate::windows::presets::PresetsWindow;
 use fixed_map::{Key, Map};
 
 /// We derive Deserialize/Serialize so we can persist app state on shutdown.
@@ -27,14 +43,68 @@ pub struct TemplateApp {
     textures_loaded: bool,
     #[cfg_attr(feature = "persistence", serde(skip))]
     skin_textures: Map<SkinImage, LoadedTexture>,
+    /// What `skin_textures` are on, for freeing when they're replaced
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    skin_atlas: Option<SkinAtlas>,
+    /// How big the skin is drawn
+    skin_scale: SkinScale,
+    /// What was wrong with the skin when it was loaded, shown until dismissed
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    skin_messages: Vec<String>,
+
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    player: Option<PlaybackEngine>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    tag_reader: Option<TagReader>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    open_file_window: bool,
+    open_file_path: String,
+    playlist: Playlist,
+    show_playlist: bool,
+    playlist_editor: PlaylistWindowState,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    playlist_dialog: PlaylistDialog,
+    /// How tracks are named from their tags
+    title_format: TitleFormat,
+    play_order: PlayOrder,
+    window_layout: WindowLayout,
 
     volume: f32,
-    pub skin_path: Option<PathBuf>
+    /// -100 (left only) to 100 (right only)
+    balance: i32,
+    equalizer: EqSettings,
+    /// Load the preset named after a track when it's opened
+    equalizer_auto: bool,
+    show_equalizer: bool,
+    eq_presets: PresetStore,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    presets_window: PresetsWindow,
+    /// Whether the time display counts down to the end of the track
+    show_remaining_time: bool,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    title_marquee: MarqueeState,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    seek_bar: SeekBarState,
+    pub skin_path: Option<PathBuf>,
+    /// A skin archive to use instead of `skin_path`, for when there's no filesystem to load from
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub skin_bytes: Option<Vec<u8>>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub output: OutputKind,
 }
 
 pub struct LoadedTexture {
     pub size: eframe::egui::Vec2,
+    /// The atlas page the sprite is on
     pub texture: eframe::egui::TextureId,
+    /// Where on the page it is
+    pub uv: Rect,
+}
+
+impl LoadedTexture {
+    pub fn image(&self) -> egui::Image {
+        egui::Image::new(self.texture, self.size).uv(self.uv)
+    }
 }
 
 impl Default for TemplateApp {
@@ -46,8 +116,33 @@ impl Default for TemplateApp {
             skin_images: None,
             textures_loaded: false,
             skin_textures: Default::default(),
+            skin_atlas: None,
+            skin_scale: Default::default(),
+            skin_messages: vec![],
+            player: None,
+            tag_reader: None,
+            open_file_window: false,
+            open_file_path: Default::default(),
+            playlist: Default::default(),
+            show_playlist: true,
+            playlist_editor: Default::default(),
+            playlist_dialog: Default::default(),
+            title_format: Default::default(),
+            play_order: Default::default(),
+            window_layout: Default::default(),
             volume: 0.5,
-            skin_path: None
+            balance: 0,
+            equalizer: Default::default(),
+            equalizer_auto: false,
+            show_equalizer: true,
+            eq_presets: Default::default(),
+            presets_window: Default::default(),
+            show_remaining_time: false,
+            title_marquee: Default::default(),
+            seek_bar: Default::default(),
+            skin_path: None,
+            skin_bytes: None,
+            output: OutputKind::Device,
         }
     }
 }
@@ -68,10 +163,44 @@ impl epi::App for TemplateApp {
         // Note that you must enable the `persistence` feature for this to work.
         #[cfg(feature = "persistence")]
         if let Some(storage) = _storage {
-            *self = epi::get_value(storage, epi::APP_KEY).unwrap_or_default()
+            // What was asked for on the command line (or by the page) isn't saved, so it has to survive the load.
+            let output = std::mem::take(&mut self.output);
+            let skin_bytes = self.skin_bytes.take();
+            let skin_path = self.skin_path.take();
+            *self = epi::get_value(storage, epi::APP_KEY).unwrap_or_default();
+            self.output = output;
+            self.skin_bytes = skin_bytes;
+            // Without one, the last skin given is used again.
+            if skin_path.is_some() {
+                self.skin_path = skin_path;
+            }
+        }
+        let source = match &self.skin_bytes {
+            Some(bytes) => SkinSource::Bytes(bytes),
+            None => SkinSource::from_path(self.skin_path.clone().unwrap_or_else(|| PathBuf::from(r"/Users/vivlim/winamp/base-2.91.wsz.zip"))),
+        };
+        let skin = skin::open_skin(&source);
+        self.skin_images = match skin {
+            Ok((skin, warnings)) => {
+                self.skin_messages = warnings.iter().map(|warning| warning.to_string()).collect();
+                Some(skin)
+            }
+            Err(e) => {
+                self.skin_messages = vec![format!("Couldn't load the skin ({}), using the base skin", e)];
+                skin::base_skin().ok()
+            }
+        };
+
+        // There are no threads to decode or read tags on in the browser.
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mut player = PlaybackEngine::new(self.output.clone());
+            player.set_volume(self.volume);
+            player.set_balance(self.balance as f32 / 100.0);
+            player.set_equalizer(self.equalizer);
+            self.player = Some(player);
+            self.tag_reader = Some(TagReader::new());
         }
-        let skin = skin::open_skin(self.skin_path.as_ref().unwrap_or(&PathBuf::from(r"/Users/vivlim/winamp/base-2.91.wsz.zip")));
-        self.skin_images = skin.ok();
     }
 
     /// Called by the frame work to save state before shutdown.
@@ -84,24 +213,24 @@ impl epi::App for TemplateApp {
     /// Called each time the UI needs repainting, which may be many times per second.
     /// Put your widgets into a `SidePanel`, `TopPanel`, `CentralPanel`, `Window` or `Area`.
     fn update(&mut self, ctx: &egui::CtxRef, frame: &mut epi::Frame<'_>) {
-        let Self { label, value, skin_images, textures_loaded, skin_textures, volume, skin_path} = self;
+        let Self { label, value, skin_images, textures_loaded, skin_textures, skin_atlas, skin_scale, skin_messages, player, tag_reader, open_file_window, open_file_path, playlist, show_playlist, playlist_editor, playlist_dialog, title_format, play_order, window_layout, volume, balance, equalizer, equalizer_auto, show_equalizer, eq_presets, presets_window, show_remaining_time, title_marquee, seek_bar, skin_path, skin_bytes, output} = self;
+
+        skin_scale.handle_input(&ctx.input());
+        if skin_scale.apply(ctx, frame) {
+            // They were uploaded for the old scale.
+            *textures_loaded = false;
+        }
 
         if !*textures_loaded {
-            match skin_images {
-                Some(skin) => {
-                    for (name, image) in skin.images.iter() {
-                        println!("loading texture for {}", name);
-                        let texture = frame.tex_allocator().alloc_srgba_premultiplied(image.size, &image.pixels);
-                        let size = egui::Vec2::new(image.size.0 as f32, image.size.1 as f32);
-                        skin_textures.insert(name.clone(), LoadedTexture {
-                            size,
-                            texture,
-                        });
-
-                    }
-                   *textures_loaded = true;
-                },
-                None => ()
+            if let Some(skin) = skin_images {
+                // Drawn at the skin's own size, but with a pixel for every pixel on screen.
+                let (atlas, textures) = SkinAtlas::upload(skin, skin_scale.texture_scale(), frame.tex_allocator());
+                // Whatever skin or scale was loaded before
+                if let Some(previous) = skin_atlas.replace(atlas) {
+                    previous.free(frame.tex_allocator());
+                }
+                *skin_textures = textures;
+                *textures_loaded = true;
             }
         }
 
@@ -111,44 +240,417 @@ impl epi::App for TemplateApp {
         // Tip: a good default choice is to just keep the `CentralPanel`.
         // For inspiration and more examples, go to https://emilk.github.io/egui
 
+        let mut quit = false;
         egui::CentralPanel::default().show(ctx, |ui| {
             if *textures_loaded {
                 let tex = skin_textures.get(SkinImage::MainWindow).unwrap();
-                ui.put(egui::Rect::from_min_size(Pos2::new(0.0, 0.0), tex.size), create_image_widget(tex));
+                use crate::skin_generated::SkinImage::*;
+                let canvas = ui.input().screen_rect();
+                let origin = window_layout.main;
+                let main_focused = window_layout.is_focused(SkinWindow::Main);
+                let main_height = if window_layout.main_shaded { TITLE_BAR_HEIGHT } else { tex.size.y };
+                let equalizer_height = match (*show_equalizer, window_layout.equalizer_shaded) {
+                    (false, _) => None,
+                    (true, true) => Some(TITLE_BAR_HEIGHT),
+                    (true, false) => Some(skin_textures.get(EqWindow).unwrap().size.y),
+                };
+
+                let status = player.as_ref().map(|player| player.status()).unwrap_or_default();
+                let fraction = match status.duration {
+                    Some(duration) if status.state != PlaybackState::Stopped && duration.as_secs_f32() > 0.0 => Some(status.position.as_secs_f32() / duration.as_secs_f32()),
+                    _ => None,
+                };
+                // Set by either the big transport buttons or the little ones in the shaded window
+                let mut transport = None;
+
+                if window_layout.main_shaded {
+                    let shade = skin_textures.get(if main_focused { MainShadeSelected } else { MainShade }).unwrap();
+                    ui.put(get_abs_image_rect(shade, origin), create_image_widget(shade));
+                    // The little buttons are part of the shade image, there's nothing to draw.
+                    // They sense drags too, so pressing one doesn't pick the window up instead.
+                    for (x, width, button) in &[(169.0, 8.0, ButtonPrev), (177.0, 10.0, ButtonPlay), (187.0, 10.0, ButtonPause), (197.0, 9.0, ButtonStop), (206.0, 9.0, ButtonNext), (216.0, 9.0, ButtonEject)] {
+                        let rect = Rect::from_min_size(origin + egui::Vec2::new(*x, 2.0), egui::Vec2::new(*width, 10.0));
+                        if ui.interact(rect, ui.id().with(("main shade", *x as u32)), egui::Sense::click_and_drag()).clicked() {
+                            transport = Some(*button);
+                        }
+                    }
 
+                    // Minutes and seconds in the TEXT.BMP font, with a gap for the colon that's in the shade image
+                    if status.state != PlaybackState::Stopped {
+                        let shown = match (*show_remaining_time, status.duration) {
+                            (true, Some(duration)) => duration.checked_sub(status.position).unwrap_or_default(),
+                            _ => status.position,
+                        };
+                        let seconds = shown.as_secs();
+                        let time_rect = Rect::from_min_size(origin + egui::Vec2::new(125.0, 4.0), egui::Vec2::new(31.0, 6.0));
+                        let sign = if *show_remaining_time && status.duration.is_some() { "-" } else { " " };
+                        paint_text(ui, skin_textures, sign, time_rect.min, time_rect);
+                        paint_text(ui, skin_textures, &format!("{:02}", seconds / 60 % 100), time_rect.min + egui::Vec2::new(5.0, 0.0), time_rect);
+                        paint_text(ui, skin_textures, &format!("{:02}", seconds % 60), time_rect.min + egui::Vec2::new(21.0, 0.0), time_rect);
+                        if ui.interact(time_rect, ui.id().with("main shade time"), egui::Sense::click_and_drag()).clicked() {
+                            *show_remaining_time = !*show_remaining_time;
+                        }
+                    }
 
-                use crate::skin_generated::SkinImage::*;
-                struct ImageButtonSpec {
-                    x: u32,
-                    y: u32,
-                    texture: SkinImage,
-                    hover_texture: SkinImage,
-                    click_texture: SkinImage,
+                    let thumbs = ShadeSliderGraphics {
+                        thumb_left: skin_textures.get(MainShadePositionThumbLeft).unwrap(),
+                        thumb: skin_textures.get(MainShadePositionThumb).unwrap(),
+                        thumb_right: skin_textures.get(MainShadePositionThumbRight).unwrap(),
+                    };
+                    let thumb = *thumbs.thumb_at(seek_bar.preview().or(fraction).unwrap_or_default());
+                    let seek_textures = SeekBarGraphics::<&LoadedTexture> {
+                        bar: skin_textures.get(MainShadePositionBackground).unwrap(),
+                        thumb,
+                        thumb_pressed: thumb,
+                    };
+                    ui.put(get_abs_image_rect(seek_textures.bar, origin + egui::Vec2::new(226.0, 4.0)), SeekBar::new(seek_bar, fraction, seek_textures));
+                } else {
+                    ui.put(egui::Rect::from_min_size(origin, tex.size), create_image_widget(tex));
+                    let title_bar = skin_textures.get(if main_focused { TitleBarSelected } else { TitleBar }).unwrap();
+                    ui.put(get_abs_image_rect(title_bar, origin), create_image_widget(title_bar));
+                    struct ImageButtonSpec {
+                        x: u32,
+                        y: u32,
+                        texture: SkinImage,
+                        hover_texture: SkinImage,
+                        click_texture: SkinImage,
+                    }
+                    for i in &[
+                        ImageButtonSpec { x: 16, y: 88, texture: ButtonPrev, hover_texture: ButtonPrev, click_texture: ButtonPrevPressed },
+                        ImageButtonSpec { x: 39, y: 88, texture: ButtonPlay, hover_texture: ButtonPlay, click_texture: ButtonPlayPressed },
+                        ImageButtonSpec { x: 62, y: 88, texture: ButtonPause, hover_texture: ButtonPause, click_texture: ButtonPausePressed },
+                        ImageButtonSpec { x: 85, y: 88, texture: ButtonStop, hover_texture: ButtonStop, click_texture: ButtonStopPressed },
+                        ImageButtonSpec { x: 108, y: 88, texture: ButtonNext, hover_texture: ButtonNext, click_texture: ButtonNextPressed },
+                        ImageButtonSpec { x: 136, y: 88, texture: ButtonEject, hover_texture: ButtonEject, click_texture: ButtonEjectPressed },
+                    ] {
+                        let neutral_texture = skin_textures.get(i.texture).unwrap();
+                        let response = ui.put(get_abs_image_rect(neutral_texture, origin + egui::Vec2::new(i.x as f32, i.y as f32)),
+                            MultiImageButton::new(
+                            neutral_texture,
+                            skin_textures.get(i.hover_texture).unwrap(),
+                            skin_textures.get(i.click_texture).unwrap()));
+                        if response.clicked() {
+                            transport = Some(i.texture);
+                        }
+                    }
+                    let slider_textures = SliderGraphics::<&LoadedTexture> {
+                        bar: iter_VolumeSliderBar().map(|i| skin_textures.get(i).unwrap()).collect(),
+                        handle: skin_textures.get(SkinImage::VolumeSliderButton).unwrap(),
+                        handle_clicked: skin_textures.get(SkinImage::VolumeSliderButtonPressed).unwrap(),
+                    }; 
+                    let response = ui.put(egui::Rect::from_min_size(origin + egui::Vec2::new(106.0, 0.0), tex.size), WinampSlider::new(volume, 0.0..=1.0, slider_textures));
+                    if let (true, Some(player)) = (response.changed(), player.as_mut()) {
+                        player.set_volume(*volume);
+                    }
+
+                    let balance_textures = SliderGraphics::<&LoadedTexture> {
+                        bar: iter_BalanceSliderBar().map(|i| skin_textures.get(i).unwrap()).collect(),
+                        handle: skin_textures.get(SkinImage::BalanceSliderButton).unwrap(),
+                        handle_clicked: skin_textures.get(SkinImage::BalanceSliderButtonPressed).unwrap(),
+                    };
+                    let balance_slider = WinampSlider::new(balance, -100..=100, balance_textures).mirrored_frames(true).snap(0.0, 12.0);
+                    let response = ui.put(egui::Rect::from_min_size(origin + egui::Vec2::new(177.0, 57.0), egui::Vec2::new(38.0, 13.0)), balance_slider);
+                    if let (true, Some(player)) = (response.changed(), player.as_mut()) {
+                        player.set_balance(*balance as f32 / 100.0);
+                    }
+
+                    // NUMS_EX.BMP has a proper minus sign, so it wins if the skin has one.
+                    let has_nums_ex = skin_images.as_ref().is_some_and(|skin| !skin.borrowed_files.iter().any(|file| file == "NUMS_EX.BMP"));
+                    let digit_textures = if has_nums_ex {
+                        DigitGraphics::<&LoadedTexture> {
+                            digits: iter_NumbersExDigit().map(|i| skin_textures.get(i).unwrap()).collect(),
+                            minus: skin_textures.get(SkinImage::NumbersExMinus).unwrap(),
+                            no_minus: skin_textures.get(SkinImage::NumbersExBlank).unwrap(),
+                        }
+                    } else {
+                        DigitGraphics::<&LoadedTexture> {
+                            digits: iter_NumbersDigit().map(|i| skin_textures.get(i).unwrap()).collect(),
+                            minus: skin_textures.get(SkinImage::NumbersMinus).unwrap(),
+                            no_minus: skin_textures.get(SkinImage::NumbersNoMinus).unwrap(),
+                        }
+                    };
+                    let mut time_display = TimeDisplay::new(show_remaining_time, digit_textures);
+                    if let Some(status) = player.as_ref().map(|player| player.status()) {
+                        if status.state != PlaybackState::Stopped {
+                            time_display = time_display.position(status.position, status.duration).blink(status.state == PlaybackState::Paused);
+                        }
+                    }
+                    ui.put(egui::Rect::from_min_size(origin + egui::Vec2::new(36.0, 26.0), egui::Vec2::new(63.0, 13.0)), time_display);
+
+                    let seek_textures = SeekBarGraphics::<&LoadedTexture> {
+                        bar: skin_textures.get(SkinImage::PositionBar).unwrap(),
+                        thumb: skin_textures.get(SkinImage::PositionBarThumb).unwrap(),
+                        thumb_pressed: skin_textures.get(SkinImage::PositionBarThumbPressed).unwrap(),
+                    };
+                    ui.put(get_abs_image_rect(seek_textures.bar, origin + egui::Vec2::new(16.0, 72.0)), SeekBar::new(seek_bar, fraction, seek_textures));
+
+                    let title = match (seek_bar.preview(), status.duration) {
+                        (Some(target), Some(duration)) => {
+                            let target = duration.mul_f32(target).as_secs();
+                            format!("SEEK TO: {:02}:{:02}/{:02}:{:02}", target / 60, target % 60, duration.as_secs() / 60, duration.as_secs() % 60)
+                        }
+                        _ => playlist.current().map(|index| {
+                            let entry = &playlist.entries()[index];
+                            match status.duration.or(entry.duration) {
+                                Some(duration) => format!("{}. {} ({}:{:02})", index + 1, entry.display_title(), duration.as_secs() / 60, duration.as_secs() % 60),
+                                None => format!("{}. {}", index + 1, entry.display_title()),
+                            }
+                        }).unwrap_or_default(),
+                    };
+                    ui.put(egui::Rect::from_min_size(origin + egui::Vec2::new(111.0, 27.0), egui::Vec2::new(154.0, 6.0)), Marquee::new(&title, 154.0, title_marquee, skin_textures));
+
+                    // Stream details go blank when stopped, like the time.
+                    let spec = status.spec.filter(|_| status.state != PlaybackState::Stopped);
+                    let kbps = match spec.and(status.bitrate) {
+                        // Only three characters fit, so uncompressed audio shows in hundreds, e.g. "14H" for a CD.
+                        Some(bitrate) if bitrate >= 1000 => format!("{:>2}H", bitrate / 100),
+                        Some(bitrate) => format!("{:>3}", bitrate),
+                        None => String::new(),
+                    };
+                    let kbps_rect = Rect::from_min_size(origin + egui::Vec2::new(111.0, 43.0), egui::Vec2::new(15.0, 6.0));
+                    paint_text(ui, skin_textures, &kbps, kbps_rect.min, kbps_rect);
+                    let khz = spec.map(|spec| format!("{:>2}", (spec.sample_rate + 500) / 1000)).unwrap_or_default();
+                    let khz_rect = Rect::from_min_size(origin + egui::Vec2::new(156.0, 43.0), egui::Vec2::new(10.0, 6.0));
+                    paint_text(ui, skin_textures, &khz, khz_rect.min, khz_rect);
+
+                    let channels = spec.map(|spec| spec.channels);
+                    let mono = skin_textures.get(if channels == Some(1) { MonoOn } else { Mono }).unwrap();
+                    ui.put(get_abs_image_rect(mono, origin + egui::Vec2::new(212.0, 41.0)), create_image_widget(mono));
+                    let stereo = skin_textures.get(if channels.is_some_and(|channels| channels >= 2) { StereoOn } else { Stereo }).unwrap();
+                    ui.put(get_abs_image_rect(stereo, origin + egui::Vec2::new(239.0, 41.0)), create_image_widget(stereo));
+
+                    let (shuffle, shuffle_pressed) = if play_order.shuffle { (ShuffleOn, ShuffleOnPressed) } else { (Shuffle, ShufflePressed) };
+                    let shuffle = skin_textures.get(shuffle).unwrap();
+                    if ui.put(get_abs_image_rect(shuffle, origin + egui::Vec2::new(164.0, 89.0)), MultiImageButton::new(shuffle, shuffle, skin_textures.get(shuffle_pressed).unwrap())).clicked() {
+                        play_order.set_shuffle(!play_order.shuffle);
+                    }
+
+                    // The skin only has on and off, so which kind of repeat it is goes in the tooltip.
+                    let (repeat, repeat_pressed) = if play_order.repeat != RepeatMode::Off { (RepeatOn, RepeatOnPressed) } else { (Repeat, RepeatPressed) };
+                    let repeat = skin_textures.get(repeat).unwrap();
+                    let repeat_tooltip = match play_order.repeat {
+                        RepeatMode::Off => "Repeat: off",
+                        RepeatMode::All => "Repeat: playlist",
+                        RepeatMode::One => "Repeat: this track",
+                    };
+                    if ui.put(get_abs_image_rect(repeat, origin + egui::Vec2::new(210.0, 89.0)), MultiImageButton::new(repeat, repeat, skin_textures.get(repeat_pressed).unwrap())).on_hover_text(repeat_tooltip).clicked() {
+                        play_order.repeat = play_order.repeat.cycled();
+                    }
+
+                    let (eq_toggle, eq_toggle_pressed) = if *show_equalizer { (EqToggleOn, EqToggleOnPressed) } else { (EqToggle, EqTogglePressed) };
+                    let eq_toggle = skin_textures.get(eq_toggle).unwrap();
+                    if ui.put(get_abs_image_rect(eq_toggle, origin + egui::Vec2::new(219.0, 58.0)), MultiImageButton::new(eq_toggle, eq_toggle, skin_textures.get(eq_toggle_pressed).unwrap())).clicked() {
+                        *show_equalizer = !*show_equalizer;
+                    }
+
+                    let (pl_toggle, pl_toggle_pressed) = if *show_playlist { (PlaylistToggleOn, PlaylistToggleOnPressed) } else { (PlaylistToggle, PlaylistTogglePressed) };
+                    let pl_toggle = skin_textures.get(pl_toggle).unwrap();
+                    if ui.put(get_abs_image_rect(pl_toggle, origin + egui::Vec2::new(242.0, 58.0)), MultiImageButton::new(pl_toggle, pl_toggle, skin_textures.get(pl_toggle_pressed).unwrap())).clicked() {
+                        *show_playlist = !*show_playlist;
+                    }
+
+                }
+
+                if let (Some(target), Some(duration), Some(player)) = (seek_bar.take_seek(), status.duration, player.as_ref()) {
+                    player.seek(duration.mul_f32(target));
+                }
+                match (transport, player.as_ref()) {
+                    (Some(ButtonEject), _) => *open_file_window = true,
+                    // With nothing loaded yet, play starts the playlist like Winamp: the current entry, or the first.
+                    (Some(ButtonPlay), Some(_)) if status.track.is_none() => {
+                        if playlist.current().is_none() {
+                            playlist.set_current(Some(0));
+                        }
+                        play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+                    }
+                    (Some(ButtonPlay), Some(player)) => player.play(),
+                    (Some(ButtonPause), Some(player)) => player.pause(),
+                    (Some(ButtonStop), Some(player)) => player.stop(),
+                    (Some(ButtonPrev), _) => {
+                        let index = play_order.previous(playlist);
+                        if index.is_some() {
+                            playlist.set_current(index);
+                            play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+                        }
+                    }
+                    (Some(ButtonNext), _) => {
+                        let index = play_order.next(playlist);
+                        if index.is_some() {
+                            playlist.set_current(index);
+                            play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+                        }
+                    }
+                    _ => (),
+                }
+
+                // Everything left of the buttons moves the window. In the shaded window the mini controls sit on top of it.
+                let title_bar = ui.interact(Rect::from_min_size(origin, egui::Vec2::new(244.0, TITLE_BAR_HEIGHT)), ui.id().with("main title bar"), egui::Sense::click_and_drag());
+                window_layout.drag(SkinWindow::Main, &title_bar, origin, main_height, equalizer_height, canvas);
+                let title_button = |ui: &mut egui::Ui, x: f32, image, pressed| {
+                    let texture = skin_textures.get(image).unwrap();
+                    ui.put(get_abs_image_rect(texture, origin + egui::Vec2::new(x, 3.0)), MultiImageButton::new(texture, texture, skin_textures.get(pressed).unwrap()))
+                };
+                // eframe 0.14 can't minimise its own window, so the button is only drawn, not clickable.
+                let minimize = skin_textures.get(TitleBarMinimize).unwrap();
+                minimize.image().paint_at(ui, get_abs_image_rect(minimize, origin + egui::Vec2::new(244.0, 3.0)));
+                let (shade, shade_pressed) = if window_layout.main_shaded { (TitleBarUnshade, TitleBarUnshadePressed) } else { (TitleBarShade, TitleBarShadePressed) };
+                if title_button(ui, 254.0, shade, shade_pressed).clicked() {
+                    window_layout.toggle_shade(SkinWindow::Main);
+                }
+                if title_button(ui, 264.0, TitleBarClose, TitleBarClosePressed).clicked() {
+                    quit = true;
+                }
+                let mut windows = vec![(SkinWindow::Main, Rect::from_min_size(origin, egui::Vec2::new(tex.size.x, main_height)))];
+
+                if *show_equalizer {
+                    let graph_colors = skin_images.as_ref().and_then(|skin| skin.images.get(EqGraphLineColors)).map(|image| image.pixels.clone()).unwrap_or_default();
+                    let equalizer_origin = window_layout.equalizer_origin(main_height);
+                    let focused = window_layout.is_focused(SkinWindow::Equalizer);
+                    let events = if window_layout.equalizer_shaded {
+                        equalizer_shade(ui, equalizer_origin, focused, volume, balance, skin_textures)
+                    } else {
+                        equalizer_window(ui, equalizer_origin, focused, equalizer, equalizer_auto, skin_textures, &graph_colors)
+                    };
+                    if let Some(title_bar) = &events.title_bar {
+                        window_layout.drag(SkinWindow::Equalizer, title_bar, equalizer_origin, main_height, equalizer_height, canvas);
+                    }
+                    let equalizer_size = egui::Vec2::new(skin_textures.get(EqWindow).unwrap().size.x, equalizer_height.unwrap_or_default());
+                    windows.push((SkinWindow::Equalizer, Rect::from_min_size(equalizer_origin, equalizer_size)));
+                    if let (true, Some(player)) = (events.changed, player.as_ref()) {
+                        player.set_equalizer(*equalizer);
+                    }
+                    if let (true, Some(player)) = (events.volume_changed, player.as_mut()) {
+                        player.set_volume(*volume);
+                    }
+                    if let (true, Some(player)) = (events.balance_changed, player.as_mut()) {
+                        player.set_balance(*balance as f32 / 100.0);
+                    }
+                    if events.shade {
+                        window_layout.toggle_shade(SkinWindow::Equalizer);
+                    }
+                    if events.closed {
+                        *show_equalizer = false;
+                    }
+                    if events.presets.is_some_and(|presets| presets.clicked()) {
+                        presets_window.open = !presets_window.open;
+                    }
                 }
-                for i in &[
-                    ImageButtonSpec { x: 16, y: 88, texture: ButtonPrev, hover_texture: ButtonPrev, click_texture: ButtonPrevPressed },
-                    ImageButtonSpec { x: 39, y: 88, texture: ButtonPlay, hover_texture: ButtonPlay, click_texture: ButtonPlayPressed },
-                    ImageButtonSpec { x: 62, y: 88, texture: ButtonPause, hover_texture: ButtonPause, click_texture: ButtonPausePressed },
-                    ImageButtonSpec { x: 85, y: 88, texture: ButtonStop, hover_texture: ButtonStop, click_texture: ButtonStopPressed },
-                    ImageButtonSpec { x: 108, y: 88, texture: ButtonNext, hover_texture: ButtonNext, click_texture: ButtonNextPressed },
-                    ImageButtonSpec { x: 136, y: 88, texture: ButtonEject, hover_texture: ButtonEject, click_texture: ButtonEjectPressed },
-                ] {
-                    let neutral_texture = skin_textures.get(i.texture).unwrap();
-                    ui.put(get_abs_image_rect(neutral_texture, i.x as f32, i.y as f32),
-                        MultiImageButton::new(
-                        neutral_texture,
-                        skin_textures.get(i.hover_texture).unwrap(),
-                        skin_textures.get(i.click_texture).unwrap()));
+
+                if *show_playlist {
+                    let playlist_origin = window_layout.playlist_origin(main_height, equalizer_height);
+                    let focused = window_layout.is_focused(SkinWindow::Playlist);
+                    let style = skin_images.as_ref().map(|skin| skin.playlist_style.clone()).unwrap_or_default();
+                    let (events, playlist_size) = if window_layout.playlist_shaded {
+                        let size = egui::Vec2::new(playlist_editor.size().x, TITLE_BAR_HEIGHT);
+                        (playlist_shade(ui, playlist_origin, focused, playlist, playlist_editor, skin_textures), size)
+                    } else {
+                        let editor = PlaylistEditor { playlist, state: playlist_editor, dialog: playlist_dialog };
+                        (playlist_window(ui, playlist_origin, focused, editor, skin_textures, &style), playlist_editor.size())
+                    };
+                    if let Some(title_bar) = &events.title_bar {
+                        window_layout.drag(SkinWindow::Playlist, title_bar, playlist_origin, main_height, equalizer_height, canvas);
+                    }
+                    windows.push((SkinWindow::Playlist, Rect::from_min_size(playlist_origin, playlist_size)));
+                    if events.shade {
+                        window_layout.toggle_shade(SkinWindow::Playlist);
+                    }
+                    if let Some(index) = events.play {
+                        play_order.jumped(playlist, index);
+                        playlist.set_current(Some(index));
+                        play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+                    }
+                    if events.closed {
+                        *show_playlist = false;
+                    }
                 }
-                let slider_textures = SliderGraphics::<&LoadedTexture> {
-                    bar: iter_VolumeSliderBar().map(|i| skin_textures.get(i).unwrap()).collect(),
-                    handle: skin_textures.get(SkinImage::VolumeSliderButton).unwrap(),
-                    handle_clicked: skin_textures.get(SkinImage::VolumeSliderButtonPressed).unwrap(),
-                }; 
-                ui.put(egui::Rect::from_min_size(Pos2::new(106.0, 0.0), tex.size), WinampSlider::new(volume, 0.0..=1.0, slider_textures));
+
+                let pointer = &ui.input().pointer;
+                let pressed = if pointer.any_pressed() { pointer.interact_pos() } else { None };
+                window_layout.click_focus(pressed, &windows);
             }
         });
+        if quit {
+            frame.quit();
+        }
+
+        playlist_dialog.show(ctx, playlist, title_format);
+
+        if !skin_messages.is_empty() {
+            let mut open = true;
+            let mut done = false;
+            egui::Window::new("Skin").open(&mut open).collapsible(false).show(ctx, |ui| {
+                for message in skin_messages.iter() {
+                    ui.label(message);
+                }
+                if ui.button("OK").clicked() {
+                    done = true;
+                }
+            });
+            if !open || done {
+                skin_messages.clear();
+            }
+        }
 
+        if presets_window.show(ctx, eq_presets, equalizer) {
+            if let Some(player) = player.as_ref() {
+                player.set_equalizer(*equalizer);
+            }
+        }
+
+        if *open_file_window {
+            egui::Window::new("Open file").collapsible(false).show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(open_file_path);
+                    if ui.button("Open").clicked() {
+                        let path = PathBuf::from(open_file_path.as_str());
+                        // A playlist replaces the current one, anything else is added to the end of it.
+                        let index = if PlaylistFormat::from_path(&path).is_some() {
+                            match Playlist::load(&path) {
+                                Ok(loaded) => {
+                                    *playlist = loaded;
+                                    Some(0)
+                                }
+                                Err(e) => {
+                                    playlist_dialog.open_with_error(PlaylistPrompt::Load, path.display().to_string(), e.to_string());
+                                    None
+                                }
+                            }
+                        } else {
+                            Some(playlist.add(PlaylistEntry::new(Location::Path(path))))
+                        };
+                        playlist.set_current(index);
+
+                        play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+                        *open_file_window = false;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        *open_file_window = false;
+                    }
+                });
+            });
+        }
+
+        if player.as_ref().is_some_and(|player| player.take_finished()) {
+            let index = play_order.after_finished(playlist);
+            if index.is_some() {
+                playlist.set_current(index);
+                play_current(playlist, player.as_ref(), equalizer, eq_presets, *equalizer_auto);
+            }
+        }
+
+        // Tags are read in the background, so adding a big folder doesn't hold everything up.
+        if let Some(tag_reader) = tag_reader {
+            if playlist.read_tags(tag_reader, title_format) {
+                ctx.request_repaint();
+            }
+        }
+
+        // Keep redrawing while something is playing so the position stays current, or paused so the time blinks.
+        if let Some(player) = player.as_ref() {
+            if player.status().state != PlaybackState::Stopped {
+                ctx.request_repaint();
+            }
+        }
 
         /*
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
@@ -221,7 +723,7 @@ impl epi::App for TemplateApp {
                 egui::ScrollArea::auto_sized().show(ui, |ui| {
                     for (name, texture) in skin_textures.iter() {
                         ui.heading(name.as_ref());
-                        ui.add(egui::Image::new(texture.texture, texture.size));
+                        ui.add(texture.image());
                     }
                 });
             });
@@ -230,9 +732,32 @@ impl epi::App for TemplateApp {
 }
 
 fn create_image_widget(texture: &LoadedTexture) -> egui::Image {
-    egui::Image::new(texture.texture, texture.size)
+    texture.image()
 }
 
-fn get_abs_image_rect(texture: &LoadedTexture, x: f32, y: f32) -> Rect {
-    egui::Rect::from_min_size(Pos2::new(x, y), texture.size)
-}
\ No newline at end of file
+fn get_abs_image_rect(texture: &LoadedTexture, pos: Pos2) -> Rect {
+    egui::Rect::from_min_size(pos, texture.size)
+}
+
+/// Starts the playlist's current entry, loading its equalizer preset first if auto-load is on.
+fn play_current(playlist: &Playlist, player: Option<&PlaybackEngine>, equalizer: &mut EqSettings, eq_presets: &PresetStore, equalizer_auto: bool) {
+    let (path, section) = match playlist.current_entry() {
+        Some(entry) => match entry.location.path() {
+            Some(path) => (path, entry.section),
+            None => return,
+        },
+        None => return,
+    };
+    // Auto-load presets are saved under the name of the file they're for, like Winamp.
+    if equalizer_auto {
+        let file_name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        if let Some(preset) = eq_presets.get(&file_name) {
+            preset.apply(equalizer);
+        }
+    }
+    if let Some(player) = player {
+        player.set_equalizer(*equalizer);
+        player.load(path.to_path_buf(), section);
+        player.play();
+    }
+}
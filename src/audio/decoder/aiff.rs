@@ -0,0 +1,234 @@
+use std::{convert::TryFrom, fs::File, io::{BufReader, Read, Seek, SeekFrom}, path::Path, time::Duration};
+
+use super::{Decoder, duration_of, frames_at};
+use crate::audio::{AudioError, StreamSpec};
+
+/// Frames returned per packet.
+const PACKET_FRAMES: usize = 1024;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Encoding {
+    /// Plain AIFF, and AIFC 'NONE'. Samples are left-justified in their bytes.
+    BigEndianInt,
+    /// AIFC 'sowt', as written by a lot of Mac software.
+    LittleEndianInt,
+    Float32,
+    Float64,
+}
+
+/// AIFF and uncompressed AIFF-C.
+pub struct AiffDecoder {
+    reader: BufReader<File>,
+    spec: StreamSpec,
+    encoding: Encoding,
+    bytes_per_sample: usize,
+    data_start: u64,
+    total_frames: u64,
+    position: u64,
+}
+
+impl AiffDecoder {
+    pub fn open(path: &Path) -> Result<Self, AudioError> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut form = [0u8; 12];
+        reader.read_exact(&mut form)?;
+        if &form[0..4] != b"FORM" {
+            return Err(AudioError::Malformed("missing FORM chunk".to_string()));
+        }
+        let is_aifc = &form[8..12] == b"AIFC";
+
+        let mut comm = None;
+        let mut data_start = None;
+        // Walk the chunks until we have both the format and the sample data.
+        while comm.is_none() || data_start.is_none() {
+            let mut chunk_header = [0u8; 8];
+            if reader.read_exact(&mut chunk_header).is_err() {
+                break;
+            }
+            let size = u32::from_be_bytes([chunk_header[4], chunk_header[5], chunk_header[6], chunk_header[7]]) as u64;
+            // Chunks are padded to an even length.
+            let padded_size = size + (size & 1);
+            let chunk_start = reader.stream_position()?;
+
+            match &chunk_header[0..4] {
+                b"COMM" => {
+                    let mut data = vec![0u8; size as usize];
+                    reader.read_exact(&mut data)?;
+                    comm = Some(data);
+                }
+                b"SSND" => {
+                    let mut offset = [0u8; 4];
+                    reader.read_exact(&mut offset)?;
+                    data_start = Some(chunk_start + 8 + u32::from_be_bytes(offset) as u64);
+                }
+                _ => (),
+            }
+            reader.seek(SeekFrom::Start(chunk_start + padded_size))?;
+        }
+
+        let comm = comm.ok_or_else(|| AudioError::Malformed("missing COMM chunk".to_string()))?;
+        let data_start = data_start.ok_or_else(|| AudioError::Malformed("missing SSND chunk".to_string()))?;
+        if comm.len() < 18 {
+            return Err(AudioError::Malformed("COMM chunk too short".to_string()));
+        }
+
+        let channels = u16::from_be_bytes([comm[0], comm[1]]) as usize;
+        let total_frames = u32::from_be_bytes([comm[2], comm[3], comm[4], comm[5]]) as u64;
+        let bits = u16::from_be_bytes([comm[6], comm[7]]) as usize;
+        let sample_rate = extended_to_f64(&comm[8..18]).round() as u32;
+
+        let encoding = if is_aifc && comm.len() >= 22 {
+            match &comm[18..22] {
+                b"NONE" | b"twos" => Encoding::BigEndianInt,
+                b"sowt" => Encoding::LittleEndianInt,
+                b"fl32" | b"FL32" => Encoding::Float32,
+                b"fl64" | b"FL64" => Encoding::Float64,
+                other => return Err(AudioError::UnsupportedFormat(format!("AIFF-C compression {}", String::from_utf8_lossy(other)))),
+            }
+        } else {
+            Encoding::BigEndianInt
+        };
+        let bytes_per_sample = match encoding {
+            Encoding::Float32 => 4,
+            Encoding::Float64 => 8,
+            _ => bits.div_ceil(8),
+        };
+        if channels == 0 || sample_rate == 0 || bytes_per_sample == 0 || bytes_per_sample > 8 {
+            return Err(AudioError::Malformed(format!("{} channels, {} Hz, {} bits", channels, sample_rate, bits)));
+        }
+
+        reader.seek(SeekFrom::Start(data_start))?;
+        Ok(Self {
+            reader,
+            spec: StreamSpec { sample_rate, channels },
+            encoding,
+            bytes_per_sample,
+            data_start,
+            total_frames,
+            position: 0,
+        })
+    }
+
+    fn decode_sample(&self, bytes: &[u8]) -> f32 {
+        match self.encoding {
+            Encoding::Float32 => f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            Encoding::Float64 => {
+                let mut b = [0u8; 8];
+                b.copy_from_slice(bytes);
+                f64::from_be_bytes(b) as f32
+            }
+            Encoding::BigEndianInt | Encoding::LittleEndianInt => {
+                // Assemble into the top of an i64 so the sign comes along, whatever the width.
+                let mut value: i64 = 0;
+                for i in 0..self.bytes_per_sample {
+                    let byte = match self.encoding {
+                        Encoding::BigEndianInt => bytes[i],
+                        _ => bytes[self.bytes_per_sample - 1 - i],
+                    };
+                    value |= (byte as i64) << (56 - 8 * i);
+                }
+                (value as f64 / i64::MAX as f64) as f32
+            }
+        }
+    }
+}
+
+impl Decoder for AiffDecoder {
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        Some(duration_of(self.total_frames, self.spec.sample_rate))
+    }
+
+    fn bitrate(&self) -> Option<u32> {
+        u32::try_from(self.spec.sample_rate as u64 * (self.spec.channels * self.bytes_per_sample * 8) as u64 / 1000).ok()
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        let frame = frames_at(position, self.spec.sample_rate).min(self.total_frames);
+        let frame_bytes = (self.bytes_per_sample * self.spec.channels) as u64;
+        self.reader.seek(SeekFrom::Start(self.data_start + frame * frame_bytes))?;
+        self.position = frame;
+        Ok(duration_of(frame, self.spec.sample_rate))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        let frames = (self.total_frames - self.position).min(PACKET_FRAMES as u64) as usize;
+        if frames == 0 {
+            return Ok(None);
+        }
+        let mut data = vec![0u8; frames * self.spec.channels * self.bytes_per_sample];
+        self.reader.read_exact(&mut data)?;
+        self.position += frames as u64;
+        Ok(Some(data.chunks_exact(self.bytes_per_sample).map(|s| self.decode_sample(s)).collect()))
+    }
+}
+
+/// AIFF stores its sample rate as an 80-bit IEEE 754 extended float.
+fn extended_to_f64(bytes: &[u8]) -> f64 {
+    let sign = if bytes[0] & 0x80 != 0 { -1.0 } else { 1.0 };
+    let exponent = (((bytes[0] & 0x7f) as i32) << 8) | bytes[1] as i32;
+    let mut mantissa_bytes = [0u8; 8];
+    mantissa_bytes.copy_from_slice(&bytes[2..10]);
+    let mantissa = u64::from_be_bytes(mantissa_bytes);
+    if exponent == 0 && mantissa == 0 {
+        return 0.0;
+    }
+    sign * mantissa as f64 * 2f64.powi(exponent - 16383 - 63)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16-bit mono AIFF at 8 kHz.
+    fn write_fixture(name: &str, samples: &[i16]) -> std::path::PathBuf {
+        let mut comm = 1u16.to_be_bytes().to_vec();
+        comm.extend((samples.len() as u32).to_be_bytes());
+        comm.extend(16u16.to_be_bytes());
+        comm.extend([0x40, 0x0b, 0xfa, 0, 0, 0, 0, 0, 0, 0]);
+        let mut ssnd = vec![0u8; 8];
+        ssnd.extend(samples.iter().flat_map(|s| s.to_be_bytes()));
+
+        let mut form = b"AIFF".to_vec();
+        for (id, chunk) in [(b"COMM", comm), (b"SSND", ssnd)] {
+            form.extend(id);
+            form.extend((chunk.len() as u32).to_be_bytes());
+            form.extend(chunk);
+        }
+        let path = std::env::temp_dir().join(format!("aiff-test-{}-{}.aiff", name, std::process::id()));
+        std::fs::write(&path, [&b"FORM"[..], &(form.len() as u32).to_be_bytes(), &form].concat()).unwrap();
+        path
+    }
+
+    #[test]
+    fn extended_sample_rates() {
+        assert_eq!(extended_to_f64(&[0x40, 0x0e, 0xac, 0x44, 0, 0, 0, 0, 0, 0]), 44100.0);
+        assert_eq!(extended_to_f64(&[0x40, 0x0e, 0xbb, 0x80, 0, 0, 0, 0, 0, 0]), 48000.0);
+        assert_eq!(extended_to_f64(&[0x40, 0x0b, 0xfa, 0, 0, 0, 0, 0, 0, 0]), 8000.0);
+        assert_eq!(extended_to_f64(&[0x3f, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0]), 1.0);
+        assert_eq!(extended_to_f64(&[0xbf, 0xff, 0x80, 0, 0, 0, 0, 0, 0, 0]), -1.0);
+        assert_eq!(extended_to_f64(&[0; 10]), 0.0);
+    }
+
+    #[test]
+    fn seeking_stops_at_the_end() {
+        let samples: Vec<i16> = (0..800).map(|i| i * 40 - 16000).collect();
+        let path = write_fixture("seek", &samples);
+        let mut decoder = AiffDecoder::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(decoder.spec(), StreamSpec { sample_rate: 8000, channels: 1 });
+        assert_eq!(decoder.duration(), Some(Duration::from_millis(100)));
+
+        assert_eq!(decoder.seek(Duration::from_millis(75)).unwrap(), Duration::from_millis(75));
+        let packet = decoder.next_packet().unwrap().unwrap();
+        assert_eq!(packet.len(), 200);
+        assert!((packet[0] - samples[600] as f32 / 32768.0).abs() < 1e-6);
+
+        assert_eq!(decoder.seek(Duration::from_secs(10)).unwrap(), Duration::from_millis(100));
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+}
@@ -0,0 +1,167 @@
+use std::{fs::{self, File}, io::{BufReader, Read, Seek, SeekFrom}, mem, path::{Path, PathBuf}, time::Duration};
+
+use claxon::{frame::FrameReader, input::BufferedReader};
+
+use super::{Decoder, duration_of, frames_at};
+use crate::audio::{AudioError, StreamSpec};
+
+const SEEKTABLE_BLOCK: u8 = 3;
+/// Seek points with this sample number are unused slots.
+const PLACEHOLDER_POINT: u64 = u64::MAX;
+
+#[derive(Debug, Copy, Clone)]
+struct SeekPoint {
+    /// First sample (per channel) of the target frame
+    sample: u64,
+    /// Byte offset of the target frame, from the first frame
+    offset: u64,
+}
+
+/// FLAC via claxon. claxon only reads forwards, so seeking jumps to the nearest
+/// SEEKTABLE point (or the start) and decodes up to the exact sample from there.
+pub struct FlacDecoder {
+    path: PathBuf,
+    frames: FrameReader<BufferedReader<File>>,
+    spec: StreamSpec,
+    scale: f32,
+    total_frames: Option<u64>,
+    /// Offset of the first audio frame in the file
+    audio_start: u64,
+    seek_points: Vec<SeekPoint>,
+    /// Average over the whole stream, as claxon doesn't say how big each frame is
+    bitrate: Option<u32>,
+    /// Frames still to be dropped after a seek, to land on the exact sample
+    skip: u64,
+    buffer: Vec<i32>,
+}
+
+impl FlacDecoder {
+    /// `data_offset` is where the "fLaC" marker starts, past any ID3 tag.
+    pub fn open(path: &Path, data_offset: u64) -> Result<Self, AudioError> {
+        let mut file = File::open(path)?;
+        file.seek(SeekFrom::Start(data_offset))?;
+        let streaminfo = claxon::FlacReader::new(BufReader::new(file))?.streaminfo();
+
+        let (audio_start, seek_points) = read_metadata_layout(path, data_offset)?;
+        let audio_bytes = fs::metadata(path)?.len().saturating_sub(audio_start);
+        let seconds = streaminfo.samples.map(|frames| frames as f64 / streaminfo.sample_rate as f64);
+        let bitrate = seconds.filter(|seconds| *seconds > 0.0).map(|seconds| (audio_bytes as f64 * 8.0 / seconds / 1000.0).round() as u32);
+
+        let mut decoder = Self {
+            path: path.to_path_buf(),
+            frames: open_frames(path, audio_start)?,
+            spec: StreamSpec {
+                sample_rate: streaminfo.sample_rate,
+                channels: streaminfo.channels as usize,
+            },
+            scale: (1i64 << (streaminfo.bits_per_sample - 1)) as f32,
+            total_frames: streaminfo.samples,
+            audio_start,
+            seek_points,
+            bitrate,
+            skip: 0,
+            buffer: vec![],
+        };
+        decoder.seek_points.retain(|p| p.sample != PLACEHOLDER_POINT);
+        Ok(decoder)
+    }
+}
+
+impl Decoder for FlacDecoder {
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.total_frames.map(|frames| duration_of(frames, self.spec.sample_rate))
+    }
+
+    fn bitrate(&self) -> Option<u32> {
+        self.bitrate
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        let mut target = frames_at(position, self.spec.sample_rate);
+        if let Some(total) = self.total_frames {
+            target = target.min(total);
+        }
+        let point = self
+            .seek_points
+            .iter()
+            .rev()
+            .find(|p| p.sample <= target)
+            .copied()
+            .unwrap_or(SeekPoint { sample: 0, offset: 0 });
+
+        self.frames = open_frames(&self.path, self.audio_start + point.offset)?;
+        self.skip = target - point.sample;
+        Ok(duration_of(target, self.spec.sample_rate))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        loop {
+            let block = match self.frames.read_next_or_eof(mem::take(&mut self.buffer))? {
+                Some(block) => block,
+                None => return Ok(None),
+            };
+            let duration = block.duration() as u64;
+            let skip = self.skip.min(duration);
+            self.skip -= skip;
+
+            let mut samples = Vec::with_capacity((duration - skip) as usize * self.spec.channels);
+            for i in skip as u32..duration as u32 {
+                for ch in 0..block.channels() {
+                    samples.push(block.sample(ch, i) as f32 / self.scale);
+                }
+            }
+            self.buffer = block.into_buffer();
+            if !samples.is_empty() {
+                return Ok(Some(samples));
+            }
+        }
+    }
+}
+
+fn open_frames(path: &Path, offset: u64) -> Result<FrameReader<BufferedReader<File>>, AudioError> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(offset))?;
+    Ok(FrameReader::new(BufferedReader::new(file)))
+}
+
+/// Walk the metadata block headers to find where the audio starts, picking up the seek table on the way.
+fn read_metadata_layout(path: &Path, data_offset: u64) -> Result<(u64, Vec<SeekPoint>), AudioError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    reader.seek(SeekFrom::Start(data_offset + 4))?;
+    let mut position = data_offset + 4;
+    let mut seek_points = vec![];
+
+    loop {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let is_last = header[0] & 0x80 != 0;
+        let block_type = header[0] & 0x7f;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+
+        if block_type == SEEKTABLE_BLOCK {
+            let mut table = vec![0u8; length as usize];
+            reader.read_exact(&mut table)?;
+            for point in table.chunks_exact(18) {
+                let mut sample = [0u8; 8];
+                let mut offset = [0u8; 8];
+                sample.copy_from_slice(&point[0..8]);
+                offset.copy_from_slice(&point[8..16]);
+                seek_points.push(SeekPoint {
+                    sample: u64::from_be_bytes(sample),
+                    offset: u64::from_be_bytes(offset),
+                });
+            }
+        } else {
+            reader.seek(SeekFrom::Current(length as i64))?;
+        }
+
+        position += 4 + length;
+        if is_last {
+            return Ok((position, seek_points));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fs::File, io::{Read, Seek, SeekFrom}, path::Path, time::Duration};
+
+use super::{AudioError, StreamSpec};
+
+pub mod aiff;
+pub mod flac;
+pub mod mp3;
+#[cfg(feature = "opus")]
+pub mod opus;
+pub mod vorbis;
+pub mod wav;
+
+/// How much of the start of a file we look at to figure out what it is.
+const SNIFF_BYTES: usize = 4096;
+
+/// Turns a file into interleaved f32 samples.
+pub trait Decoder {
+    fn spec(&self) -> StreamSpec;
+
+    /// None if the length can't be known without decoding the whole thing.
+    fn duration(&self) -> Option<Duration>;
+
+    /// In kbps, for the stretch of the stream decoded last where the format makes that possible,
+    /// so it moves around with VBR. Otherwise the stream's nominal or average bitrate.
+    fn bitrate(&self) -> Option<u32>;
+
+    /// Returns the position actually reached. That's exact where the format allows it;
+    /// otherwise it can be a little either side of what was asked for.
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError>;
+
+    /// The next chunk of interleaved samples, or None at the end of the stream.
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError>;
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, strum_macros::Display)]
+pub enum Format {
+    Mp3,
+    Flac,
+    Vorbis,
+    Opus,
+    Wav,
+    Aiff,
+}
+
+/// Open a file with whichever decoder its contents call for. The extension is ignored.
+pub fn open(path: &Path) -> Result<Box<dyn Decoder>, AudioError> {
+    let mut file = File::open(path)?;
+    let mut header = read_up_to(&mut file, SNIFF_BYTES)?;
+
+    // ID3v2 tags get stuck on the front of all sorts of things, look past them.
+    let data_offset = id3v2_size(&header).unwrap_or(0);
+    if data_offset > 0 {
+        file.seek(SeekFrom::Start(data_offset))?;
+        header = read_up_to(&mut file, SNIFF_BYTES)?;
+    }
+
+    let format = sniff(&header).ok_or_else(|| AudioError::UnsupportedFormat(path.display().to_string()))?;
+    Ok(match format {
+        Format::Mp3 => Box::new(mp3::Mp3Decoder::open(path, data_offset)?),
+        Format::Flac => Box::new(flac::FlacDecoder::open(path, data_offset)?),
+        Format::Vorbis => Box::new(vorbis::VorbisDecoder::open(path)?),
+        #[cfg(feature = "opus")]
+        Format::Opus => Box::new(opus::OpusDecoder::open(path)?),
+        #[cfg(not(feature = "opus"))]
+        Format::Opus => return Err(AudioError::UnsupportedFormat(format!("{} (built without opus support)", path.display()))),
+        Format::Wav => Box::new(wav::WavDecoder::open(path)?),
+        Format::Aiff => Box::new(aiff::AiffDecoder::open(path)?),
+    })
+}
+
+/// Identify a stream by its magic bytes.
+pub fn sniff(header: &[u8]) -> Option<Format> {
+    if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE" {
+        Some(Format::Wav)
+    } else if header.len() >= 12 && &header[0..4] == b"FORM" && (&header[8..12] == b"AIFF" || &header[8..12] == b"AIFC") {
+        Some(Format::Aiff)
+    } else if header.starts_with(b"fLaC") {
+        Some(Format::Flac)
+    } else if header.starts_with(b"OggS") && header.len() >= 27 {
+        // The codec is named at the start of the first packet, which follows the segment table.
+        let packet = header.get(27 + header[26] as usize..).unwrap_or(&[]);
+        if packet.starts_with(b"\x01vorbis") {
+            Some(Format::Vorbis)
+        } else if packet.starts_with(b"OpusHead") {
+            Some(Format::Opus)
+        } else {
+            None
+        }
+    } else if mp3::looks_like_mp3(header) {
+        Some(Format::Mp3)
+    } else {
+        None
+    }
+}
+
+/// Size of an ID3v2 tag at the start of `header`, including its header and footer.
+pub fn id3v2_size(header: &[u8]) -> Option<u64> {
+    if header.len() < 10 || &header[0..3] != b"ID3" {
+        return None;
+    }
+    let size = header[6..10].iter().fold(0u64, |acc, b| (acc << 7) | (*b as u64 & 0x7f));
+    let footer = if header[5] & 0x10 != 0 { 10 } else { 0 };
+    Some(10 + size + footer)
+}
+
+fn read_up_to(file: &mut File, count: usize) -> Result<Vec<u8>, AudioError> {
+    let mut data = vec![];
+    file.by_ref().take(count as u64).read_to_end(&mut data)?;
+    Ok(data)
+}
+
+/// Granule position of the last page in an Ogg file, which is where its length comes from.
+pub(crate) fn ogg_last_granule(path: &Path) -> Option<u64> {
+    const TAIL_BYTES: u64 = 65536;
+    let mut file = File::open(path).ok()?;
+    let length = file.seek(SeekFrom::End(0)).ok()?;
+    file.seek(SeekFrom::Start(length.saturating_sub(TAIL_BYTES))).ok()?;
+    let tail = read_up_to(&mut file, TAIL_BYTES as usize).ok()?;
+
+    let page = (0..tail.len().saturating_sub(14)).rev().find(|i| &tail[*i..*i + 4] == b"OggS")?;
+    let mut granule = [0u8; 8];
+    granule.copy_from_slice(&tail[page + 6..page + 14]);
+    Some(u64::from_le_bytes(granule))
+}
+
+/// Frame index for a position, at the given rate.
+pub(crate) fn frames_at(position: Duration, sample_rate: u32) -> u64 {
+    (position.as_secs_f64() * sample_rate as f64).round() as u64
+}
+
+pub(crate) fn duration_of(frames: u64, sample_rate: u32) -> Duration {
+    Duration::from_secs_f64(frames as f64 / sample_rate as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magic_bytes() {
+        assert_eq!(sniff(b"RIFF\x24\0\0\0WAVEfmt "), Some(Format::Wav));
+        assert_eq!(sniff(b"FORM\0\0\0\x2eAIFFCOMM"), Some(Format::Aiff));
+        assert_eq!(sniff(b"FORM\0\0\0\x2eAIFCFVER"), Some(Format::Aiff));
+        assert_eq!(sniff(b"fLaC\0\0\0\x22"), Some(Format::Flac));
+        assert_eq!(sniff(b"RIFF\x24\0\0\0AVI "), None);
+        assert_eq!(sniff(b""), None);
+
+        let mut ogg = b"OggS".to_vec();
+        ogg.extend([0u8; 22]);
+        ogg.push(1);
+        ogg.push(30);
+        assert_eq!(sniff(&[&ogg[..], b"\x01vorbis"].concat()), Some(Format::Vorbis));
+        assert_eq!(sniff(&[&ogg[..], b"OpusHead"].concat()), Some(Format::Opus));
+        assert_eq!(sniff(&[&ogg[..], b"\x7fFLAC"].concat()), None);
+
+        // MPEG-1 layer 3, 128 kbps, 44.1 kHz: 417 bytes to the next frame.
+        let mut mp3 = vec![0u8; 421];
+        mp3[0..4].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+        mp3[417..421].copy_from_slice(&[0xff, 0xfb, 0x90, 0x00]);
+        assert_eq!(sniff(&mp3), Some(Format::Mp3));
+        mp3[417] = 0;
+        assert_eq!(sniff(&mp3), None);
+    }
+
+    #[test]
+    fn past_an_id3v2_tag() {
+        let mut file = b"ID3\x04\x00\x00\x00\x00\x01\x7f".to_vec();
+        file.extend(vec![0xffu8; 0xff]);
+        file.extend(b"fLaC\0\0\0\x22");
+        // Sniffing doesn't look past the tag itself, `open` does that first.
+        assert_eq!(sniff(&file), None);
+        let offset = id3v2_size(&file).unwrap();
+        assert_eq!(offset, 10 + 0xff);
+        assert_eq!(sniff(&file[offset as usize..]), Some(Format::Flac));
+
+        // The size is syncsafe, and a footer adds another 10 bytes.
+        assert_eq!(id3v2_size(b"ID3\x04\x00\x10\x00\x00\x02\x01"), Some(10 + 0x101 + 10));
+        assert_eq!(id3v2_size(b"fLaC\0\0\0\x22\0\0"), None);
+        assert_eq!(id3v2_size(b"ID3\x04"), None);
+    }
+}
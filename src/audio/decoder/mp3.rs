@@ -0,0 +1,284 @@
+use std::{fs::File, io::{BufReader, Read, Seek, SeekFrom}, path::{Path, PathBuf}, time::Duration};
+
+use super::{Decoder, duration_of, frames_at};
+use crate::audio::{AudioError, StreamSpec};
+
+const BITRATES_V1_L1: [u32; 15] = [0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448];
+const BITRATES_V1_L2: [u32; 15] = [0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384];
+const BITRATES_V1_L3: [u32; 15] = [0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320];
+const BITRATES_V2_L1: [u32; 15] = [0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256];
+const BITRATES_V2_L23: [u32; 15] = [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+
+/// The bits of an MPEG audio frame header we care about.
+#[derive(Debug, Copy, Clone)]
+pub struct FrameHeader {
+    /// MPEG-1, as opposed to MPEG-2 or 2.5
+    pub mpeg1: bool,
+    pub bitrate_kbps: u32,
+    pub sample_rate: u32,
+    pub mono: bool,
+    /// Total length of the frame in bytes, header included
+    pub length: usize,
+    /// Samples per channel in the frame
+    pub samples: u32,
+}
+
+pub fn parse_frame_header(bytes: &[u8]) -> Option<FrameHeader> {
+    if bytes.len() < 4 || bytes[0] != 0xff || bytes[1] & 0xe0 != 0xe0 {
+        return None;
+    }
+    let version = (bytes[1] >> 3) & 3;
+    let layer = match (bytes[1] >> 1) & 3 {
+        1 => 3,
+        2 => 2,
+        3 => 1,
+        _ => return None,
+    };
+    let bitrate_index = (bytes[2] >> 4) as usize;
+    let rate_index = ((bytes[2] >> 2) & 3) as usize;
+    // Reserved version, free-format and invalid bitrates, reserved sample rate.
+    if version == 1 || bitrate_index == 0 || bitrate_index == 15 || rate_index == 3 {
+        return None;
+    }
+    let mpeg1 = version == 3;
+    let bitrate_kbps = match (mpeg1, layer) {
+        (true, 1) => BITRATES_V1_L1,
+        (true, 2) => BITRATES_V1_L2,
+        (true, _) => BITRATES_V1_L3,
+        (false, 1) => BITRATES_V2_L1,
+        (false, _) => BITRATES_V2_L23,
+    }[bitrate_index];
+    let sample_rate = [44100, 48000, 32000][rate_index] >> match version {
+        3 => 0,
+        2 => 1,
+        _ => 2,
+    };
+    let padding = ((bytes[2] >> 1) & 1) as u32;
+
+    let (samples, length) = match layer {
+        1 => (384, (12 * bitrate_kbps * 1000 / sample_rate + padding) * 4),
+        2 => (1152, 144 * bitrate_kbps * 1000 / sample_rate + padding),
+        _ if mpeg1 => (1152, 144 * bitrate_kbps * 1000 / sample_rate + padding),
+        _ => (576, 72 * bitrate_kbps * 1000 / sample_rate + padding),
+    };
+
+    Some(FrameHeader {
+        mpeg1,
+        bitrate_kbps,
+        sample_rate,
+        mono: bytes[3] >> 6 == 3,
+        length: length as usize,
+        samples,
+    })
+}
+
+/// Frame sync with a sane header, followed by another one where the first says it ends (if we can see that far).
+pub fn looks_like_mp3(header: &[u8]) -> bool {
+    match parse_frame_header(header) {
+        Some(frame) => match header.get(frame.length..) {
+            Some(next) if next.len() >= 4 => parse_frame_header(next).is_some(),
+            _ => true,
+        },
+        None => false,
+    }
+}
+
+/// MPEG audio (layer 3, and 1/2 along for the ride) via minimp3.
+/// minimp3 can't seek, so seeking reopens the file and decodes forwards to the exact sample.
+pub struct Mp3Decoder {
+    path: PathBuf,
+    data_offset: u64,
+    decoder: minimp3::Decoder<BufReader<File>>,
+    spec: StreamSpec,
+    duration: Option<Duration>,
+    /// The first frame gets decoded in `open` to find the format, and handed out from here.
+    pending: Option<Vec<f32>>,
+    /// Frames still to be dropped after a seek, to land on the exact sample
+    skip: u64,
+    /// Of the last frame decoded
+    bitrate: u32,
+}
+
+impl Mp3Decoder {
+    /// `data_offset` is where the audio starts, past any ID3 tag.
+    pub fn open(path: &Path, data_offset: u64) -> Result<Self, AudioError> {
+        let mut decoder = open_decoder(path, data_offset)?;
+        let first = match decoder.next_frame() {
+            Ok(frame) => frame,
+            Err(minimp3::Error::Eof) => return Err(AudioError::Malformed("no MPEG audio frames".to_string())),
+            Err(e) => return Err(e.into()),
+        };
+        let spec = StreamSpec {
+            sample_rate: first.sample_rate as u32,
+            channels: first.channels,
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            data_offset,
+            decoder,
+            spec,
+            duration: estimate_duration(path, data_offset),
+            pending: Some(to_f32(&first.data)),
+            skip: 0,
+            bitrate: first.bitrate as u32,
+        })
+    }
+}
+
+impl Decoder for Mp3Decoder {
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.duration
+    }
+
+    fn bitrate(&self) -> Option<u32> {
+        Some(self.bitrate)
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        self.decoder = open_decoder(&self.path, self.data_offset)?;
+        self.pending = None;
+        self.skip = frames_at(position, self.spec.sample_rate);
+        Ok(duration_of(self.skip, self.spec.sample_rate))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        if let Some(samples) = self.pending.take() {
+            return Ok(Some(samples));
+        }
+        loop {
+            let frame = match self.decoder.next_frame() {
+                Ok(frame) => frame,
+                Err(minimp3::Error::Eof) => return Ok(None),
+                Err(e) => return Err(e.into()),
+            };
+            self.bitrate = frame.bitrate as u32;
+            let frames = (frame.data.len() / frame.channels.max(1)) as u64;
+            let skip = self.skip.min(frames);
+            self.skip -= skip;
+            if skip < frames {
+                return Ok(Some(to_f32(&frame.data[skip as usize * frame.channels..])));
+            }
+        }
+    }
+}
+
+fn open_decoder(path: &Path, data_offset: u64) -> Result<minimp3::Decoder<BufReader<File>>, AudioError> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::Start(data_offset))?;
+    Ok(minimp3::Decoder::new(BufReader::new(file)))
+}
+
+fn to_f32(samples: &[i16]) -> Vec<f32> {
+    samples.iter().map(|s| *s as f32 / 32768.0).collect()
+}
+
+/// Use the frame count from a Xing/Info or VBRI header if there is one,
+/// otherwise assume constant bitrate and go by the file size.
+fn estimate_duration(path: &Path, data_offset: u64) -> Option<Duration> {
+    let mut file = File::open(path).ok()?;
+    let length = file.seek(SeekFrom::End(0)).ok()?;
+    file.seek(SeekFrom::Start(data_offset)).ok()?;
+    let mut data = vec![];
+    file.take(4096).read_to_end(&mut data).ok()?;
+
+    let start = (0..data.len()).find(|i| looks_like_mp3(&data[*i..]))?;
+    let frame = parse_frame_header(&data[start..])?;
+    let body = &data[start..];
+
+    let side_info = match (frame.mpeg1, frame.mono) {
+        (true, false) => 32,
+        (true, true) | (false, false) => 17,
+        (false, true) => 9,
+    };
+    let xing = body.get(4 + side_info..).unwrap_or(&[]);
+    let vbri = body.get(4 + 32..).unwrap_or(&[]);
+    let frame_count = if (xing.starts_with(b"Xing") || xing.starts_with(b"Info")) && xing.len() >= 12 && xing[7] & 1 != 0 {
+        Some(u32::from_be_bytes([xing[8], xing[9], xing[10], xing[11]]))
+    } else if vbri.starts_with(b"VBRI") && vbri.len() >= 18 {
+        Some(u32::from_be_bytes([vbri[14], vbri[15], vbri[16], vbri[17]]))
+    } else {
+        None
+    };
+
+    match frame_count {
+        Some(count) => Some(duration_of(count as u64 * frame.samples as u64, frame.sample_rate)),
+        None => {
+            let audio_bytes = length.saturating_sub(data_offset + start as u64);
+            Some(Duration::from_secs_f64(audio_bytes as f64 * 8.0 / (frame.bitrate_kbps as f64 * 1000.0)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// MPEG-1 layer 3, 128 kbps, 44.1 kHz, stereo.
+    const HEADER: [u8; 4] = [0xff, 0xfb, 0x90, 0x00];
+    const FRAME_LENGTH: usize = 417;
+
+    fn estimate(name: &str, first_frame: &[u8], frames: usize) -> Option<Duration> {
+        let mut data = vec![];
+        for i in 0..frames {
+            let mut frame = vec![0u8; FRAME_LENGTH];
+            frame[0..4].copy_from_slice(&HEADER);
+            if i == 0 {
+                frame[4..4 + first_frame.len()].copy_from_slice(first_frame);
+            }
+            data.extend(frame);
+        }
+        // Something in front of the audio, to be skipped like an ID3 tag.
+        let data = [&b"junk"[..], &data].concat();
+        let path = std::env::temp_dir().join(format!("mp3-test-{}-{}.mp3", name, std::process::id()));
+        std::fs::write(&path, data).unwrap();
+        let duration = estimate_duration(&path, 4);
+        let _ = std::fs::remove_file(&path);
+        duration
+    }
+
+    #[test]
+    fn frame_header() {
+        let frame = parse_frame_header(&HEADER).unwrap();
+        assert!(frame.mpeg1 && !frame.mono);
+        assert_eq!((frame.bitrate_kbps, frame.sample_rate, frame.length, frame.samples), (128, 44100, FRAME_LENGTH, 1152));
+        // Free format and reserved sample rates are out.
+        assert!(parse_frame_header(&[0xff, 0xfb, 0x00, 0x00]).is_none());
+        assert!(parse_frame_header(&[0xff, 0xfb, 0x9c, 0x00]).is_none());
+    }
+
+    #[test]
+    fn duration_from_xing() {
+        // 32 bytes of side info, then the tag, flags with the frame count bit set, and the count.
+        let mut xing = vec![0u8; 32];
+        xing.extend(b"Xing\0\0\0\x01");
+        xing.extend(3675u32.to_be_bytes());
+        assert_eq!(estimate("xing", &xing, 3), Some(Duration::from_secs(96)));
+
+        // LAME writes "Info" for CBR files.
+        xing[32..36].copy_from_slice(b"Info");
+        assert_eq!(estimate("info", &xing, 3), Some(Duration::from_secs(96)));
+
+        // Without the frame count flag, it's back to going by the size.
+        xing[39] = 0;
+        assert_eq!(estimate("xing-no-count", &xing, 3), Some(Duration::from_secs_f64(3.0 * 417.0 * 8.0 / 128000.0)));
+    }
+
+    #[test]
+    fn duration_from_vbri() {
+        // Always 32 bytes in, then version, delay, quality, byte count and frame count.
+        let mut vbri = vec![0u8; 32];
+        vbri.extend(b"VBRI\0\x01\0\0\0\0\0\0\0\0");
+        vbri.extend(3675u32.to_be_bytes());
+        assert_eq!(estimate("vbri", &vbri, 3), Some(Duration::from_secs(96)));
+    }
+
+    #[test]
+    fn duration_from_size() {
+        assert_eq!(estimate("cbr", &[], 10), Some(Duration::from_secs_f64(10.0 * 417.0 * 8.0 / 128000.0)));
+        assert_eq!(estimate("empty", &[], 0), None);
+    }
+}
@@ -0,0 +1,144 @@
+use std::{convert::TryFrom, fs::File, io::BufReader, path::Path, time::Duration};
+
+use audiopus::{Channels, MutSignals, SampleRate, coder::Decoder as LibOpusDecoder, packet::Packet};
+use ogg::PacketReader;
+
+use super::{Decoder, duration_of, frames_at, ogg_last_granule};
+use crate::audio::{AudioError, StreamSpec};
+
+/// Opus always decodes at 48kHz, and granule positions count at that rate too.
+const OPUS_RATE: u32 = 48000;
+/// Largest possible Opus packet, 120ms
+const MAX_PACKET_FRAMES: usize = 5760;
+/// Decode this much ahead of a seek target so the decoder has converged by the time we get there.
+const SEEK_PREROLL: u64 = 3840;
+
+/// Ogg Opus via libopus. Only mono and stereo (channel mapping family 0) streams.
+pub struct OpusDecoder {
+    reader: PacketReader<BufReader<File>>,
+    decoder: LibOpusDecoder,
+    channels: usize,
+    /// Samples at the start of the stream that are only there to prime the decoder
+    pre_skip: u64,
+    /// Linear gain from the header's output gain
+    gain: f32,
+    last_granule: Option<u64>,
+    /// Granule position at the end of the last decoded packet
+    granule: u64,
+    /// Frames still to be dropped, for pre-skip and after seeking
+    skip: u64,
+    buffer: Vec<f32>,
+    /// Of the last packet decoded
+    bitrate: Option<u32>,
+}
+
+impl OpusDecoder {
+    pub fn open(path: &Path) -> Result<Self, AudioError> {
+        let mut reader = PacketReader::new(BufReader::new(File::open(path)?));
+        let head = reader.read_packet_expected()?.data;
+        if head.len() < 19 || !head.starts_with(b"OpusHead") {
+            return Err(AudioError::Malformed("missing OpusHead".to_string()));
+        }
+        let channels = head[9] as usize;
+        let pre_skip = u16::from_le_bytes([head[10], head[11]]) as u64;
+        let output_gain = i16::from_le_bytes([head[16], head[17]]);
+        let mapping_family = head[18];
+        if mapping_family != 0 || channels == 0 || channels > 2 {
+            return Err(AudioError::UnsupportedFormat(format!("Opus with {} channels (mapping family {})", channels, mapping_family)));
+        }
+        // OpusTags, which the tag reader deals with.
+        reader.read_packet_expected()?;
+
+        Ok(Self {
+            reader,
+            decoder: new_libopus_decoder(channels)?,
+            channels,
+            pre_skip,
+            // Q7.8 dB
+            gain: 10f32.powf(output_gain as f32 / (20.0 * 256.0)),
+            last_granule: ogg_last_granule(path),
+            granule: 0,
+            skip: pre_skip,
+            buffer: vec![0.0; MAX_PACKET_FRAMES * channels],
+            bitrate: None,
+        })
+    }
+
+    fn decode(&mut self, packet: &ogg::Packet) -> Result<Vec<f32>, AudioError> {
+        let frames = self.decoder.decode_float(Some(Packet::try_from(&packet.data[..])?), MutSignals::try_from(&mut self.buffer[..])?, false)?;
+        if frames > 0 {
+            self.bitrate = Some((packet.data.len() as u64 * 8 * OPUS_RATE as u64 / frames as u64 / 1000) as u32);
+        }
+        Ok(self.buffer[..frames * self.channels].iter().map(|s| s * self.gain).collect())
+    }
+}
+
+impl Decoder for OpusDecoder {
+    fn spec(&self) -> StreamSpec {
+        StreamSpec {
+            sample_rate: OPUS_RATE,
+            channels: self.channels,
+        }
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.last_granule.map(|granule| duration_of(granule.saturating_sub(self.pre_skip), OPUS_RATE))
+    }
+
+    fn bitrate(&self) -> Option<u32> {
+        self.bitrate
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        let target = frames_at(position, OPUS_RATE) + self.pre_skip;
+        self.reader.seek_absgp(None, target.saturating_sub(SEEK_PREROLL))?;
+        self.decoder = new_libopus_decoder(self.channels)?;
+
+        // Decode (and throw away) until a page ends, which tells us where we are.
+        loop {
+            let packet = match self.reader.read_packet()? {
+                Some(packet) => packet,
+                None => {
+                    self.skip = 0;
+                    return Ok(position);
+                }
+            };
+            self.decode(&packet)?;
+            if packet.last_in_page() {
+                self.granule = packet.absgp_page();
+                break;
+            }
+        }
+        self.skip = target.saturating_sub(self.granule);
+        Ok(duration_of(self.granule.max(target) - self.pre_skip, OPUS_RATE))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        loop {
+            let packet = match self.reader.read_packet()? {
+                Some(packet) => packet,
+                None => return Ok(None),
+            };
+            let mut samples = self.decode(&packet)?;
+            let mut frames = (samples.len() / self.channels) as u64;
+
+            // The final page's granule position says where the stream really ends.
+            if packet.last_in_stream() && packet.absgp_page() < self.granule + frames {
+                frames = packet.absgp_page().saturating_sub(self.granule);
+                samples.truncate(frames as usize * self.channels);
+            }
+            self.granule = if packet.last_in_page() { packet.absgp_page() } else { self.granule + frames };
+
+            let skip = self.skip.min(frames);
+            self.skip -= skip;
+            if skip < frames {
+                return Ok(Some(samples.split_off(skip as usize * self.channels)));
+            }
+        }
+    }
+}
+
+fn new_libopus_decoder(channels: usize) -> Result<LibOpusDecoder, AudioError> {
+    let channels = if channels == 1 { Channels::Mono } else { Channels::Stereo };
+    Ok(LibOpusDecoder::new(SampleRate::Hz48000, channels)?)
+}
@@ -0,0 +1,89 @@
+use std::{fs::File, io::BufReader, path::Path, time::Duration};
+
+use lewton::inside_ogg::OggStreamReader;
+
+use super::{Decoder, duration_of, frames_at, ogg_last_granule};
+use crate::audio::{AudioError, StreamSpec};
+
+/// Ogg Vorbis via lewton.
+pub struct VorbisDecoder {
+    reader: OggStreamReader<BufReader<File>>,
+    spec: StreamSpec,
+    total_frames: Option<u64>,
+    /// Frames still to be dropped after a seek, to land on the exact sample
+    skip: u64,
+}
+
+impl VorbisDecoder {
+    pub fn open(path: &Path) -> Result<Self, AudioError> {
+        let reader = OggStreamReader::new(BufReader::new(File::open(path)?))?;
+        let spec = StreamSpec {
+            sample_rate: reader.ident_hdr.audio_sample_rate,
+            channels: reader.ident_hdr.audio_channels as usize,
+        };
+        Ok(Self {
+            reader,
+            spec,
+            total_frames: ogg_last_granule(path),
+            skip: 0,
+        })
+    }
+}
+
+impl Decoder for VorbisDecoder {
+    fn spec(&self) -> StreamSpec {
+        self.spec
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        self.total_frames.map(|frames| duration_of(frames, self.spec.sample_rate))
+    }
+
+    /// lewton doesn't say how big packets are, so this is the header's nominal bitrate.
+    fn bitrate(&self) -> Option<u32> {
+        Some(self.reader.ident_hdr.bitrate_nominal).filter(|bitrate| *bitrate > 0).map(|bitrate| bitrate as u32 / 1000)
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        let target = frames_at(position, self.spec.sample_rate);
+        self.reader.seek_absgp_pg(target)?;
+
+        // We only know where we are once a packet that ends a page has been decoded,
+        // so decode (and throw away) until then.
+        let mut reached = loop {
+            if self.reader.read_dec_packet_itl()?.is_none() {
+                self.skip = 0;
+                return Ok(duration_of(target, self.spec.sample_rate));
+            }
+            if let Some(granule) = self.reader.get_last_absgp() {
+                break granule;
+            }
+        };
+        // lewton lands on page boundaries, so this can end up a page past the target,
+        // and the packets before that are already gone.
+        if reached < target {
+            self.skip = target - reached;
+            reached = target;
+        } else {
+            self.skip = 0;
+        }
+        Ok(duration_of(reached, self.spec.sample_rate))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        loop {
+            let packet = match self.reader.read_dec_packet_itl()? {
+                Some(packet) => packet,
+                None => return Ok(None),
+            };
+            let frames = (packet.len() / self.spec.channels) as u64;
+            let skip = self.skip.min(frames);
+            self.skip -= skip;
+
+            let samples: Vec<f32> = packet[skip as usize * self.spec.channels..].iter().map(|s| *s as f32 / 32768.0).collect();
+            if !samples.is_empty() {
+                return Ok(Some(samples));
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::{convert::TryFrom, fs::File, io::BufReader, path::Path, time::Duration};
+
+use super::{Decoder, duration_of, frames_at};
+use crate::audio::{AudioError, StreamSpec};
+
+/// Frames returned per packet.
+const PACKET_FRAMES: usize = 1024;
+
+pub struct WavDecoder {
+    reader: hound::WavReader<BufReader<File>>,
+    spec: hound::WavSpec,
+}
+
+impl WavDecoder {
+    pub fn open(path: &Path) -> Result<Self, AudioError> {
+        let reader = hound::WavReader::open(path)?;
+        let spec = reader.spec();
+        if spec.sample_rate == 0 {
+            return Err(AudioError::Malformed(format!("{} channels, {} Hz, {} bits", spec.channels, spec.sample_rate, spec.bits_per_sample)));
+        }
+        Ok(Self { reader, spec })
+    }
+}
+
+impl Decoder for WavDecoder {
+    fn spec(&self) -> StreamSpec {
+        StreamSpec {
+            sample_rate: self.spec.sample_rate,
+            channels: self.spec.channels as usize,
+        }
+    }
+
+    fn duration(&self) -> Option<Duration> {
+        Some(duration_of(self.reader.duration() as u64, self.spec.sample_rate))
+    }
+
+    fn bitrate(&self) -> Option<u32> {
+        u32::try_from(self.spec.sample_rate as u64 * self.spec.channels as u64 * self.spec.bits_per_sample as u64 / 1000).ok()
+    }
+
+    fn seek(&mut self, position: Duration) -> Result<Duration, AudioError> {
+        let frame = frames_at(position, self.spec.sample_rate).min(self.reader.duration() as u64);
+        self.reader.seek(frame as u32)?;
+        Ok(duration_of(frame, self.spec.sample_rate))
+    }
+
+    fn next_packet(&mut self) -> Result<Option<Vec<f32>>, AudioError> {
+        let count = PACKET_FRAMES * self.spec.channels as usize;
+        let samples = match self.spec.sample_format {
+            hound::SampleFormat::Float => self.reader.samples::<f32>().take(count).collect::<Result<Vec<_>, _>>()?,
+            hound::SampleFormat::Int => {
+                let scale = (1i64 << (self.spec.bits_per_sample - 1)) as f32;
+                self.reader.samples::<i32>().take(count).map(|s| s.map(|s| s as f32 / scale)).collect::<Result<Vec<_>, _>>()?
+            }
+        };
+        Ok(if samples.is_empty() { None } else { Some(samples) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeking_stops_at_the_end() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let path = std::env::temp_dir().join(format!("wav-test-seek-{}.wav", std::process::id()));
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..1600 {
+            writer.write_sample(i as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut decoder = WavDecoder::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(decoder.duration(), Some(Duration::from_millis(100)));
+        assert_eq!(decoder.bitrate(), Some(256));
+
+        assert_eq!(decoder.seek(Duration::from_millis(75)).unwrap(), Duration::from_millis(75));
+        let packet = decoder.next_packet().unwrap().unwrap();
+        assert_eq!(packet.len(), 400);
+        assert_eq!(packet[0], 1200.0 / 32768.0);
+
+        assert_eq!(decoder.seek(Duration::from_secs(10)).unwrap(), Duration::from_millis(100));
+        assert_eq!(decoder.next_packet().unwrap(), None);
+    }
+
+    #[test]
+    fn zero_sample_rate_is_refused() {
+        let mut file = b"RIFF\x24\0\0\0WAVEfmt \x10\0\0\0\x01\0\x01\0".to_vec();
+        file.extend(0u32.to_le_bytes());
+        file.extend(0u32.to_le_bytes());
+        file.extend(b"\x02\0\x10\0data\0\0\0\0");
+        let path = std::env::temp_dir().join(format!("wav-test-rate-{}.wav", std::process::id()));
+        std::fs::write(&path, file).unwrap();
+        let result = WavDecoder::open(&path);
+        let _ = std::fs::remove_file(&path);
+        assert!(matches!(result, Err(AudioError::Malformed(_))));
+    }
+}
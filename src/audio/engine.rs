@@ -0,0 +1,488 @@
+use std::{path::PathBuf, sync::{Arc, Mutex, mpsc::{self, Receiver, Sender}}, thread::{self, JoinHandle}, time::Duration};
+
+use super::{AudioError, StreamSpec, decoder::{self, Decoder}, equalizer::{EqSettings, Equalizer}, output::{AudioOutput, OutputKind}};
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PlaybackState {
+    #[default]
+    Stopped,
+    Playing,
+    Paused,
+}
+
+/// Part of a file to play as if it were the whole track, e.g. one track of an album ripped to a single file.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TrackSection {
+    pub start: Duration,
+    /// None to play to the end of the file
+    pub end: Option<Duration>,
+}
+
+/// Snapshot of what the decoder thread is doing, for the UI to draw from.
+/// Positions and durations are within the loaded section, if there is one.
+#[derive(Debug, Clone, Default)]
+pub struct PlaybackStatus {
+    pub state: PlaybackState,
+    pub track: Option<PathBuf>,
+    pub position: Duration,
+    pub duration: Option<Duration>,
+    /// Of the loaded track, for the kHz readout and mono/stereo lights
+    pub spec: Option<StreamSpec>,
+    /// In kbps, following the decoder as it goes
+    pub bitrate: Option<u32>,
+    /// Set when the loaded track played through to the end. Cleared by the next load/play.
+    pub finished: bool,
+    pub error: Option<String>,
+}
+
+enum Command {
+    Load(PathBuf, Option<TrackSection>),
+    Play,
+    Pause,
+    Stop,
+    Seek(Duration),
+    SetGains(ChannelGains),
+    SetEqualizer(EqSettings),
+    Shutdown,
+}
+
+/// Applied to samples as they're decoded, so changes are heard once the output's buffer has played out.
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct ChannelGains {
+    left: f32,
+    right: f32,
+}
+
+impl Default for ChannelGains {
+    fn default() -> Self {
+        Self { left: 1.0, right: 1.0 }
+    }
+}
+
+/// Owns the decoder thread. Dropping it stops playback.
+pub struct PlaybackEngine {
+    commands: Sender<Command>,
+    status: Arc<Mutex<PlaybackStatus>>,
+    thread: Option<JoinHandle<()>>,
+    /// 0 to 1
+    volume: f32,
+    /// -1 (left only) to 1 (right only)
+    balance: f32,
+}
+
+impl PlaybackEngine {
+    pub fn new(output: OutputKind) -> Self {
+        let (commands, receiver) = mpsc::channel();
+        let status = Arc::new(Mutex::new(PlaybackStatus::default()));
+        let thread_status = status.clone();
+        let thread = thread::Builder::new()
+            .name("decoder".to_string())
+            // Some outputs (cpal streams) can't move between threads, so create it on the one that uses it.
+            .spawn(move || DecoderThread::new(receiver, thread_status, output.create()).run())
+            .expect("failed to spawn decoder thread");
+
+        Self {
+            commands,
+            status,
+            thread: Some(thread),
+            volume: 1.0,
+            balance: 0.0,
+        }
+    }
+
+    /// Load a track, stopping whatever was playing. Call `play` to start it.
+    /// With a section, only that part of the file is played and positions are relative to its start.
+    pub fn load(&self, path: PathBuf, section: Option<TrackSection>) {
+        self.send(Command::Load(path, section));
+    }
+
+    /// Starts from the beginning if stopped or already playing, resumes if paused.
+    pub fn play(&self) {
+        self.send(Command::Play);
+    }
+
+    /// Toggles pause, like Winamp's pause button.
+    pub fn pause(&self) {
+        self.send(Command::Pause);
+    }
+
+    pub fn stop(&self) {
+        self.send(Command::Stop);
+    }
+
+    pub fn seek(&self, position: Duration) {
+        self.send(Command::Seek(position));
+    }
+
+    /// Whether the track has played through to the end since this was last asked, so whoever
+    /// moves on to the next track only does it once.
+    pub fn take_finished(&self) -> bool {
+        let mut status = self.status.lock().unwrap();
+        std::mem::replace(&mut status.finished, false)
+    }
+
+    /// 0 is silent, 1 is full volume.
+    pub fn set_volume(&mut self, volume: f32) {
+        self.volume = volume.clamp(0.0, 1.0);
+        self.send_gains();
+    }
+
+    /// -1 is left only, 0 is both channels at full volume, 1 is right only.
+    pub fn set_balance(&mut self, balance: f32) {
+        self.balance = balance.clamp(-1.0, 1.0);
+        self.send_gains();
+    }
+
+    pub fn set_equalizer(&self, settings: EqSettings) {
+        self.send(Command::SetEqualizer(settings));
+    }
+
+    fn send_gains(&self) {
+        // Turning one side up past the middle only ever turns the other side down.
+        self.send(Command::SetGains(ChannelGains {
+            left: self.volume * (1.0 - self.balance).min(1.0),
+            right: self.volume * (1.0 + self.balance).min(1.0),
+        }));
+    }
+
+    pub fn status(&self) -> PlaybackStatus {
+        self.status.lock().unwrap().clone()
+    }
+
+    fn send(&self, command: Command) {
+        // The thread only goes away if it panicked, in which case there's nothing to control anymore.
+        let _ = self.commands.send(command);
+    }
+}
+
+impl Drop for PlaybackEngine {
+    fn drop(&mut self) {
+        self.send(Command::Shutdown);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+struct DecoderThread {
+    commands: Receiver<Command>,
+    status: Arc<Mutex<PlaybackStatus>>,
+    track: Option<PathBuf>,
+    section: Option<TrackSection>,
+    source: Option<Box<dyn Decoder>>,
+    output: Box<dyn AudioOutput>,
+    state: PlaybackState,
+    /// Frames handed to the output since the last seek.
+    frames_written: u64,
+    /// Where in the file the last seek went to, so positions stay sample accurate from there.
+    seek_base: Duration,
+    /// Decoded frames still to drop, where the decoder couldn't seek exactly to where we asked.
+    skip_frames: u64,
+    gains: ChannelGains,
+    equalizer: Equalizer,
+}
+
+impl DecoderThread {
+    fn new(commands: Receiver<Command>, status: Arc<Mutex<PlaybackStatus>>, output: Box<dyn AudioOutput>) -> Self {
+        Self {
+            commands,
+            status,
+            track: None,
+            section: None,
+            source: None,
+            output,
+            state: PlaybackState::Stopped,
+            frames_written: 0,
+            seek_base: Duration::default(),
+            skip_frames: 0,
+            gains: ChannelGains::default(),
+            equalizer: Equalizer::new(),
+        }
+    }
+
+    fn run(mut self) {
+        loop {
+            if self.state == PlaybackState::Playing {
+                while let Ok(command) = self.commands.try_recv() {
+                    if !self.handle(command) {
+                        return;
+                    }
+                }
+                if self.state == PlaybackState::Playing {
+                    self.pump();
+                }
+            } else {
+                match self.commands.recv() {
+                    Ok(command) => {
+                        if !self.handle(command) {
+                            return;
+                        }
+                    }
+                    Err(_) => return,
+                }
+            }
+            self.publish();
+        }
+    }
+
+    /// Returns false when the thread should exit.
+    fn handle(&mut self, command: Command) -> bool {
+        let result = match command {
+            Command::Load(path, section) => {
+                let closed = self.close();
+                self.track = Some(path);
+                self.section = section;
+                let mut status = self.status.lock().unwrap();
+                status.finished = false;
+                status.duration = None;
+                closed
+            }
+            Command::Play => match self.state {
+                PlaybackState::Paused => {
+                    self.output.pause(false);
+                    self.state = PlaybackState::Playing;
+                    Ok(())
+                }
+                _ => self.start(),
+            },
+            Command::Pause => {
+                match self.state {
+                    PlaybackState::Playing => {
+                        self.output.pause(true);
+                        self.state = PlaybackState::Paused;
+                    }
+                    PlaybackState::Paused => {
+                        self.output.pause(false);
+                        self.state = PlaybackState::Playing;
+                    }
+                    PlaybackState::Stopped => (),
+                }
+                Ok(())
+            }
+            Command::Stop => self.close(),
+            Command::Seek(position) => self.seek(position),
+            Command::SetGains(gains) => {
+                self.gains = gains;
+                Ok(())
+            }
+            Command::SetEqualizer(settings) => {
+                self.equalizer.set(settings);
+                Ok(())
+            }
+            Command::Shutdown => return false,
+        };
+
+        if let Err(e) = result {
+            // The first error is the one worth reporting.
+            let _ = self.close();
+            self.status.lock().unwrap().error = Some(e.to_string());
+        }
+        true
+    }
+
+    /// (Re)open the current track and play it from the start.
+    fn start(&mut self) -> Result<(), AudioError> {
+        self.close()?;
+        let path = match &self.track {
+            Some(path) => path.clone(),
+            None => return Ok(()),
+        };
+        let source = decoder::open(&path)?;
+        self.output.open(source.spec())?;
+
+        self.source = Some(source);
+        self.equalizer.reset();
+        self.frames_written = 0;
+        self.seek_base = Duration::default();
+        self.skip_frames = 0;
+        if self.section.is_some() {
+            // Straight to the start of the section.
+            self.seek(Duration::default())?;
+        }
+        self.state = PlaybackState::Playing;
+        let mut status = self.status.lock().unwrap();
+        status.finished = false;
+        status.error = None;
+        Ok(())
+    }
+
+    /// `position` is from the start of the section, if there is one.
+    fn seek(&mut self, position: Duration) -> Result<(), AudioError> {
+        let target = self.section.map_or(Duration::default(), |section| section.start) + position;
+        if let Some(source) = &mut self.source {
+            let reached = source.seek(target)?;
+            let sample_rate = source.spec().sample_rate;
+            self.skip_frames = decoder::frames_at(target, sample_rate).saturating_sub(decoder::frames_at(reached, sample_rate));
+            self.seek_base = if self.skip_frames > 0 { target } else { reached };
+            self.output.discard();
+            self.equalizer.reset();
+            self.frames_written = 0;
+        }
+        Ok(())
+    }
+
+    /// Decode one chunk and push it to the output.
+    fn pump(&mut self) {
+        let source = match &mut self.source {
+            Some(source) => source,
+            None => {
+                self.state = PlaybackState::Stopped;
+                return;
+            }
+        };
+
+        let result = match source.next_packet() {
+            Ok(None) => self.output.flush().map(|_| true),
+            Ok(Some(mut samples)) => {
+                let spec = source.spec();
+                let channels = spec.channels;
+                let skip = (self.skip_frames as usize).min(samples.len() / channels);
+                samples.drain(..skip * channels);
+                self.skip_frames -= skip as u64;
+
+                // Stop exactly at the end of the section.
+                let end_frame = self.section.and_then(|section| section.end).map(|end| decoder::frames_at(end, spec.sample_rate));
+                let at_frame = decoder::frames_at(self.seek_base, spec.sample_rate) + self.frames_written;
+                let remaining = end_frame.map(|end| end.saturating_sub(at_frame) as usize);
+                let reached_end = remaining.is_some_and(|remaining| samples.len() / channels >= remaining);
+                if let Some(remaining) = remaining {
+                    samples.truncate(remaining.min(samples.len() / channels) * channels);
+                }
+
+                self.frames_written += (samples.len() / channels) as u64;
+                self.equalizer.process(&mut samples, spec);
+                apply_gains(&mut samples, channels, self.gains);
+                match self.output.write(&samples) {
+                    Ok(()) if reached_end => self.output.flush().map(|_| true),
+                    Ok(()) => Ok(false),
+                    Err(e) => Err(e),
+                }
+            }
+            Err(e) => Err(e),
+        };
+
+        match result {
+            Ok(false) => (),
+            Ok(true) => match self.close() {
+                Ok(()) => self.status.lock().unwrap().finished = true,
+                Err(e) => self.status.lock().unwrap().error = Some(e.to_string()),
+            },
+            Err(e) => {
+                let _ = self.close();
+                self.status.lock().unwrap().error = Some(e.to_string());
+            }
+        }
+    }
+
+    fn close(&mut self) -> Result<(), AudioError> {
+        let closed = match self.source.take() {
+            Some(_) => self.output.close(),
+            None => Ok(()),
+        };
+        self.frames_written = 0;
+        self.seek_base = Duration::default();
+        self.skip_frames = 0;
+        self.state = PlaybackState::Stopped;
+        closed
+    }
+
+    fn publish(&self) {
+        let mut status = self.status.lock().unwrap();
+        status.state = self.state;
+        status.track = self.track.clone();
+        match &self.source {
+            Some(source) => {
+                status.spec = Some(source.spec());
+                status.bitrate = source.bitrate();
+                let written = self.seek_base + decoder::duration_of(self.frames_written, source.spec().sample_rate);
+                let played = written.checked_sub(self.output.latency()).unwrap_or_default();
+                match self.section {
+                    Some(section) => {
+                        status.position = played.checked_sub(section.start).unwrap_or_default();
+                        status.duration = section.end.or_else(|| source.duration()).and_then(|end| end.checked_sub(section.start));
+                    }
+                    None => {
+                        status.position = played;
+                        status.duration = source.duration();
+                    }
+                }
+            }
+            None => {
+                status.position = Duration::default();
+                status.spec = None;
+                status.bitrate = None;
+            }
+        }
+    }
+}
+
+/// Balance only means something with two channels. Mono gets the louder side, and channels
+/// past the first two (surround) are left at the overall volume.
+fn apply_gains(samples: &mut [f32], channels: usize, gains: ChannelGains) {
+    if gains == ChannelGains::default() {
+        return;
+    }
+    let volume = gains.left.max(gains.right);
+    for frame in samples.chunks_mut(channels) {
+        for (channel, sample) in frame.iter_mut().enumerate() {
+            *sample *= match (channels, channel) {
+                (1, _) => volume,
+                (_, 0) => gains.left,
+                (_, 1) => gains.right,
+                _ => volume,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{path::Path, time::Instant};
+
+    fn write_fixture(path: &Path, spec: hound::WavSpec, samples: &[i16]) {
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for sample in samples {
+            writer.write_sample(*sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    #[test]
+    fn wav_file_output_is_bit_exact() {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 44100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        // A few packets' worth, with both extremes and the values either side of zero.
+        let mut samples = vec![i16::MIN, i16::MAX, -1, 0, 1, -12345, 12345, i16::MIN + 1];
+        samples.extend((0..6000).map(|i| ((i * 7919) % 65536 - 32768) as i16));
+
+        let directory = std::env::temp_dir();
+        let input = directory.join(format!("engine-test-in-{}.wav", std::process::id()));
+        let output = directory.join(format!("engine-test-out-{}.wav", std::process::id()));
+        write_fixture(&input, spec, &samples);
+
+        let engine = PlaybackEngine::new(OutputKind::WavFile(output.clone()));
+        engine.load(input.clone(), None);
+        engine.play();
+        let started = Instant::now();
+        while !engine.take_finished() {
+            assert!(engine.status().error.is_none(), "{:?}", engine.status().error);
+            assert!(started.elapsed() < Duration::from_secs(10), "playback never finished");
+            thread::sleep(Duration::from_millis(5));
+        }
+        // Dropping the engine drops the output, which finalizes the file.
+        drop(engine);
+
+        let mut reader = hound::WavReader::open(&output).unwrap();
+        assert_eq!(reader.spec(), spec);
+        let written: Vec<i16> = reader.samples::<i16>().collect::<Result<_, _>>().unwrap();
+        let _ = std::fs::remove_file(&input);
+        let _ = std::fs::remove_file(&output);
+        assert_eq!(written, samples);
+    }
+}
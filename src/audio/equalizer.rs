@@ -0,0 +1,257 @@
+use std::f64::consts::PI;
+
+use super::StreamSpec;
+
+/// Centre frequencies of Winamp's ten bands, in Hz.
+pub const EQ_BANDS: [f64; 10] = [60.0, 170.0, 310.0, 600.0, 1000.0, 3000.0, 6000.0, 12000.0, 14000.0, 16000.0];
+/// Every slider, preamp included, goes this far up and down in dB.
+pub const EQ_RANGE_DB: f32 = 12.0;
+
+/// Roughly an octave wide, so neighbouring bands overlap smoothly.
+const BAND_Q: f64 = 1.2;
+/// Gains are recalculated this often while they're moving, in frames.
+const SMOOTHING_BLOCK: usize = 32;
+/// How quickly gains follow the sliders. After this many seconds a change is ~63% of the way there.
+const SMOOTHING_TIME: f64 = 0.02;
+/// Closer than this to where they're going (in dB) and gains just jump the rest of the way.
+const SETTLED_DB: f64 = 0.001;
+
+/// What the equalizer window shows. Gains are in dB, between -`EQ_RANGE_DB` and `EQ_RANGE_DB`.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EqSettings {
+    pub enabled: bool,
+    pub preamp: f32,
+    pub bands: [f32; 10],
+}
+
+impl Default for EqSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            preamp: 0.0,
+            bands: [0.0; 10],
+        }
+    }
+}
+
+impl EqSettings {
+    /// Gains to actually apply; everything is flat while switched off.
+    fn target(&self) -> ([f64; 10], f64) {
+        if self.enabled {
+            let mut bands = [0.0; 10];
+            for (band, gain) in bands.iter_mut().zip(self.bands.iter()) {
+                *band = *gain as f64;
+            }
+            (bands, self.preamp as f64)
+        } else {
+            ([0.0; 10], 0.0)
+        }
+    }
+}
+
+/// RBJ cookbook peaking filter, transposed direct form II.
+#[derive(Debug, Copy, Clone, Default)]
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+}
+
+impl Biquad {
+    fn peaking(frequency: f64, gain_db: f64, sample_rate: f64) -> Self {
+        let a = 10f64.powf(gain_db / 40.0);
+        let w0 = 2.0 * PI * frequency / sample_rate;
+        let alpha = w0.sin() / (2.0 * BAND_Q);
+        let cos_w0 = w0.cos();
+        let a0 = 1.0 + alpha / a;
+        Self {
+            b0: (1.0 + alpha * a) / a0,
+            b1: -2.0 * cos_w0 / a0,
+            b2: (1.0 - alpha * a) / a0,
+            a1: -2.0 * cos_w0 / a0,
+            a2: (1.0 - alpha / a) / a0,
+        }
+    }
+}
+
+/// The ten band filters for one channel, and their state.
+#[derive(Debug, Clone, Default)]
+struct ChannelState {
+    /// (s1, s2) for each band
+    state: [(f64, f64); 10],
+}
+
+/// Ten peaking filters plus preamp, applied to interleaved samples.
+/// Gains glide towards new settings rather than jumping, so dragging a slider doesn't click.
+pub struct Equalizer {
+    settings: EqSettings,
+    spec: Option<StreamSpec>,
+    /// Where the gains are right now, on their way to the settings
+    current_bands: [f64; 10],
+    current_preamp: f64,
+    filters: [Biquad; 10],
+    /// Bands at or above Nyquist can't be filtered, so are left out
+    active_bands: usize,
+    channels: Vec<ChannelState>,
+}
+
+impl Equalizer {
+    pub fn new() -> Self {
+        Self {
+            settings: EqSettings::default(),
+            spec: None,
+            current_bands: [0.0; 10],
+            current_preamp: 0.0,
+            filters: [Biquad::default(); 10],
+            active_bands: 0,
+            channels: vec![],
+        }
+    }
+
+    pub fn set(&mut self, settings: EqSettings) {
+        self.settings = settings;
+    }
+
+    /// Forget the filters' history, e.g. after a seek, so the old audio doesn't ring into the new.
+    pub fn reset(&mut self) {
+        for channel in &mut self.channels {
+            *channel = ChannelState::default();
+        }
+    }
+
+    pub fn process(&mut self, samples: &mut [f32], spec: StreamSpec) {
+        if self.spec != Some(spec) {
+            self.spec = Some(spec);
+            self.channels = vec![ChannelState::default(); spec.channels];
+            self.active_bands = EQ_BANDS.iter().take_while(|f| **f < spec.sample_rate as f64 * 0.45).count();
+            self.update_filters();
+        }
+
+        let (target_bands, target_preamp) = self.settings.target();
+        let settled = self.current_preamp == target_preamp && self.current_bands == target_bands;
+        if settled && target_preamp == 0.0 && target_bands.iter().all(|gain| *gain == 0.0) {
+            // Flat, so nothing to do. Keep the filters quiet for when it's switched back on.
+            self.reset();
+            return;
+        }
+
+        let channels = spec.channels.max(1);
+        let smoothing = 1.0 - (-(SMOOTHING_BLOCK as f64) / (SMOOTHING_TIME * spec.sample_rate as f64)).exp();
+        for block in samples.chunks_mut(SMOOTHING_BLOCK * channels) {
+            if !(self.current_preamp == target_preamp && self.current_bands == target_bands) {
+                approach(&mut self.current_preamp, target_preamp, smoothing);
+                for (current, target) in self.current_bands.iter_mut().zip(target_bands.iter()) {
+                    approach(current, *target, smoothing);
+                }
+                self.update_filters();
+            }
+
+            let preamp = 10f64.powf(self.current_preamp / 20.0);
+            for frame in block.chunks_mut(channels) {
+                for (sample, channel) in frame.iter_mut().zip(self.channels.iter_mut()) {
+                    let mut x = *sample as f64 * preamp;
+                    for (filter, (s1, s2)) in self.filters.iter().zip(channel.state.iter_mut()).take(self.active_bands) {
+                        let y = filter.b0 * x + *s1;
+                        *s1 = filter.b1 * x - filter.a1 * y + *s2;
+                        *s2 = filter.b2 * x - filter.a2 * y;
+                        x = y;
+                    }
+                    *sample = x as f32;
+                }
+            }
+        }
+    }
+
+    fn update_filters(&mut self) {
+        let sample_rate = match self.spec {
+            Some(spec) => spec.sample_rate as f64,
+            None => return,
+        };
+        for ((filter, frequency), gain) in self.filters.iter_mut().zip(EQ_BANDS.iter()).zip(self.current_bands.iter()).take(self.active_bands) {
+            *filter = Biquad::peaking(*frequency, *gain, sample_rate);
+        }
+    }
+}
+
+/// One step of a one-pole glide from `current` to `target`.
+fn approach(current: &mut f64, target: f64, amount: f64) {
+    *current += (target - *current) * amount;
+    if (target - *current).abs() < SETTLED_DB {
+        *current = target;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gain of `filter` at `frequency`, in dB.
+    fn response_db(filter: &Biquad, frequency: f64, sample_rate: f64) -> f64 {
+        let w = 2.0 * PI * frequency / sample_rate;
+        // H(z) with z = e^jw, as (real, imaginary) parts of the numerator and denominator.
+        let part = |c0: f64, c1: f64, c2: f64| (c0 + c1 * w.cos() + c2 * (2.0 * w).cos(), -c1 * w.sin() - c2 * (2.0 * w).sin());
+        let (nr, ni) = part(filter.b0, filter.b1, filter.b2);
+        let (dr, di) = part(1.0, filter.a1, filter.a2);
+        10.0 * ((nr * nr + ni * ni) / (dr * dr + di * di)).log10()
+    }
+
+    /// How much louder, in dB, a sine at `frequency` comes out of a second through `settings`.
+    fn sine_through(settings: EqSettings, frequency: f64) -> f64 {
+        let spec = StreamSpec { sample_rate: 44100, channels: 2 };
+        let input: Vec<f32> = (0..44100).flat_map(|i| {
+            let s = (0.25 * (2.0 * PI * frequency * i as f64 / 44100.0).sin()) as f32;
+            [s, s]
+        }).collect();
+        let mut output = input.clone();
+        let mut equalizer = Equalizer::new();
+        equalizer.set(settings);
+        for packet in output.chunks_mut(2048) {
+            equalizer.process(packet, spec);
+        }
+        // Well after the gains have settled.
+        let peak = |samples: &[f32]| samples[2 * 22050..].iter().fold(0f32, |peak, s| peak.max(s.abs())) as f64;
+        20.0 * (peak(&output) / peak(&input)).log10()
+    }
+
+    #[test]
+    fn flat_is_unity() {
+        for frequency in EQ_BANDS.iter() {
+            let filter = Biquad::peaking(*frequency, 0.0, 44100.0);
+            for probe in [20.0, 100.0, 1000.0, 5000.0, 20000.0].iter() {
+                assert!(response_db(&filter, *probe, 44100.0).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn full_boost_and_cut_at_the_centre() {
+        for frequency in EQ_BANDS.iter() {
+            for gain in [12.0, -12.0, 6.0].iter() {
+                let filter = Biquad::peaking(*frequency, *gain, 44100.0);
+                let centre = response_db(&filter, *frequency, 44100.0);
+                assert!((centre - gain).abs() < 0.01, "{} Hz at {} dB: {}", frequency, gain, centre);
+            }
+        }
+        // And it falls away either side.
+        let filter = Biquad::peaking(1000.0, 12.0, 44100.0);
+        assert!(response_db(&filter, 100.0, 44100.0) < 1.0);
+        assert!(response_db(&filter, 10000.0, 44100.0) < 1.0);
+    }
+
+    #[test]
+    fn bands_and_preamp_apply_to_audio() {
+        let mut settings = EqSettings { enabled: true, ..EqSettings::default() };
+        settings.bands[4] = 12.0;
+        assert!((sine_through(settings, 1000.0) - 12.0).abs() < 0.2);
+
+        settings.preamp = -6.0;
+        assert!((sine_through(settings, 1000.0) - 6.0).abs() < 0.2);
+
+        // Switched off, nothing happens at all.
+        settings.enabled = false;
+        assert_eq!(sine_through(settings, 1000.0), 0.0);
+    }
+}
@@ -0,0 +1,46 @@
+use std::io;
+use thiserror::Error;
+
+pub mod decoder;
+pub mod engine;
+pub mod equalizer;
+pub mod output;
+
+pub use engine::{PlaybackEngine, PlaybackState, TrackSection};
+pub use equalizer::{EqSettings, EQ_RANGE_DB};
+pub use output::OutputKind;
+
+/// Format of the interleaved f32 samples passed between decoders and outputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StreamSpec {
+    pub sample_rate: u32,
+    pub channels: usize,
+}
+
+#[derive(Error, Debug)]
+pub enum AudioError {
+    #[error("IO error")]
+    Io(#[from] io::Error),
+    #[error("Wav")]
+    Wav(#[from] hound::Error),
+    #[error("Flac")]
+    Flac(#[from] claxon::Error),
+    #[error("Vorbis")]
+    Vorbis(#[from] lewton::VorbisError),
+    #[error("Mp3")]
+    Mp3(#[from] minimp3::Error),
+    #[cfg(feature = "opus")]
+    #[error("Ogg")]
+    Ogg(#[from] ogg::OggReadError),
+    #[cfg(feature = "opus")]
+    #[error("Opus")]
+    Opus(#[from] audiopus::Error),
+    #[error("Unsupported format {0}")]
+    UnsupportedFormat(String),
+    #[error("Malformed file {0}")]
+    Malformed(String),
+    #[error("No output device available")]
+    NoOutputDevice,
+    #[error("Output device error {0}")]
+    Device(String),
+}
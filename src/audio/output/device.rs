@@ -0,0 +1,264 @@
+use std::{collections::VecDeque, sync::{Arc, Mutex}, thread, time::Duration};
+
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{Sample, SampleFormat, StreamConfig};
+
+use super::AudioOutput;
+use crate::audio::{AudioError, StreamSpec};
+
+/// How much audio (in seconds) we keep queued up ahead of the device.
+const BUFFER_SECONDS: f32 = 0.25;
+
+/// Plays interleaved f32 samples on the default output device.
+/// If the device can't run at the source's rate/channel count, samples are converted on the way in.
+pub struct DeviceOutput {
+    stream: Option<OpenStream>,
+}
+
+struct OpenStream {
+    stream: cpal::Stream,
+    shared: Arc<Mutex<SharedBuffer>>,
+    converter: Option<Converter>,
+    device_channels: usize,
+    device_sample_rate: u32,
+    capacity: usize,
+}
+
+struct SharedBuffer {
+    samples: VecDeque<f32>,
+    paused: bool,
+}
+
+impl DeviceOutput {
+    pub fn new() -> Self {
+        Self {
+            stream: None,
+        }
+    }
+}
+
+impl AudioOutput for DeviceOutput {
+    fn open(&mut self, spec: StreamSpec) -> Result<(), AudioError> {
+        self.stream = None;
+        self.stream = Some(OpenStream::open(spec)?);
+        Ok(())
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), AudioError> {
+        if let Some(stream) = &mut self.stream {
+            stream.write(samples);
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self, paused: bool) {
+        if let Some(stream) = &mut self.stream {
+            stream.pause(paused);
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), AudioError> {
+        if let Some(stream) = &self.stream {
+            stream.drain();
+        }
+        Ok(())
+    }
+
+    fn discard(&mut self) {
+        if let Some(stream) = &self.stream {
+            stream.shared.lock().unwrap().samples.clear();
+        }
+    }
+
+    fn latency(&self) -> Duration {
+        match &self.stream {
+            Some(stream) => stream.latency(),
+            None => Duration::default(),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), AudioError> {
+        self.stream = None;
+        Ok(())
+    }
+}
+
+impl OpenStream {
+    fn open(spec: StreamSpec) -> Result<Self, AudioError> {
+        let StreamSpec { sample_rate, channels } = spec;
+        let host = cpal::default_host();
+        let device = host.default_output_device().ok_or(AudioError::NoOutputDevice)?;
+
+        let supported = device
+            .supported_output_configs()
+            .map_err(|e| AudioError::Device(e.to_string()))?
+            .find(|range| {
+                range.channels() as usize == channels
+                    && range.min_sample_rate().0 <= sample_rate
+                    && sample_rate <= range.max_sample_rate().0
+            })
+            .map(|range| range.with_sample_rate(cpal::SampleRate(sample_rate)));
+        let supported = match supported {
+            Some(s) => s,
+            None => device.default_output_config().map_err(|e| AudioError::Device(e.to_string()))?,
+        };
+
+        let sample_format = supported.sample_format();
+        let config: StreamConfig = supported.config();
+        let device_channels = config.channels as usize;
+        let device_sample_rate = config.sample_rate.0;
+
+        let shared = Arc::new(Mutex::new(SharedBuffer {
+            samples: VecDeque::new(),
+            paused: false,
+        }));
+
+        let stream = match sample_format {
+            SampleFormat::F32 => build_stream::<f32>(&device, &config, shared.clone()),
+            SampleFormat::I16 => build_stream::<i16>(&device, &config, shared.clone()),
+            SampleFormat::U16 => build_stream::<u16>(&device, &config, shared.clone()),
+        }?;
+        stream.play().map_err(|e| AudioError::Device(e.to_string()))?;
+
+        let converter = if device_channels != channels || device_sample_rate != sample_rate {
+            Some(Converter::new(channels, device_channels, sample_rate, device_sample_rate))
+        } else {
+            None
+        };
+
+        Ok(Self {
+            stream,
+            shared,
+            converter,
+            device_channels,
+            device_sample_rate,
+            capacity: (device_sample_rate as f32 * BUFFER_SECONDS) as usize * device_channels,
+        })
+    }
+
+    /// Queue interleaved samples, blocking while the device buffer is full.
+    fn write(&mut self, samples: &[f32]) {
+        let converted;
+        let samples = match &mut self.converter {
+            Some(converter) => {
+                converted = converter.process(samples);
+                &converted[..]
+            }
+            None => samples,
+        };
+
+        let mut remaining = samples;
+        while !remaining.is_empty() {
+            {
+                let mut shared = self.shared.lock().unwrap();
+                let space = self.capacity.saturating_sub(shared.samples.len());
+                let count = space.min(remaining.len());
+                shared.samples.extend(&remaining[..count]);
+                remaining = &remaining[count..];
+            }
+            if !remaining.is_empty() {
+                thread::sleep(Duration::from_millis(5));
+            }
+        }
+    }
+
+    fn pause(&mut self, paused: bool) {
+        self.shared.lock().unwrap().paused = paused;
+        // Not all hosts support pausing, the flag above keeps the callback quiet either way.
+        if paused {
+            let _ = self.stream.pause();
+        } else {
+            let _ = self.stream.play();
+        }
+    }
+
+    fn latency(&self) -> Duration {
+        let buffered = self.shared.lock().unwrap().samples.len();
+        let frames = buffered / self.device_channels;
+        Duration::from_secs_f64(frames as f64 / self.device_sample_rate as f64)
+    }
+
+    /// Block until everything queued has been handed to the device.
+    fn drain(&self) {
+        while !self.shared.lock().unwrap().samples.is_empty() {
+            thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
+
+fn build_stream<T: Sample>(device: &cpal::Device, config: &StreamConfig, shared: Arc<Mutex<SharedBuffer>>) -> Result<cpal::Stream, AudioError> {
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _: &cpal::OutputCallbackInfo| {
+                let mut shared = shared.lock().unwrap();
+                for out in data.iter_mut() {
+                    let sample = if shared.paused { 0.0 } else { shared.samples.pop_front().unwrap_or(0.0) };
+                    *out = T::from(&sample);
+                }
+            },
+            |e| eprintln!("output stream error: {}", e),
+        )
+        .map_err(|e| AudioError::Device(e.to_string()))
+}
+
+/// Channel mapping and linear-interpolation resampling, for when the device won't take the source format as is.
+struct Converter {
+    in_channels: usize,
+    out_channels: usize,
+    /// Input frames advanced per output frame
+    step: f64,
+    /// Position of the next output frame, relative to `previous`
+    position: f64,
+    /// Last input frame of the previous chunk (already channel mapped), so we can interpolate across chunks
+    previous: Vec<f32>,
+}
+
+impl Converter {
+    fn new(in_channels: usize, out_channels: usize, in_rate: u32, out_rate: u32) -> Self {
+        Self {
+            in_channels,
+            out_channels,
+            step: in_rate as f64 / out_rate as f64,
+            position: 0.0,
+            previous: vec![0.0; out_channels],
+        }
+    }
+
+    fn map_channels(&self, frame: &[f32], out: &mut Vec<f32>) {
+        if self.in_channels == self.out_channels {
+            out.extend_from_slice(frame);
+        } else if self.in_channels == 1 {
+            out.extend(std::iter::repeat_n(frame[0], self.out_channels));
+        } else if self.out_channels == 1 {
+            out.push(frame.iter().sum::<f32>() / self.in_channels as f32);
+        } else {
+            for c in 0..self.out_channels {
+                out.push(frame.get(c).copied().unwrap_or(0.0));
+            }
+        }
+    }
+
+    fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        let mut frames = self.previous.clone();
+        for frame in input.chunks_exact(self.in_channels) {
+            self.map_channels(frame, &mut frames);
+        }
+        let frame_count = frames.len() / self.out_channels - 1;
+
+        let mut output = vec![];
+        while self.position < frame_count as f64 {
+            let index = self.position.floor() as usize;
+            let fraction = (self.position - index as f64) as f32;
+            let a = &frames[index * self.out_channels..(index + 1) * self.out_channels];
+            let b = &frames[(index + 1) * self.out_channels..(index + 2) * self.out_channels];
+            for c in 0..self.out_channels {
+                output.push(a[c] + (b[c] - a[c]) * fraction);
+            }
+            self.position += self.step;
+        }
+        self.position -= frame_count as f64;
+        self.previous = frames[frame_count * self.out_channels..].to_vec();
+        output
+    }
+}
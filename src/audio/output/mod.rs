@@ -0,0 +1,53 @@
+use std::{path::PathBuf, time::Duration};
+
+use super::{AudioError, StreamSpec};
+
+pub mod device;
+pub mod null;
+pub mod wav_file;
+
+/// Somewhere for decoded audio to go.
+/// Outputs are created on the decoder thread and `open`ed again for every track.
+pub trait AudioOutput {
+    /// Get ready to receive interleaved f32 samples in this format.
+    fn open(&mut self, spec: StreamSpec) -> Result<(), AudioError>;
+
+    /// Queue interleaved samples. May block until there's room for them.
+    fn write(&mut self, samples: &[f32]) -> Result<(), AudioError>;
+
+    fn pause(&mut self, paused: bool);
+
+    /// Block until everything written so far has been played (or written out).
+    fn flush(&mut self) -> Result<(), AudioError>;
+
+    /// Drop anything queued that hasn't been played yet, e.g. after a seek.
+    fn discard(&mut self);
+
+    /// How long until the last written sample is heard.
+    fn latency(&self) -> Duration;
+
+    /// Release the device or file until the next `open`.
+    fn close(&mut self) -> Result<(), AudioError>;
+}
+
+/// Which output the playback engine should create.
+#[derive(Debug, Clone, Default)]
+pub enum OutputKind {
+    /// The default sound device.
+    #[default]
+    Device,
+    /// Throws audio away, in real time.
+    Null,
+    /// Writes 16-bit PCM to a .wav file, as fast as it can be decoded.
+    WavFile(PathBuf),
+}
+
+impl OutputKind {
+    pub fn create(&self) -> Box<dyn AudioOutput> {
+        match self {
+            OutputKind::Device => Box::new(device::DeviceOutput::new()),
+            OutputKind::Null => Box::new(null::NullOutput::new()),
+            OutputKind::WavFile(path) => Box::new(wav_file::WavFileOutput::new(path.clone())),
+        }
+    }
+}
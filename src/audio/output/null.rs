@@ -0,0 +1,86 @@
+use std::{thread, time::{Duration, Instant}};
+
+use super::AudioOutput;
+use crate::audio::{AudioError, StreamSpec};
+
+/// Same as the device output, how far ahead of "playback" we let the writer get.
+const BUFFER_AHEAD: Duration = Duration::from_millis(250);
+
+/// Discards everything, but takes as long about it as a real device would,
+/// so position tracking and end-of-track behave the same without a sound card.
+pub struct NullOutput {
+    spec: Option<StreamSpec>,
+    /// When the last written sample would finish playing.
+    deadline: Instant,
+    /// Latency remembered while paused, since the deadline doesn't move then.
+    paused_latency: Option<Duration>,
+}
+
+impl NullOutput {
+    pub fn new() -> Self {
+        Self {
+            spec: None,
+            deadline: Instant::now(),
+            paused_latency: None,
+        }
+    }
+}
+
+impl AudioOutput for NullOutput {
+    fn open(&mut self, spec: StreamSpec) -> Result<(), AudioError> {
+        self.spec = Some(spec);
+        self.deadline = Instant::now();
+        self.paused_latency = None;
+        Ok(())
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), AudioError> {
+        let spec = match self.spec {
+            Some(spec) => spec,
+            None => return Ok(()),
+        };
+        let frames = samples.len() / spec.channels;
+        let now = Instant::now();
+        self.deadline = self.deadline.max(now) + Duration::from_secs_f64(frames as f64 / spec.sample_rate as f64);
+
+        let ahead = self.deadline - now;
+        if ahead > BUFFER_AHEAD {
+            thread::sleep(ahead - BUFFER_AHEAD);
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self, paused: bool) {
+        if paused {
+            self.paused_latency = Some(self.latency());
+        } else if let Some(latency) = self.paused_latency.take() {
+            self.deadline = Instant::now() + latency;
+        }
+    }
+
+    fn flush(&mut self) -> Result<(), AudioError> {
+        thread::sleep(self.latency());
+        Ok(())
+    }
+
+    fn discard(&mut self) {
+        self.deadline = Instant::now();
+        if self.paused_latency.is_some() {
+            self.paused_latency = Some(Duration::default());
+        }
+    }
+
+    fn latency(&self) -> Duration {
+        match self.paused_latency {
+            Some(latency) => latency,
+            None => self.deadline.saturating_duration_since(Instant::now()),
+        }
+    }
+
+    fn close(&mut self) -> Result<(), AudioError> {
+        self.spec = None;
+        self.paused_latency = None;
+        self.deadline = Instant::now();
+        Ok(())
+    }
+}
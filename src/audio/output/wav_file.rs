@@ -0,0 +1,87 @@
+use std::{fs::File, io::BufWriter, path::PathBuf, time::Duration};
+
+use super::AudioOutput;
+use crate::audio::{AudioError, StreamSpec};
+
+/// Writes everything played to a 16-bit PCM .wav file, without any pacing.
+/// Consecutive tracks are appended to the same file, so they must share a format.
+/// The file is finalized when the output is dropped, and its header is kept up to date
+/// on every `close` so it's readable even if we never get that far.
+pub struct WavFileOutput {
+    path: PathBuf,
+    writer: Option<hound::WavWriter<BufWriter<File>>>,
+    paused: bool,
+}
+
+impl WavFileOutput {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            writer: None,
+            paused: false,
+        }
+    }
+}
+
+impl AudioOutput for WavFileOutput {
+    fn open(&mut self, spec: StreamSpec) -> Result<(), AudioError> {
+        let wav_spec = hound::WavSpec {
+            channels: spec.channels as u16,
+            sample_rate: spec.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        self.paused = false;
+        match &self.writer {
+            Some(writer) if writer.spec() == wav_spec => Ok(()),
+            Some(writer) => Err(AudioError::Device(format!(
+                "{} is already {} Hz/{} channels, can't append {} Hz/{} channels",
+                self.path.display(),
+                writer.spec().sample_rate,
+                writer.spec().channels,
+                spec.sample_rate,
+                spec.channels
+            ))),
+            None => {
+                self.writer = Some(hound::WavWriter::create(&self.path, wav_spec)?);
+                Ok(())
+            }
+        }
+    }
+
+    fn write(&mut self, samples: &[f32]) -> Result<(), AudioError> {
+        if self.paused {
+            return Ok(());
+        }
+        if let Some(writer) = &mut self.writer {
+            for sample in samples {
+                // The inverse of how 16-bit input is decoded, so it comes back out bit for bit.
+                writer.write_sample((sample * 32768.0).round().clamp(-32768.0, 32767.0) as i16)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn pause(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    fn flush(&mut self) -> Result<(), AudioError> {
+        if let Some(writer) = &mut self.writer {
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    fn discard(&mut self) {
+        // Everything written is already on its way to disk.
+    }
+
+    fn latency(&self) -> Duration {
+        Duration::default()
+    }
+
+    fn close(&mut self) -> Result<(), AudioError> {
+        self.flush()
+    }
+}
@@ -0,0 +1,215 @@
+//! Winamp's equalizer preset files. A single .EQF and the winamp.q1 library are the same format:
+//! a header followed by any number of presets, each a fixed size name and one byte per slider.
+
+use std::{fs, io, path::Path};
+
+use thiserror::Error;
+
+use crate::audio::{EqSettings, EQ_RANGE_DB};
+
+const HEADER: &[u8] = b"Winamp EQ library file v1.1\x1a!--";
+/// Names are null-terminated in a fixed size field.
+const NAME_LENGTH: usize = 257;
+/// Ten bands then the preamp
+const VALUE_COUNT: usize = 11;
+const PRESET_LENGTH: usize = NAME_LENGTH + VALUE_COUNT;
+/// Sliders are stored as 0 (top, +12 dB) to 63 (bottom, -12 dB).
+const VALUE_STEPS: f32 = 63.0;
+
+#[derive(Error, Debug)]
+pub enum PresetError {
+    #[error("IO error")]
+    Io(#[from] io::Error),
+    #[error("Not a Winamp EQ preset file")]
+    BadHeader,
+}
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EqPreset {
+    pub name: String,
+    /// dB
+    pub preamp: f32,
+    /// dB
+    pub bands: [f32; 10],
+}
+
+impl EqPreset {
+    pub fn new(name: &str, settings: &EqSettings) -> Self {
+        Self {
+            name: name.to_string(),
+            preamp: settings.preamp,
+            bands: settings.bands,
+        }
+    }
+
+    /// Applies the preset's sliders, leaving the equalizer on or off as it was.
+    pub fn apply(&self, settings: &mut EqSettings) {
+        settings.preamp = self.preamp;
+        settings.bands = self.bands;
+    }
+}
+
+/// Reads every preset in an .EQF or winamp.q1. A partial preset at the end is ignored.
+pub fn parse_eqf(data: &[u8]) -> Result<Vec<EqPreset>, PresetError> {
+    let body = data.strip_prefix(HEADER).ok_or(PresetError::BadHeader)?;
+    Ok(body
+        .chunks_exact(PRESET_LENGTH)
+        .map(|preset| {
+            let (name, values) = preset.split_at(NAME_LENGTH);
+            let name_end = name.iter().position(|b| *b == 0).unwrap_or(NAME_LENGTH);
+            let mut bands = [0.0; 10];
+            for (band, value) in bands.iter_mut().zip(values.iter()) {
+                *band = value_to_db(*value);
+            }
+            EqPreset {
+                name: String::from_utf8_lossy(&name[..name_end]).into_owned(),
+                preamp: value_to_db(values[10]),
+                bands,
+            }
+        })
+        .collect())
+}
+
+/// Writes presets in the format of both .EQF and winamp.q1. Slider positions are rounded to the 64 steps the format has.
+pub fn write_eqf(presets: &[EqPreset]) -> Vec<u8> {
+    let mut data = HEADER.to_vec();
+    for preset in presets {
+        // Leave room for the terminator, without cutting a character in half.
+        let mut name_length = preset.name.len().min(NAME_LENGTH - 1);
+        while !preset.name.is_char_boundary(name_length) {
+            name_length -= 1;
+        }
+        let mut name = [0u8; NAME_LENGTH];
+        name[..name_length].copy_from_slice(&preset.name.as_bytes()[..name_length]);
+        data.extend_from_slice(&name);
+        data.extend(preset.bands.iter().map(|band| db_to_value(*band)));
+        data.push(db_to_value(preset.preamp));
+    }
+    data
+}
+
+fn value_to_db(value: u8) -> f32 {
+    let value = (value as f32).min(VALUE_STEPS);
+    (1.0 - value / VALUE_STEPS) * 2.0 * EQ_RANGE_DB - EQ_RANGE_DB
+}
+
+fn db_to_value(db: f32) -> u8 {
+    let db = db.clamp(-EQ_RANGE_DB, EQ_RANGE_DB);
+    ((1.0 - (db + EQ_RANGE_DB) / (2.0 * EQ_RANGE_DB)) * VALUE_STEPS).round() as u8
+}
+
+/// The user's presets, in the order they were saved.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct PresetStore {
+    presets: Vec<EqPreset>,
+}
+
+impl PresetStore {
+    pub fn presets(&self) -> &[EqPreset] {
+        &self.presets
+    }
+
+    /// Names are matched ignoring case, as Winamp does.
+    pub fn get(&self, name: &str) -> Option<&EqPreset> {
+        self.presets.iter().find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Adds the preset, replacing any with the same name.
+    pub fn save(&mut self, preset: EqPreset) {
+        match self.presets.iter_mut().find(|existing| existing.name.eq_ignore_ascii_case(&preset.name)) {
+            Some(existing) => *existing = preset,
+            None => self.presets.push(preset),
+        }
+    }
+
+    pub fn delete(&mut self, name: &str) {
+        self.presets.retain(|preset| !preset.name.eq_ignore_ascii_case(name));
+    }
+
+    /// Adds every preset in an .EQF or winamp.q1, returning them.
+    pub fn import(&mut self, path: &Path) -> Result<Vec<EqPreset>, PresetError> {
+        let presets = parse_eqf(&fs::read(path)?)?;
+        for preset in &presets {
+            self.save(preset.clone());
+        }
+        Ok(presets)
+    }
+
+    /// Writes the whole store, as a winamp.q1.
+    pub fn export(&self, path: &Path) -> Result<(), PresetError> {
+        Ok(fs::write(path, write_eqf(&self.presets))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture_preset(name: &str, values: [u8; VALUE_COUNT]) -> Vec<u8> {
+        let mut data = name.as_bytes().to_vec();
+        data.resize(NAME_LENGTH, 0);
+        data.extend_from_slice(&values);
+        data
+    }
+
+    #[test]
+    fn single_preset_round_trip() {
+        let mut eqf = b"Winamp EQ library file v1.1\x1a!--".to_vec();
+        eqf.extend(fixture_preset("Entry1", [0, 63, 21, 42, 0, 63, 21, 42, 0, 63, 0]));
+
+        let presets = parse_eqf(&eqf).unwrap();
+        assert_eq!(presets.len(), 1);
+        assert_eq!(presets[0].name, "Entry1");
+        assert_eq!(presets[0].bands[0], 12.0);
+        assert_eq!(presets[0].bands[1], -12.0);
+        assert!((presets[0].bands[2] - 4.0).abs() < 1e-4);
+        assert!((presets[0].bands[3] + 4.0).abs() < 1e-4);
+        assert_eq!(presets[0].preamp, 12.0);
+
+        assert_eq!(write_eqf(&presets), eqf);
+    }
+
+    #[test]
+    fn library_round_trip() {
+        let mut q1 = HEADER.to_vec();
+        q1.extend(fixture_preset("Classical", [31, 31, 31, 31, 31, 31, 44, 44, 44, 48, 31]));
+        q1.extend(fixture_preset("Rock", [18, 24, 42, 46, 39, 25, 16, 13, 13, 13, 33]));
+        q1.extend(fixture_preset("Flat", [31; VALUE_COUNT]));
+
+        let presets = parse_eqf(&q1).unwrap();
+        let names: Vec<_> = presets.iter().map(|preset| preset.name.as_str()).collect();
+        assert_eq!(names, ["Classical", "Rock", "Flat"]);
+        assert_eq!(write_eqf(&presets), q1);
+    }
+
+    #[test]
+    fn partial_trailing_preset_is_ignored() {
+        let mut q1 = HEADER.to_vec();
+        q1.extend(fixture_preset("Whole", [31; VALUE_COUNT]));
+        q1.extend(&fixture_preset("Cut off", [31; VALUE_COUNT])[..100]);
+        assert_eq!(parse_eqf(&q1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn bad_header() {
+        assert!(matches!(parse_eqf(b"Winamp EQ library file v1.0"), Err(PresetError::BadHeader)));
+    }
+
+    #[test]
+    fn settings_survive_quantisation() {
+        let mut settings = EqSettings::default();
+        settings.preamp = -3.5;
+        settings.bands = [-12.0, -8.0, -4.0, -1.0, 0.0, 1.0, 4.0, 8.0, 12.0, 30.0];
+        let written = write_eqf(&[EqPreset::new("Mine", &settings)]);
+        let read = &parse_eqf(&written).unwrap()[0];
+
+        // 0 dB sits exactly between two steps, so allow a little over half a step.
+        let tolerance = EQ_RANGE_DB / VALUE_STEPS + 1e-4;
+        assert!((read.preamp - settings.preamp).abs() <= tolerance);
+        for (read, written) in read.bands.iter().zip(settings.bands.iter()) {
+            assert!((read - written.clamp(-EQ_RANGE_DB, EQ_RANGE_DB)).abs() <= tolerance);
+        }
+    }
+}
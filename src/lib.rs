@@ -3,10 +3,19 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
 mod app;
+mod audio;
+mod eq_presets;
+mod playlist;
+mod scale;
 mod skin;
+mod skin_atlas;
+mod skin_config;
 mod skin_generated;
+mod tags;
 mod widgets;
+mod windows;
 pub use app::TemplateApp;
+pub use audio::OutputKind;
 
 // ----------------------------------------------------------------------------
 // When compiling for web:
@@ -24,3 +33,12 @@ pub fn start(canvas_id: &str) -> Result<(), eframe::wasm_bindgen::JsValue> {
     let app = TemplateApp::default();
     eframe::start_web(canvas_id, Box::new(app))
 }
+
+/// Like `start`, but with a skin (the bytes of a .wsz file) fetched by the page.
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen]
+pub fn start_with_skin(canvas_id: &str, skin: &[u8]) -> Result<(), eframe::wasm_bindgen::JsValue> {
+    let mut app = TemplateApp::default();
+    app.skin_bytes = Some(skin.to_vec());
+    eframe::start_web(canvas_id, Box::new(app))
+}
@@ -15,8 +15,14 @@ fn main() {
     if let Some(skin) = opt.skin {
         app.skin_path = Some(skin);
     }
-    let mut native_options = eframe::NativeOptions::default();
-    //native_options.decorated = false;
+    app.output = match opt.output.as_str() {
+        "null" => eframe_template::OutputKind::Null,
+        "wav" => eframe_template::OutputKind::WavFile(opt.output_file.unwrap_or_else(|| PathBuf::from("output.wav"))),
+        _ => eframe_template::OutputKind::Device,
+    };
+    // The skinned title bars move the windows around inside this one, but the OS decorations stay:
+    // eframe can't move, minimise or tell the focus of its own window, so without them it'd be stuck.
+    let native_options = eframe::NativeOptions::default();
     eframe::run_native(Box::new(app), native_options);
 }
 
@@ -25,6 +31,15 @@ fn main() {
 #[derive(Debug, StructOpt)]
 #[structopt(name = "example", about = "An example of StructOpt usage.")]
 struct Opt {
+    /// Skin to use: a .wsz/.zip file or an unpacked skin folder
     #[structopt(parse(from_os_str))]
-    pub skin: Option<PathBuf>
-}
\ No newline at end of file
+    pub skin: Option<PathBuf>,
+
+    /// Where to send audio: the sound device, nowhere (null), or a .wav file
+    #[structopt(long, default_value = "device", possible_values = &["device", "null", "wav"])]
+    pub output: String,
+
+    /// File to write when `--output wav` is used
+    #[structopt(long, parse(from_os_str))]
+    pub output_file: Option<PathBuf>,
+}
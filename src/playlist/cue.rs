@@ -0,0 +1,274 @@
+//! Cue sheets: tracks as INDEX points into one or more audio files, timed in CD frames of 1/75 s.
+//! A gap before a track (its INDEX 00) is played at the end of the track before, as it would be
+//! playing the CD straight through. PREGAP is silence that isn't in the file, so there's nothing to play.
+
+use std::{path::{Path, PathBuf}, time::Duration};
+
+use super::{relative_path, resolve_location, Location, PlaylistEntry, PlaylistError};
+use crate::audio::TrackSection;
+
+const FRAMES_PER_SECOND: u64 = 75;
+
+/// A TRACK, until we know where the next one starts.
+struct CueTrack {
+    file: PathBuf,
+    title: Option<String>,
+    performer: Option<String>,
+    /// INDEX 01, in frames
+    start: Option<u64>,
+    /// INDEX 00, in frames
+    gap: Option<u64>,
+}
+
+pub fn parse(text: &str, base: &Path) -> Result<Vec<PlaylistEntry>, PlaylistError> {
+    let mut album_performer = None;
+    let mut file: Option<PathBuf> = None;
+    let mut tracks: Vec<CueTrack> = vec![];
+    // False while in a data track, whose details we skip.
+    let mut in_audio_track = false;
+
+    for line in text.lines() {
+        let line = line.trim();
+        let (command, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        let rest = rest.trim();
+        match command.to_ascii_uppercase().as_str() {
+            "FILE" => {
+                // FILE "name" TYPE, and the quotes are optional if there are no spaces.
+                let name = rest.rsplit_once(char::is_whitespace).map_or(rest, |(name, _)| name);
+                file = match resolve_location(&unquote(name), base, false) {
+                    Location::Path(path) => Some(path),
+                    Location::Url(url) => return Err(PlaylistError::Malformed(format!("cue sheet FILE can't be a URL ({})", url))),
+                };
+                in_audio_track = false;
+            }
+            "TRACK" => {
+                in_audio_track = rest.to_ascii_uppercase().ends_with("AUDIO");
+                if in_audio_track {
+                    let file = file.clone().ok_or_else(|| PlaylistError::Malformed("cue sheet TRACK before FILE".to_string()))?;
+                    tracks.push(CueTrack {
+                        file,
+                        title: None,
+                        performer: None,
+                        start: None,
+                        gap: None,
+                    });
+                }
+            }
+            "TITLE" if in_audio_track => tracks.last_mut().unwrap().title = Some(unquote(rest)),
+            "PERFORMER" if in_audio_track => tracks.last_mut().unwrap().performer = Some(unquote(rest)),
+            // Before the first track these are for the whole album. Its title isn't any use to us.
+            "PERFORMER" if tracks.is_empty() => album_performer = Some(unquote(rest)),
+            "INDEX" if in_audio_track => {
+                let (number, time) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                let time = parse_time(time.trim()).ok_or_else(|| PlaylistError::Malformed(format!("cue sheet INDEX {}", rest)))?;
+                let track = tracks.last_mut().unwrap();
+                match number.parse::<u32>() {
+                    Ok(0) => track.gap = Some(time),
+                    Ok(1) => track.start = Some(time),
+                    // Later indices are points within the track.
+                    _ => (),
+                }
+            }
+            _ => (),
+        }
+    }
+
+    let mut entries = vec![];
+    for (i, track) in tracks.iter().enumerate() {
+        let start = track.start.or(track.gap).unwrap_or(0);
+        // The next track in the same file ends this one, including its gap.
+        let end = tracks
+            .get(i + 1)
+            .filter(|next| next.file == track.file)
+            .and_then(|next| next.start.or(next.gap))
+            .map(frames_to_duration);
+        let section = TrackSection {
+            start: frames_to_duration(start),
+            end,
+        };
+
+        let mut entry = PlaylistEntry::new(Location::Path(track.file.clone()));
+        entry.title = match (track.performer.as_ref().or(album_performer.as_ref()), &track.title) {
+            (Some(performer), Some(title)) => Some(format!("{} - {}", performer, title)),
+            (None, Some(title)) => Some(title.clone()),
+            (_, None) => None,
+        };
+        entry.duration = end.and_then(|end| end.checked_sub(section.start));
+        entry.section = Some(section);
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// One TRACK per entry, sharing a FILE where consecutive entries are from the same one.
+/// URLs can't go in a cue sheet so they're left out.
+pub fn write(entries: &[PlaylistEntry], base: &Path) -> String {
+    let mut text = String::new();
+    let mut current_file = None;
+    let mut number = 0;
+    for entry in entries {
+        let path = match &entry.location {
+            Location::Path(path) => path,
+            Location::Url(_) => continue,
+        };
+        if current_file != Some(path) {
+            let file_type = match path.extension().map(|extension| extension.to_string_lossy().to_ascii_lowercase()).as_deref() {
+                Some("mp3") => "MP3",
+                Some("aif") | Some("aiff") => "AIFF",
+                _ => "WAVE",
+            };
+            text.push_str(&format!("FILE \"{}\" {}\r\n", quotable(&relative_path(path, base)), file_type));
+            current_file = Some(path);
+        }
+        number += 1;
+        text.push_str(&format!("  TRACK {:02} AUDIO\r\n", number));
+        if let Some(title) = &entry.title {
+            text.push_str(&format!("    TITLE \"{}\"\r\n", quotable(title)));
+        }
+        let start = entry.section.map_or(0, |section| duration_to_frames(section.start));
+        text.push_str(&format!(
+            "    INDEX 01 {:02}:{:02}:{:02}\r\n",
+            start / FRAMES_PER_SECOND / 60,
+            start / FRAMES_PER_SECOND % 60,
+            start % FRAMES_PER_SECOND
+        ));
+    }
+    text
+}
+
+/// mm:ss:ff, where minutes can go past 59.
+fn parse_time(time: &str) -> Option<u64> {
+    let mut parts = time.split(':').map(|part| part.parse::<u64>().ok());
+    let (minutes, seconds, frames) = (parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() || seconds >= 60 || frames >= FRAMES_PER_SECOND {
+        return None;
+    }
+    Some((minutes * 60 + seconds) * FRAMES_PER_SECOND + frames)
+}
+
+/// Exact to the nanosecond, which is closer than any sample rate needs.
+fn frames_to_duration(frames: u64) -> Duration {
+    Duration::from_nanos((frames as u128 * 1_000_000_000 / FRAMES_PER_SECOND as u128) as u64)
+}
+
+fn duration_to_frames(duration: Duration) -> u64 {
+    ((duration.as_nanos() * FRAMES_PER_SECOND as u128 + 500_000_000) / 1_000_000_000) as u64
+}
+
+fn unquote(text: &str) -> String {
+    let text = text.trim();
+    match text.strip_prefix('"') {
+        Some(quoted) => quoted.split('"').next().unwrap_or_default().to_string(),
+        None => text.to_string(),
+    }
+}
+
+/// There's no escaping a quote inside a quoted string.
+fn quotable(text: &str) -> String {
+    text.replace('"', "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHEET: &str = "REM GENRE Rock\r\n\
+                         PERFORMER \"Album Artist\"\r\n\
+                         TITLE \"The Album\"\r\n\
+                         FILE \"disc one.wav\" WAVE\r\n\
+                         \x20 TRACK 01 AUDIO\r\n\
+                         \x20   TITLE \"First\"\r\n\
+                         \x20   INDEX 01 00:00:00\r\n\
+                         \x20 TRACK 02 AUDIO\r\n\
+                         \x20   TITLE \"Second\"\r\n\
+                         \x20   PERFORMER \"Guest\"\r\n\
+                         \x20   INDEX 00 03:58:50\r\n\
+                         \x20   INDEX 01 04:00:00\r\n\
+                         \x20   INDEX 02 05:00:00\r\n\
+                         \x20 TRACK 03 MODE1/2352\r\n\
+                         \x20   TITLE \"Data\"\r\n\
+                         \x20   INDEX 01 06:00:00\r\n\
+                         FILE two.flac WAVE\r\n\
+                         \x20 TRACK 04 AUDIO\r\n\
+                         \x20   INDEX 00 00:00:00\r\n\
+                         \x20   INDEX 01 00:02:00\r\n\
+                         \x20 TRACK 05 AUDIO\r\n\
+                         \x20   TITLE \"Last\"\r\n\
+                         \x20   INDEX 00 10:00:00\r\n";
+
+    fn section(start: Duration, end: Option<Duration>) -> Option<TrackSection> {
+        Some(TrackSection { start, end })
+    }
+
+    #[test]
+    fn sheet() {
+        let entries = parse(SHEET, Path::new("/music")).unwrap();
+        let secs = Duration::from_secs;
+        assert_eq!(entries.len(), 4, "the data track is left out");
+
+        assert_eq!(entries[0].location, Location::Path(PathBuf::from("/music/disc one.wav")));
+        assert_eq!(entries[0].title.as_deref(), Some("Album Artist - First"));
+        // Runs to the next track's INDEX 01, so its gap is played at the end of this one.
+        assert_eq!(entries[0].section, section(secs(0), Some(secs(240))));
+        assert_eq!(entries[0].duration, Some(secs(240)));
+
+        assert_eq!(entries[1].title.as_deref(), Some("Guest - Second"));
+        // The last track in its file plays to the end of it.
+        assert_eq!(entries[1].section, section(secs(240), None));
+        assert_eq!(entries[1].duration, None);
+
+        assert_eq!(entries[2].location, Location::Path(PathBuf::from("/music/two.flac")));
+        assert_eq!(entries[2].title, None);
+        assert_eq!(entries[2].section, section(secs(2), Some(secs(600))));
+        assert_eq!(entries[2].duration, Some(secs(598)));
+
+        // With only an INDEX 00, that's where it starts.
+        assert_eq!(entries[3].title.as_deref(), Some("Album Artist - Last"));
+        assert_eq!(entries[3].section, section(secs(600), None));
+    }
+
+    #[test]
+    fn malformed_sheets() {
+        let malformed = |text| matches!(parse(text, Path::new("/music")), Err(PlaylistError::Malformed(_)));
+        assert!(malformed("TRACK 01 AUDIO\nINDEX 01 00:00:00\n"));
+        assert!(malformed("FILE \"http://example.com/a.mp3\" MP3\n"));
+        assert!(malformed("FILE a.wav WAVE\nTRACK 01 AUDIO\nINDEX 01 00:60:00\n"));
+    }
+
+    #[test]
+    fn times() {
+        assert_eq!(parse_time("00:00:00"), Some(0));
+        assert_eq!(parse_time("01:02:03"), Some((60 + 2) * 75 + 3));
+        // Minutes can go past an hour, CDs are 79 minutes long.
+        assert_eq!(parse_time("79:59:74"), Some((79 * 60 + 59) * 75 + 74));
+        assert_eq!(parse_time("00:60:00"), None);
+        assert_eq!(parse_time("00:00:75"), None);
+        assert_eq!(parse_time("01:02"), None);
+        assert_eq!(parse_time("01:02:03:04"), None);
+        assert_eq!(parse_time("aa:bb:cc"), None);
+    }
+
+    #[test]
+    fn frames_round_trip() {
+        assert_eq!(frames_to_duration(75), Duration::from_secs(1));
+        assert_eq!(frames_to_duration(1), Duration::from_nanos(13_333_333));
+        for frames in (0..75 * 3).chain([75 * 60 * 79 + 74, u32::MAX as u64]) {
+            assert_eq!(duration_to_frames(frames_to_duration(frames)), frames);
+        }
+        // Rounded to the nearest frame
+        assert_eq!(duration_to_frames(Duration::from_millis(6)), 0);
+        assert_eq!(duration_to_frames(Duration::from_millis(7)), 1);
+    }
+
+    #[test]
+    fn written_sheet_reads_back() {
+        let entries = parse(SHEET, Path::new("/music")).unwrap();
+        let written = write(&entries, Path::new("/music"));
+        assert!(written.starts_with("FILE \"disc one.wav\" WAVE\r\n  TRACK 01 AUDIO\r\n"));
+        assert_eq!(written.matches("FILE ").count(), 2);
+
+        let read = parse(&written, Path::new("/music")).unwrap();
+        let sections: Vec<_> = read.iter().map(|entry| entry.section).collect();
+        assert_eq!(sections, entries.iter().map(|entry| entry.section).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,100 @@
+//! M3U and M3U8: one location per line, optionally preceded by `#EXTINF:<seconds>,<title>`.
+
+use std::{path::Path, time::Duration};
+
+use super::{relative_path, resolve_location, Location, PlaylistEntry};
+
+pub fn parse(text: &str, base: &Path) -> Vec<PlaylistEntry> {
+    let mut entries = vec![];
+    // The #EXTINF line applies to the next location.
+    let mut info: Option<(Option<Duration>, Option<String>)> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if let Some(extinf) = line.strip_prefix("#EXTINF:") {
+            let (length, title) = extinf.split_once(',').unwrap_or((extinf, ""));
+            // Some players put attributes after the length, e.g. `-1 tvg-id="..."`.
+            let seconds = length.split_whitespace().next().and_then(|seconds| seconds.parse::<f64>().ok());
+            // Negative is unknown, and anything else Duration can't hold is nonsense.
+            let duration = seconds.and_then(|seconds| Duration::try_from_secs_f64(seconds).ok());
+            let title = Some(title.trim().to_string()).filter(|title| !title.is_empty());
+            info = Some((duration, title));
+        } else if !line.is_empty() && !line.starts_with('#') {
+            let mut entry = PlaylistEntry::new(resolve_location(line, base, false));
+            if let Some((duration, title)) = info.take() {
+                entry.duration = duration;
+                entry.title = title;
+            }
+            entries.push(entry);
+        }
+    }
+    entries
+}
+
+/// Extended M3U, with CRLF line endings as Winamp writes them.
+pub fn write(entries: &[PlaylistEntry], base: &Path) -> String {
+    let mut text = String::from("#EXTM3U\r\n");
+    for entry in entries {
+        if entry.title.is_some() || entry.duration.is_some() {
+            let seconds = entry.duration.map_or(-1, |duration| duration.as_secs() as i64);
+            text.push_str(&format!("#EXTINF:{},{}\r\n", seconds, entry.display_title()));
+        }
+        match &entry.location {
+            Location::Path(path) => text.push_str(&relative_path(path, base)),
+            Location::Url(url) => text.push_str(url),
+        }
+        text.push_str("\r\n");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn extinf() {
+        let text = "#EXTM3U\r\n\
+                    #EXTINF:123,Artist - Title, with a comma\r\n\
+                    01.mp3\r\n\
+                    \r\n\
+                    # a comment\r\n\
+                    02.mp3\r\n\
+                    #EXTINF:-1 tvg-id=\"x\",Radio\r\n\
+                    http://example.com/stream\r\n\
+                    #EXTINF:12.5,\r\n\
+                    03.mp3\r\n";
+        let entries = parse(text, Path::new("/music"));
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].location, Location::Path(PathBuf::from("/music/01.mp3")));
+        assert_eq!(entries[0].title.as_deref(), Some("Artist - Title, with a comma"));
+        assert_eq!(entries[0].duration, Some(Duration::from_secs(123)));
+        // An #EXTINF only goes with the location right after it.
+        assert_eq!(entries[1].title, None);
+        assert_eq!(entries[1].duration, None);
+        assert_eq!(entries[2].location, Location::Url("http://example.com/stream".to_string()));
+        assert_eq!(entries[2].title.as_deref(), Some("Radio"));
+        assert_eq!(entries[2].duration, None);
+        assert_eq!(entries[3].title, None);
+        assert_eq!(entries[3].duration, Some(Duration::from_millis(12500)));
+    }
+
+    #[test]
+    fn unusable_lengths() {
+        let text = "#EXTINF:inf,Forever\n01.mp3\n\
+                    #EXTINF:1e30,Too long\n02.mp3\n\
+                    #EXTINF:NaN,Not a number\n03.mp3\n\
+                    #EXTINF:-0.5,Negative\n04.mp3\n";
+        let entries = parse(text, Path::new("/music"));
+        assert_eq!(entries.len(), 4);
+        assert!(entries.iter().all(|entry| entry.duration.is_none() && entry.title.is_some()));
+    }
+
+    #[test]
+    fn written_like_winamp() {
+        let mut entry = PlaylistEntry::new(Location::Path(PathBuf::from("/music/album/01.mp3")));
+        entry.title = Some("Title".to_string());
+        let entries = [entry, PlaylistEntry::new(Location::Path(PathBuf::from("/music/02.mp3")))];
+        assert_eq!(write(&entries, Path::new("/music")), "#EXTM3U\r\n#EXTINF:-1,Title\r\nalbum/01.mp3\r\n02.mp3\r\n");
+    }
+}
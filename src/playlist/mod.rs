@@ -0,0 +1,582 @@
+//! The list of tracks to play, and the playlist files it can be loaded from and saved to.
+//! Paths in playlist files may be relative, in which case they're relative to the playlist file.
+
+use std::{fmt, fs, io, path::{Path, PathBuf}, sync::atomic::{AtomicU64, Ordering}, time::Duration};
+
+use thiserror::Error;
+
+use crate::{audio::TrackSection, tags::{TagReader, TitleFormat}};
+
+pub mod cue;
+pub mod m3u;
+pub mod order;
+pub mod pls;
+pub mod xspf;
+
+/// What adding a folder picks up. The decoders go by content, but a folder is full of other things.
+const AUDIO_EXTENSIONS: &[&str] = &["mp3", "flac", "ogg", "oga", "opus", "wav", "aif", "aiff", "aifc"];
+
+/// Shared by every playlist, so one that replaces another never ends up with the same generation.
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+#[derive(Error, Debug)]
+pub enum PlaylistError {
+    #[error("IO error")]
+    Io(#[from] io::Error),
+    #[error("Unsupported playlist {0}")]
+    UnsupportedFormat(String),
+    #[error("Malformed playlist {0}")]
+    Malformed(String),
+}
+
+/// Where an entry's audio comes from.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Location {
+    Path(PathBuf),
+    /// Anything with a scheme other than file://, e.g. a stream
+    Url(String),
+}
+
+impl Location {
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Location::Path(path) => Some(path),
+            Location::Url(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Location::Path(path) => write!(f, "{}", path.display()),
+            Location::Url(url) => write!(f, "{}", url),
+        }
+    }
+}
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistEntry {
+    pub location: Location,
+    /// From the playlist file, or the track itself once it's been read
+    pub title: Option<String>,
+    pub duration: Option<Duration>,
+    /// Part of a longer file, for tracks from a cue sheet
+    pub section: Option<TrackSection>,
+    pub selected: bool,
+    /// Whether the title and duration have been filled in from the track's tags yet
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pub tags_read: bool,
+}
+
+impl PlaylistEntry {
+    pub fn new(location: Location) -> Self {
+        Self {
+            location,
+            title: None,
+            duration: None,
+            section: None,
+            selected: false,
+            tags_read: false,
+        }
+    }
+
+    /// The title if there is one, otherwise the file name without its extension.
+    pub fn display_title(&self) -> String {
+        if let Some(title) = &self.title {
+            return title.clone();
+        }
+        match &self.location {
+            Location::Path(path) => path.file_stem().unwrap_or_else(|| path.as_os_str()).to_string_lossy().into_owned(),
+            Location::Url(url) => url.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlaylistFormat {
+    /// Latin-1, unless it turns out to be valid UTF-8
+    M3u,
+    M3u8,
+    Pls,
+    Xspf,
+    Cue,
+}
+
+impl PlaylistFormat {
+    /// Going by the extension, as every other player does.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_string_lossy().to_ascii_lowercase();
+        match extension.as_str() {
+            "m3u" => Some(PlaylistFormat::M3u),
+            "m3u8" => Some(PlaylistFormat::M3u8),
+            "pls" => Some(PlaylistFormat::Pls),
+            "xspf" => Some(PlaylistFormat::Xspf),
+            "cue" => Some(PlaylistFormat::Cue),
+            _ => None,
+        }
+    }
+
+    /// Relative locations are resolved against `base`, the folder the playlist is in.
+    pub fn parse(self, data: &[u8], base: &Path) -> Result<Vec<PlaylistEntry>, PlaylistError> {
+        match self {
+            PlaylistFormat::M3u => Ok(m3u::parse(&decode_text(data, false), base)),
+            PlaylistFormat::M3u8 => Ok(m3u::parse(&decode_text(data, true), base)),
+            PlaylistFormat::Pls => pls::parse(&decode_text(data, false), base),
+            PlaylistFormat::Xspf => xspf::parse(&decode_text(data, true), base),
+            PlaylistFormat::Cue => cue::parse(&decode_text(data, false), base),
+        }
+    }
+
+    /// Entries inside `base` are written relative to it, so the playlist can move along with its tracks.
+    /// Only cue sheets can say where in a file a track starts, the others just list the file.
+    pub fn write(self, entries: &[PlaylistEntry], base: &Path) -> Vec<u8> {
+        match self {
+            PlaylistFormat::M3u => encode_latin1(&m3u::write(entries, base)),
+            PlaylistFormat::M3u8 => m3u::write(entries, base).into_bytes(),
+            PlaylistFormat::Pls => pls::write(entries, base).into_bytes(),
+            PlaylistFormat::Xspf => xspf::write(entries, base).into_bytes(),
+            PlaylistFormat::Cue => encode_latin1(&cue::write(entries, base)),
+        }
+    }
+}
+
+/// Tracks in play order, along with which one is playing.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct Playlist {
+    entries: Vec<PlaylistEntry>,
+    current: Option<usize>,
+    /// Changes whenever entries are added, removed or moved, so anything holding on to indices knows they're stale
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    generation: u64,
+}
+
+impl Playlist {
+    pub fn load(path: &Path) -> Result<Self, PlaylistError> {
+        let format = PlaylistFormat::from_path(path).ok_or_else(|| PlaylistError::UnsupportedFormat(path.display().to_string()))?;
+        let entries = format.parse(&fs::read(path)?, path.parent().unwrap_or_else(|| Path::new("")))?;
+        Ok(Self { entries, current: None, generation: next_generation() })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PlaylistError> {
+        let format = PlaylistFormat::from_path(path).ok_or_else(|| PlaylistError::UnsupportedFormat(path.display().to_string()))?;
+        Ok(fs::write(path, format.write(&self.entries, path.parent().unwrap_or_else(|| Path::new(""))))?)
+    }
+
+    pub fn entries(&self) -> &[PlaylistEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Same as it was only if no entry has been added, removed or moved since, and it's still the same playlist.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Returns the index it was added at.
+    pub fn add(&mut self, entry: PlaylistEntry) -> usize {
+        self.entries.push(entry);
+        self.generation = next_generation();
+        self.entries.len() - 1
+    }
+
+    pub fn extend(&mut self, entries: impl IntoIterator<Item = PlaylistEntry>) {
+        self.entries.extend(entries);
+        self.generation = next_generation();
+    }
+
+    /// Adds every audio file in `dir` and the folders inside it, ordered by path.
+    /// Returns how many were added.
+    pub fn add_directory(&mut self, dir: &Path) -> Result<usize, PlaylistError> {
+        let mut files = vec![];
+        let mut dirs = vec![dir.to_path_buf()];
+        while let Some(dir) = dirs.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.is_dir() {
+                    dirs.push(path);
+                } else if is_audio_file(&path) {
+                    files.push(path);
+                }
+            }
+        }
+        files.sort();
+        let count = files.len();
+        self.extend(files.into_iter().map(|path| PlaylistEntry::new(Location::Path(path))));
+        Ok(count)
+    }
+
+    /// Adds a track, or everything in a playlist file. Returns how many were added.
+    pub fn add_file(&mut self, path: &Path) -> Result<usize, PlaylistError> {
+        if PlaylistFormat::from_path(path).is_some() {
+            let loaded = Playlist::load(path)?;
+            let count = loaded.len();
+            self.extend(loaded.entries);
+            Ok(count)
+        } else {
+            self.add(PlaylistEntry::new(Location::Path(path.to_path_buf())));
+            Ok(1)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.current = None;
+        self.generation = next_generation();
+    }
+
+    pub fn current(&self) -> Option<usize> {
+        self.current
+    }
+
+    pub fn current_entry(&self) -> Option<&PlaylistEntry> {
+        self.entries.get(self.current?)
+    }
+
+    /// Indices past the end are ignored.
+    pub fn set_current(&mut self, index: Option<usize>) {
+        self.current = index.filter(|index| *index < self.entries.len());
+    }
+
+    pub fn select(&mut self, index: usize, selected: bool) {
+        if let Some(entry) = self.entries.get_mut(index) {
+            entry.selected = selected;
+        }
+    }
+
+    pub fn select_all(&mut self, selected: bool) {
+        for entry in &mut self.entries {
+            entry.selected = selected;
+        }
+    }
+
+    pub fn selected(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entries.iter().enumerate().filter(|(_, entry)| entry.selected).map(|(index, _)| index)
+    }
+
+    pub fn invert_selection(&mut self) {
+        for entry in &mut self.entries {
+            entry.selected = !entry.selected;
+        }
+    }
+
+    pub fn remove_selected(&mut self) {
+        self.remove_where(|entry| entry.selected);
+    }
+
+    /// Removes everything that isn't selected.
+    pub fn crop(&mut self) {
+        self.remove_where(|entry| !entry.selected);
+    }
+
+    /// Removes files that no longer exist. URLs are left alone, as there's no telling.
+    pub fn remove_missing(&mut self) {
+        self.remove_where(|entry| entry.location.path().is_some_and(|path| !path.exists()));
+    }
+
+    /// The current entry stays current unless it was one of those removed.
+    pub fn remove_where(&mut self, mut remove: impl FnMut(&PlaylistEntry) -> bool) {
+        let keep: Vec<bool> = self.entries.iter().map(|entry| !remove(entry)).collect();
+        if let Some(current) = self.current {
+            self.current = if keep[current] { Some(keep[..current].iter().filter(|keep| **keep).count()) } else { None };
+        }
+        let mut keep = keep.into_iter();
+        self.entries.retain(|_| keep.next().unwrap_or(true));
+        self.generation = next_generation();
+    }
+
+    /// Stable, and the current entry stays current wherever it ends up.
+    pub fn sort_by_key<K: Ord>(&mut self, mut key: impl FnMut(&PlaylistEntry) -> K) {
+        let current = self.current;
+        let mut entries: Vec<(usize, PlaylistEntry)> = self.entries.drain(..).enumerate().collect();
+        entries.sort_by_key(|(_, entry)| key(entry));
+        self.current = current.and_then(|current| entries.iter().position(|(index, _)| *index == current));
+        self.entries = entries.into_iter().map(|(_, entry)| entry).collect();
+        self.generation = next_generation();
+    }
+
+    pub fn reverse(&mut self) {
+        self.entries.reverse();
+        let len = self.entries.len();
+        self.current = self.current.map(|current| len - 1 - current);
+        self.generation = next_generation();
+    }
+
+    /// Hands entries that haven't been read yet to `reader`, then names entries from the tags it has
+    /// read so far and fills in their durations. Returns whether it's still reading.
+    /// Cue sheet tracks keep the sheet's titles.
+    pub fn read_tags(&mut self, reader: &mut TagReader, title_format: &TitleFormat) -> bool {
+        for entry in self.entries.iter_mut().filter(|entry| !entry.tags_read) {
+            entry.tags_read = true;
+            if let (Location::Path(path), None) = (&entry.location, &entry.section) {
+                reader.request(path.clone());
+            }
+        }
+        // Entries may have moved or gone while the tags were being read, so they're found again by path.
+        for (path, info) in reader.finished() {
+            let info = match info {
+                Some(info) => info,
+                None => continue,
+            };
+            let entries = self.entries.iter_mut().filter(|entry| entry.section.is_none() && entry.location.path() == Some(path.as_path()));
+            for entry in entries {
+                if info.artist.is_some() || info.title.is_some() {
+                    entry.title = Some(title_format.format(&info, &path));
+                }
+                entry.duration = info.duration.or(entry.duration);
+            }
+        }
+        reader.is_busy()
+    }
+
+    /// For when the title format changes.
+    pub fn forget_tags(&mut self) {
+        for entry in &mut self.entries {
+            entry.tags_read = false;
+        }
+    }
+}
+
+fn next_generation() -> u64 {
+    NEXT_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| AUDIO_EXTENSIONS.iter().any(|audio| extension.eq_ignore_ascii_case(audio)))
+}
+
+/// A location as written in a playlist. `uri` is for formats where paths are percent-encoded URIs rather than plain paths.
+fn resolve_location(text: &str, base: &Path, uri: bool) -> Location {
+    let text = text.trim();
+    if let Some(rest) = strip_scheme(text, "file") {
+        // file://host/path, where the host is almost always empty or localhost.
+        let path = rest.strip_prefix("localhost").unwrap_or(rest);
+        let path = percent_decode(path);
+        // file:///C:/music on Windows
+        #[cfg(windows)]
+        let path = path.strip_prefix('/').filter(|p| p.get(1..2) == Some(":")).map(str::to_string).unwrap_or(path);
+        return Location::Path(PathBuf::from(path));
+    }
+    if has_scheme(text) {
+        return Location::Url(text.to_string());
+    }
+
+    let text = if uri { percent_decode(text) } else { text.to_string() };
+    // Winamp writes backslashes, which are just part of the name elsewhere.
+    #[cfg(not(windows))]
+    let text = text.replace('\\', "/");
+    let path = PathBuf::from(text);
+    if path.is_absolute() {
+        Location::Path(path)
+    } else {
+        Location::Path(base.join(path))
+    }
+}
+
+/// A path relative to `base` if it's inside it, otherwise as it is.
+fn relative_path(path: &Path, base: &Path) -> String {
+    match path.strip_prefix(base) {
+        Ok(relative) if !base.as_os_str().is_empty() || path.is_relative() => relative.to_string_lossy().into_owned(),
+        _ => path.to_string_lossy().into_owned(),
+    }
+}
+
+/// `scheme://...`, where the scheme is at least two characters so `C://` isn't one.
+fn has_scheme(text: &str) -> bool {
+    match text.split_once("://") {
+        Some((scheme, _)) => {
+            scheme.len() > 1
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+        }
+        None => false,
+    }
+}
+
+fn strip_scheme<'a>(text: &'a str, scheme: &str) -> Option<&'a str> {
+    let (found, rest) = text.split_once("://")?;
+    if found.eq_ignore_ascii_case(scheme) {
+        Some(rest)
+    } else {
+        None
+    }
+}
+
+/// Invalid escapes are left as they are.
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok()).and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Everything but unreserved characters and path separators.
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Playlists from Windows players are in the system codepage, which we take to be Latin-1
+/// unless the file is valid UTF-8 (or `utf8` says it must be).
+fn decode_text(data: &[u8], utf8: bool) -> String {
+    let data = data.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(data);
+    match std::str::from_utf8(data) {
+        Ok(text) => text.to_string(),
+        Err(_) if utf8 => String::from_utf8_lossy(data).into_owned(),
+        Err(_) => data.iter().map(|byte| *byte as char).collect(),
+    }
+}
+
+/// Falls back to UTF-8 if anything can't be written as Latin-1, rather than losing characters.
+fn encode_latin1(text: &str) -> Vec<u8> {
+    if text.chars().all(|c| (c as u32) < 256) {
+        text.chars().map(|c| c as u8).collect()
+    } else {
+        text.as_bytes().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_read_in_the_background() {
+        let path = std::env::temp_dir().join(format!("playlist-test-tags-{}.wav", std::process::id()));
+        let spec = hound::WavSpec { channels: 1, sample_rate: 8000, bits_per_sample: 16, sample_format: hound::SampleFormat::Int };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for _ in 0..8000 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut playlist = Playlist::default();
+        playlist.add(PlaylistEntry::new(Location::Url("http://example.com/stream".to_string())));
+        playlist.add(PlaylistEntry::new(Location::Path(path.clone())));
+        playlist.add(PlaylistEntry::new(Location::Path(path.with_extension("missing"))));
+        let mut reader = TagReader::new();
+        let started = std::time::Instant::now();
+        while playlist.read_tags(&mut reader, &TitleFormat::default()) {
+            assert!(started.elapsed() < Duration::from_secs(10), "tags never finished");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        let _ = std::fs::remove_file(&path);
+
+        let durations: Vec<_> = playlist.entries().iter().map(|entry| entry.duration).collect();
+        assert_eq!(durations, [None, Some(Duration::from_secs(1)), None]);
+        assert!(playlist.entries().iter().all(|entry| entry.tags_read && entry.title.is_none()));
+        // Nothing's read twice.
+        assert!(!playlist.read_tags(&mut reader, &TitleFormat::default()));
+    }
+
+    #[test]
+    fn latin1_unless_utf8() {
+        assert_eq!(decode_text(b"Bj\xF6rk.mp3", false), "Björk.mp3");
+        assert_eq!(decode_text("Björk.mp3".as_bytes(), false), "Björk.mp3");
+        assert_eq!(decode_text(b"\xEF\xBB\xBFBj\xC3\xB6rk.mp3", false), "Björk.mp3");
+        // An .m3u8 is always UTF-8, so bad bytes are replaced rather than read as Latin-1.
+        assert_eq!(decode_text(b"Bj\xF6rk.mp3", true), "Bj\u{FFFD}rk.mp3");
+    }
+
+    #[test]
+    fn latin1_unless_it_does_not_fit() {
+        assert_eq!(encode_latin1("Björk"), b"Bj\xF6rk");
+        assert_eq!(encode_latin1("坂本龍一"), "坂本龍一".as_bytes());
+    }
+
+    #[test]
+    fn locations() {
+        let base = Path::new("/music/lists");
+        assert_eq!(resolve_location("song.mp3", base, false), Location::Path(PathBuf::from("/music/lists/song.mp3")));
+        assert_eq!(resolve_location("../a b/song.mp3", base, false), Location::Path(PathBuf::from("/music/lists/../a b/song.mp3")));
+        assert_eq!(resolve_location("/elsewhere/song.mp3", base, false), Location::Path(PathBuf::from("/elsewhere/song.mp3")));
+        assert_eq!(resolve_location("a%20b.mp3", base, false), Location::Path(PathBuf::from("/music/lists/a%20b.mp3")));
+        assert_eq!(resolve_location("a%20b.mp3", base, true), Location::Path(PathBuf::from("/music/lists/a b.mp3")));
+        assert_eq!(resolve_location("file:///music/a%20b.mp3", base, false), Location::Path(PathBuf::from("/music/a b.mp3")));
+        assert_eq!(resolve_location("file://localhost/music/a.mp3", base, false), Location::Path(PathBuf::from("/music/a.mp3")));
+        assert_eq!(resolve_location(" http://example.com/stream ", base, false), Location::Url("http://example.com/stream".to_string()));
+        #[cfg(not(windows))]
+        assert_eq!(resolve_location(r"Album\01.mp3", base, false), Location::Path(PathBuf::from("/music/lists/Album/01.mp3")));
+    }
+
+    #[test]
+    fn relative_paths() {
+        let base = Path::new("/music");
+        assert_eq!(relative_path(Path::new("/music/album/01.mp3"), base), "album/01.mp3");
+        assert_eq!(relative_path(Path::new("/elsewhere/01.mp3"), base), "/elsewhere/01.mp3");
+        assert_eq!(relative_path(Path::new("/music/01.mp3"), Path::new("")), "/music/01.mp3");
+    }
+
+    #[test]
+    fn schemes() {
+        assert!(has_scheme("http://example.com"));
+        assert!(has_scheme("rtsp+tcp://example.com"));
+        assert!(!has_scheme("C://music"));
+        assert!(!has_scheme("music/01.mp3"));
+        assert_eq!(strip_scheme("FILE:///music", "file"), Some("/music"));
+    }
+
+    #[test]
+    fn percent_encoding_round_trip() {
+        let name = "/music/AC/DC - Back in Black (50%).mp3";
+        assert_eq!(percent_encode(name), "/music/AC/DC%20-%20Back%20in%20Black%20%2850%25%29.mp3");
+        assert_eq!(percent_decode(&percent_encode(name)), name);
+        assert_eq!(percent_decode("caf%C3%A9"), "café");
+        // Not escapes, so left alone
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz%4"), "%zz%4");
+    }
+
+    #[test]
+    fn every_format_round_trips() {
+        let base = Path::new("/music");
+        let mut titled = PlaylistEntry::new(Location::Path(PathBuf::from("/music/Björk/01 Hyperballad.mp3")));
+        titled.title = Some("Björk - Hyperballad & <more>".to_string());
+        titled.duration = Some(Duration::from_secs(321));
+        let untitled = PlaylistEntry::new(Location::Path(PathBuf::from("/elsewhere/02.flac")));
+        let stream = PlaylistEntry::new(Location::Url("http://example.com/stream".to_string()));
+        let entries = vec![titled, untitled, stream];
+
+        for format in [PlaylistFormat::M3u, PlaylistFormat::M3u8, PlaylistFormat::Pls, PlaylistFormat::Xspf] {
+            let written = format.write(&entries, base);
+            let read = format.parse(&written, base).unwrap();
+            assert_eq!(read, entries, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn formats_from_extensions() {
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.M3U")), Some(PlaylistFormat::M3u));
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.m3u8")), Some(PlaylistFormat::M3u8));
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.pls")), Some(PlaylistFormat::Pls));
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.xspf")), Some(PlaylistFormat::Xspf));
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.cue")), Some(PlaylistFormat::Cue));
+        assert_eq!(PlaylistFormat::from_path(Path::new("a.mp3")), None);
+        assert_eq!(PlaylistFormat::from_path(Path::new("m3u")), None);
+    }
+}
@@ -0,0 +1,339 @@
+//! Which track comes next: in order or shuffled, with or without repeat.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+
+use super::Playlist;
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RepeatMode {
+    #[default]
+    Off,
+    /// Back to the start of the playlist (or a fresh shuffle) after the last track
+    All,
+    /// The same track again when it ends. Next and previous still move on.
+    One,
+}
+
+impl RepeatMode {
+    /// What the repeat button steps through.
+    pub fn cycled(self) -> Self {
+        match self {
+            RepeatMode::Off => RepeatMode::All,
+            RepeatMode::All => RepeatMode::One,
+            RepeatMode::One => RepeatMode::Off,
+        }
+    }
+}
+
+/// Shuffle plays every track once, in a random order, before any of them come round again.
+/// Each time round is a pass; the next pass never starts with the track that ended the last.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, Default)]
+pub struct PlayOrder {
+    pub shuffle: bool,
+    pub repeat: RepeatMode,
+    /// What's left of this pass, next track last
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    upcoming: Vec<usize>,
+    /// Tracks shuffle has played, most recent last, for previous to walk back through
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    history: Vec<usize>,
+    /// Generation of the playlist the pass was made for. Any change and it starts over.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    pass: Option<u64>,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    rng: u64,
+}
+
+impl PlayOrder {
+    pub fn set_shuffle(&mut self, shuffle: bool) {
+        self.shuffle = shuffle;
+        self.upcoming.clear();
+        self.history.clear();
+        self.pass = None;
+    }
+
+    /// The track to play when the current one ends by itself, or None to stop.
+    pub fn after_finished(&mut self, playlist: &Playlist) -> Option<usize> {
+        let current = playlist.current();
+        if self.repeat == RepeatMode::One && current.is_some() {
+            return current;
+        }
+        self.advance(playlist, self.repeat == RepeatMode::All)
+    }
+
+    /// The track for the next button, which always goes somewhere unless the playlist is empty.
+    pub fn next(&mut self, playlist: &Playlist) -> Option<usize> {
+        self.advance(playlist, true)
+    }
+
+    /// The track for the previous button. Shuffle goes back the way it came; once there's no more
+    /// history it stays put.
+    pub fn previous(&mut self, playlist: &Playlist) -> Option<usize> {
+        let (len, current) = (playlist.len(), playlist.current());
+        if len == 0 {
+            return None;
+        }
+        if !self.shuffle {
+            return Some(match current {
+                Some(current) if current > 0 => current - 1,
+                _ => len - 1,
+            });
+        }
+        self.check_pass(playlist);
+        match self.history.pop() {
+            Some(previous) => {
+                // So next comes back here, rather than skipping ahead.
+                if let Some(current) = current {
+                    self.upcoming.push(current);
+                }
+                Some(previous)
+            }
+            None => current,
+        }
+    }
+
+    /// For when a track is picked by hand, before it's made current, so shuffle counts it as played
+    /// and can come back to where it was.
+    pub fn jumped(&mut self, playlist: &Playlist, to: usize) {
+        if !self.shuffle {
+            return;
+        }
+        self.check_pass(playlist);
+        if let Some(from) = playlist.current().filter(|from| *from != to) {
+            self.history.push(from);
+        }
+        self.upcoming.retain(|index| *index != to);
+    }
+
+    /// `wrap` says whether to carry on from the start once everything's been played.
+    fn advance(&mut self, playlist: &Playlist, wrap: bool) -> Option<usize> {
+        let (len, current) = (playlist.len(), playlist.current());
+        if len == 0 {
+            return None;
+        }
+        if !self.shuffle {
+            return match current {
+                None => Some(0),
+                Some(current) if current + 1 < len => Some(current + 1),
+                Some(_) if wrap => Some(0),
+                Some(_) => None,
+            };
+        }
+
+        self.check_pass(playlist);
+        if self.upcoming.is_empty() {
+            if !wrap {
+                return None;
+            }
+            self.new_pass(playlist);
+        }
+        let next = self.upcoming.pop()?;
+        if let Some(current) = current {
+            self.history.push(current);
+        }
+        Some(next)
+    }
+
+    /// Starts over if the playlist has changed since the pass was made, as the indices won't mean the same tracks.
+    fn check_pass(&mut self, playlist: &Playlist) {
+        if self.pass != Some(playlist.generation()) {
+            self.history.clear();
+            self.new_pass(playlist);
+        }
+    }
+
+    /// Everything but the current track, which has already been heard, in a random order.
+    /// A later pass still includes it, just not first.
+    fn new_pass(&mut self, playlist: &Playlist) {
+        let (len, current) = (playlist.len(), playlist.current());
+        let first_pass = self.pass != Some(playlist.generation());
+        self.pass = Some(playlist.generation());
+        self.upcoming = (0..len).filter(|index| !(first_pass && Some(*index) == current)).collect();
+        for i in (1..self.upcoming.len()).rev() {
+            let j = (self.random() % (i as u64 + 1)) as usize;
+            self.upcoming.swap(i, j);
+        }
+        // The end of the list is what plays first.
+        if let (Some(current), true) = (current, self.upcoming.len() > 1) {
+            if self.upcoming.last() == Some(&current) {
+                let j = (self.random() % (self.upcoming.len() as u64 - 1)) as usize;
+                let last = self.upcoming.len() - 1;
+                self.upcoming.swap(j, last);
+            }
+        }
+    }
+
+    /// xorshift64*, seeded from the random keys std gives hash maps (there's no clock on the web).
+    /// Good enough to shuffle a playlist.
+    fn random(&mut self) -> u64 {
+        if self.rng == 0 {
+            self.rng = RandomState::new().build_hasher().finish() | 1;
+        }
+        self.rng ^= self.rng >> 12;
+        self.rng ^= self.rng << 25;
+        self.rng ^= self.rng >> 27;
+        self.rng.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::playlist::{Location, PlaylistEntry};
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+
+    fn playlist(len: usize) -> Playlist {
+        let mut playlist = Playlist::default();
+        playlist.extend((0..len).map(|i| PlaylistEntry::new(Location::Path(PathBuf::from(format!("{:02}.mp3", i))))));
+        playlist
+    }
+
+    fn shuffled() -> PlayOrder {
+        let mut order = PlayOrder::default();
+        order.set_shuffle(true);
+        order
+    }
+
+    /// Moves on with the next button, the way the app does.
+    fn next(order: &mut PlayOrder, playlist: &mut Playlist) -> usize {
+        let index = order.next(playlist).unwrap();
+        playlist.set_current(Some(index));
+        index
+    }
+
+    #[test]
+    fn in_order() {
+        let mut order = PlayOrder::default();
+        let mut playlist = playlist(3);
+        assert_eq!(order.next(&playlist), Some(0));
+        playlist.set_current(Some(2));
+        assert_eq!(order.after_finished(&playlist), None);
+        assert_eq!(order.next(&playlist), Some(0));
+        order.repeat = RepeatMode::All;
+        assert_eq!(order.after_finished(&playlist), Some(0));
+        playlist.set_current(Some(0));
+        assert_eq!(order.previous(&playlist), Some(2));
+        assert_eq!(order.next(&Playlist::default()), None);
+    }
+
+    #[test]
+    fn repeat_one() {
+        for mut order in [PlayOrder::default(), shuffled()] {
+            order.repeat = RepeatMode::One;
+            let mut playlist = playlist(4);
+            playlist.set_current(Some(2));
+            assert_eq!(order.after_finished(&playlist), Some(2));
+            assert_ne!(order.next(&playlist), Some(2));
+        }
+    }
+
+    #[test]
+    fn every_track_once_per_pass() {
+        let mut order = shuffled();
+        order.repeat = RepeatMode::All;
+        let mut playlist = playlist(10);
+        let mut last = None;
+        for _ in 0..20 {
+            let pass: Vec<usize> = (0..10).map(|_| next(&mut order, &mut playlist)).collect();
+            assert_eq!(pass.iter().copied().collect::<HashSet<_>>().len(), 10, "{:?}", pass);
+            // The track that ended one pass doesn't start the next.
+            assert_ne!(Some(pass[0]), last);
+            last = pass.last().copied();
+        }
+    }
+
+    #[test]
+    fn shuffle_stops_after_a_pass_without_repeat() {
+        let mut order = shuffled();
+        let mut playlist = playlist(5);
+        playlist.set_current(Some(3));
+        // The track that's already playing counts as heard.
+        let mut played = vec![3];
+        while let Some(index) = order.after_finished(&playlist) {
+            playlist.set_current(Some(index));
+            played.push(index);
+            assert!(played.len() <= 5);
+        }
+        played.sort_unstable();
+        assert_eq!(played, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn previous_walks_back_through_history() {
+        let mut order = shuffled();
+        let mut playlist = playlist(8);
+        let played: Vec<usize> = (0..5).map(|_| next(&mut order, &mut playlist)).collect();
+
+        for expected in played[..4].iter().rev() {
+            let index = order.previous(&playlist);
+            assert_eq!(index, Some(*expected));
+            playlist.set_current(index);
+        }
+        // Nothing further back, so it stays put.
+        assert_eq!(order.previous(&playlist), Some(played[0]));
+
+        // And next goes forward the same way again.
+        for expected in &played[1..] {
+            assert_eq!(next(&mut order, &mut playlist), *expected);
+        }
+    }
+
+    #[test]
+    fn jumping_counts_as_played() {
+        let mut order = shuffled();
+        let mut playlist = playlist(4);
+        next(&mut order, &mut playlist);
+        let from = playlist.current();
+        let to = (0..4).find(|index| Some(*index) != from).unwrap();
+        order.jumped(&playlist, to);
+        playlist.set_current(Some(to));
+        let rest: Vec<usize> = (0..2).map(|_| next(&mut order, &mut playlist)).collect();
+        let heard: HashSet<usize> = rest.iter().copied().chain(from).chain(Some(to)).collect();
+        assert_eq!(heard.len(), 4);
+
+        for expected in [Some(rest[0]), Some(to), from] {
+            let index = order.previous(&playlist);
+            assert_eq!(index, expected);
+            playlist.set_current(index);
+        }
+    }
+
+    #[test]
+    fn pass_starts_over_when_the_playlist_changes() {
+        let changes: [fn(&mut Playlist); 5] = [
+            |playlist| playlist.sort_by_key(|entry| std::cmp::Reverse(entry.location.to_string())),
+            |playlist| playlist.reverse(),
+            |playlist| {
+                playlist.remove_where(|entry| entry.location == Location::Path(PathBuf::from("05.mp3")));
+                playlist.add(PlaylistEntry::new(Location::Path(PathBuf::from("new.mp3"))));
+            },
+            |playlist| playlist.extend(vec![]),
+            // Another playlist the same length
+            |playlist| {
+                let current = playlist.current();
+                *playlist = super::tests::playlist(6);
+                playlist.set_current(current);
+            },
+        ];
+        for change in changes.iter() {
+            let mut order = shuffled();
+            let mut playlist = playlist(6);
+            next(&mut order, &mut playlist);
+            next(&mut order, &mut playlist);
+
+            change(&mut playlist);
+            let current = playlist.current();
+            // What was played before is forgotten, along with its indices.
+            assert_eq!(order.previous(&playlist), current);
+            // The removed track may have been the one playing.
+            let rest: Vec<usize> = (current.iter().count()..6).map(|_| next(&mut order, &mut playlist)).collect();
+            let mut heard: Vec<usize> = rest.iter().copied().chain(current).collect();
+            heard.sort_unstable();
+            assert_eq!(heard, [0, 1, 2, 3, 4, 5], "{:?} then {:?}", current, rest);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! PLS: an INI file with a `[playlist]` section of numbered `FileN`, `TitleN` and `LengthN` keys.
+
+use std::{path::Path, time::Duration};
+
+use super::{relative_path, resolve_location, Location, PlaylistEntry, PlaylistError};
+use crate::skin_config::parse_ini;
+
+pub fn parse(text: &str, base: &Path) -> Result<Vec<PlaylistEntry>, PlaylistError> {
+    let sections = parse_ini(text);
+    let playlist = sections.get("playlist").ok_or_else(|| PlaylistError::Malformed("no [playlist] section".to_string()))?;
+
+    // Go by the numbers on the keys rather than NumberOfEntries, which is often wrong.
+    // The title and length go with the file key as it's written, so `File01` pairs with `Title01`.
+    let mut files: Vec<(u32, &str, &String)> = playlist
+        .iter()
+        .filter_map(|(key, location)| {
+            let suffix = key.strip_prefix("file")?;
+            Some((suffix.parse().ok()?, suffix, location))
+        })
+        .collect();
+    files.sort_unstable();
+    Ok(files
+        .into_iter()
+        .map(|(_, suffix, location)| {
+            let mut entry = PlaylistEntry::new(resolve_location(location, base, false));
+            entry.title = playlist.get(&format!("title{}", suffix)).filter(|title| !title.is_empty()).cloned();
+            // -1 for unknown, as with streams.
+            entry.duration = playlist
+                .get(&format!("length{}", suffix))
+                .and_then(|seconds| seconds.parse::<i64>().ok())
+                .filter(|seconds| *seconds >= 0)
+                .map(|seconds| Duration::from_secs(seconds as u64));
+            entry
+        })
+        .collect())
+}
+
+pub fn write(entries: &[PlaylistEntry], base: &Path) -> String {
+    let mut text = String::from("[playlist]\r\n");
+    for (index, entry) in entries.iter().enumerate() {
+        let number = index + 1;
+        let location = match &entry.location {
+            Location::Path(path) => relative_path(path, base),
+            Location::Url(url) => url.clone(),
+        };
+        text.push_str(&format!("File{}={}\r\n", number, location));
+        if let Some(title) = &entry.title {
+            text.push_str(&format!("Title{}={}\r\n", number, title));
+        }
+        let seconds = entry.duration.map_or(-1, |duration| duration.as_secs() as i64);
+        text.push_str(&format!("Length{}={}\r\n", number, seconds));
+    }
+    text.push_str(&format!("NumberOfEntries={}\r\nVersion=2\r\n", entries.len()));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn keys_go_by_number() {
+        let text = "[playlist]\r\n\
+                    NumberOfEntries=2\r\n\
+                    File10=ten.mp3\r\n\
+                    Title10=Ten\r\n\
+                    Length10=60\r\n\
+                    file2=two.mp3\r\n\
+                    LENGTH2=-1\r\n\
+                    File3=http://example.com/stream\r\n\
+                    Title3=\r\n\
+                    FileX=not a number.mp3\r\n\
+                    Version=2\r\n";
+        let entries = parse(text, Path::new("/music")).unwrap();
+        let locations: Vec<_> = entries.iter().map(|entry| entry.location.clone()).collect();
+        assert_eq!(locations, [
+            Location::Path(PathBuf::from("/music/two.mp3")),
+            Location::Url("http://example.com/stream".to_string()),
+            Location::Path(PathBuf::from("/music/ten.mp3")),
+        ]);
+        assert_eq!(entries[0].duration, None);
+        assert_eq!(entries[1].title, None);
+        assert_eq!(entries[2].title.as_deref(), Some("Ten"));
+        assert_eq!(entries[2].duration, Some(Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn numbers_written_differently() {
+        let text = "[playlist]\n\
+                    File01=one.mp3\n\
+                    Title01=One\n\
+                    File1=also one.mp3\n\
+                    File+2=two.mp3\n\
+                    Length+2=2\n";
+        let entries = parse(text, Path::new("/music")).unwrap();
+        let locations: Vec<_> = entries.iter().map(|entry| entry.location.clone()).collect();
+        assert_eq!(locations, [
+            Location::Path(PathBuf::from("/music/one.mp3")),
+            Location::Path(PathBuf::from("/music/also one.mp3")),
+            Location::Path(PathBuf::from("/music/two.mp3")),
+        ]);
+        assert_eq!(entries[0].title.as_deref(), Some("One"));
+        assert_eq!(entries[1].title, None);
+        assert_eq!(entries[2].duration, Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn needs_a_playlist_section() {
+        assert!(matches!(parse("File1=one.mp3\n", Path::new("")), Err(PlaylistError::Malformed(_))));
+    }
+
+    #[test]
+    fn numbered_from_one() {
+        let entries = [PlaylistEntry::new(Location::Path(PathBuf::from("/music/01.mp3")))];
+        assert_eq!(write(&entries, Path::new("/music")), "[playlist]\r\nFile1=01.mp3\r\nLength1=-1\r\nNumberOfEntries=1\r\nVersion=2\r\n");
+    }
+}
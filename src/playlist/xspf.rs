@@ -0,0 +1,193 @@
+//! XSPF: XML with a `<track>` per entry. Locations are URIs, so relative ones are percent-encoded paths.
+//! Only the handful of elements we use are read, with a scan for tags rather than a full XML parser.
+
+use std::{path::Path, time::Duration};
+
+use super::{percent_encode, relative_path, resolve_location, Location, PlaylistEntry, PlaylistError};
+
+pub fn parse(text: &str, base: &Path) -> Result<Vec<PlaylistEntry>, PlaylistError> {
+    if !text.contains("<playlist") {
+        return Err(PlaylistError::Malformed("no <playlist> element".to_string()));
+    }
+    let mut entries = vec![];
+    let mut rest = text;
+    while let Some(track) = next_element(&mut rest, "track") {
+        // Tracks without a location can't be played, and there's nothing to show for them either.
+        let location = match element_text(track, "location") {
+            Some(location) => location,
+            None => continue,
+        };
+        let mut entry = PlaylistEntry::new(resolve_location(&location, base, true));
+        entry.title = element_text(track, "title").filter(|title| !title.is_empty());
+        // Milliseconds
+        entry.duration = element_text(track, "duration").and_then(|ms| ms.trim().parse().ok()).map(Duration::from_millis);
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+pub fn write(entries: &[PlaylistEntry], base: &Path) -> String {
+    let mut text = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n  <trackList>\n");
+    for entry in entries {
+        let location = match &entry.location {
+            Location::Path(path) => {
+                let relative = relative_path(path, base);
+                let uri = percent_encode(&relative.replace('\\', "/"));
+                if Path::new(&relative).is_absolute() {
+                    // Windows paths start with the drive letter, which needs a slash in front.
+                    if uri.starts_with('/') {
+                        format!("file://{}", uri)
+                    } else {
+                        format!("file:///{}", uri)
+                    }
+                } else {
+                    uri
+                }
+            }
+            Location::Url(url) => url.clone(),
+        };
+        text.push_str("    <track>\n");
+        text.push_str(&format!("      <location>{}</location>\n", escape(&location)));
+        if let Some(title) = &entry.title {
+            text.push_str(&format!("      <title>{}</title>\n", escape(title)));
+        }
+        if let Some(duration) = entry.duration {
+            text.push_str(&format!("      <duration>{}</duration>\n", duration.as_millis()));
+        }
+        text.push_str("    </track>\n");
+    }
+    text.push_str("  </trackList>\n</playlist>\n");
+    text
+}
+
+/// Finds the next `<name ...>...</name>` in `text`, returning its contents and moving `text` past it.
+fn next_element<'a>(text: &mut &'a str, name: &str) -> Option<&'a str> {
+    let open = format!("<{}", name);
+    let close = format!("</{}>", name);
+    loop {
+        let start = text.find(&open)?;
+        let after_name = &text[start + open.len()..];
+        // Don't match <trackList> when looking for <track>.
+        if !after_name.starts_with(|c: char| c == '>' || c == '/' || c.is_whitespace()) {
+            *text = after_name;
+            continue;
+        }
+        let tag_end = after_name.find('>')?;
+        if after_name[..tag_end].ends_with('/') {
+            // <name/> is empty
+            *text = &after_name[tag_end + 1..];
+            return Some("");
+        }
+        let contents = &after_name[tag_end + 1..];
+        let end = contents.find(&close)?;
+        *text = &contents[end + close.len()..];
+        return Some(&contents[..end]);
+    }
+}
+
+fn element_text(text: &str, name: &str) -> Option<String> {
+    let mut text = text;
+    let contents = next_element(&mut text, name)?.trim();
+    match contents.strip_prefix("<![CDATA[").and_then(|cdata| cdata.strip_suffix("]]>")) {
+        Some(cdata) => Some(cdata.to_string()),
+        None => Some(unescape(contents)),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The five predefined entities and character references. Anything else is left alone.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest.find(';').map(|end| &rest[1..end]);
+        let replacement = entity.and_then(|entity| match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => {
+                let number = entity.strip_prefix('#')?;
+                let code = match number.strip_prefix('x').or_else(|| number.strip_prefix('X')) {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => number.parse().ok()?,
+                };
+                std::char::from_u32(code)
+            }
+        });
+        match (replacement, entity) {
+            (Some(c), Some(entity)) => {
+                unescaped.push(c);
+                rest = &rest[entity.len() + 2..];
+            }
+            _ => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn tracks() {
+        let text = r#"<?xml version="1.0" encoding="UTF-8"?>
+<playlist version="1" xmlns="http://xspf.org/ns/0/">
+  <title>Not a track title</title>
+  <trackList>
+    <track>
+      <location>Sigur%20R%C3%B3s/01.ogg</location>
+      <title>Rock &amp; Roll &lt;Live&gt; &#233;&#x00E9; &bogus; &amp</title>
+      <duration> 61500 </duration>
+    </track>
+    <track><title>No location, skipped</title></track>
+    <track>
+      <location>file:///elsewhere/a%20b.mp3</location>
+      <title><![CDATA[Kept <as> &amp; is]]></title>
+    </track>
+    <track>
+      <location>http://example.com/stream?a=1&amp;b=2</location>
+      <title/>
+    </track>
+  </trackList>
+</playlist>"#;
+        let entries = parse(text, Path::new("/music")).unwrap();
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].location, Location::Path(PathBuf::from("/music/Sigur Rós/01.ogg")));
+        assert_eq!(entries[0].title.as_deref(), Some("Rock & Roll <Live> éé &bogus; &amp"));
+        assert_eq!(entries[0].duration, Some(Duration::from_millis(61500)));
+        assert_eq!(entries[1].location, Location::Path(PathBuf::from("/elsewhere/a b.mp3")));
+        assert_eq!(entries[1].title.as_deref(), Some("Kept <as> &amp; is"));
+        assert_eq!(entries[1].duration, None);
+        assert_eq!(entries[2].location, Location::Url("http://example.com/stream?a=1&b=2".to_string()));
+        assert_eq!(entries[2].title, None);
+    }
+
+    #[test]
+    fn needs_a_playlist_element() {
+        assert!(matches!(parse("<trackList/>", Path::new("")), Err(PlaylistError::Malformed(_))));
+    }
+
+    #[test]
+    fn locations_are_uris() {
+        let entries = [
+            PlaylistEntry::new(Location::Path(PathBuf::from("/music/a b/01.mp3"))),
+            PlaylistEntry::new(Location::Path(PathBuf::from("/elsewhere/01.mp3"))),
+        ];
+        let written = write(&entries, Path::new("/music"));
+        assert!(written.contains("<location>a%20b/01.mp3</location>"));
+        assert!(written.contains("<location>file:///elsewhere/01.mp3</location>"));
+    }
+}
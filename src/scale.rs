@@ -0,0 +1,96 @@
+//! Drawing the skin bigger than its bitmaps, for screens where 275×116 is tiny.
+//!
+//! The scale goes into egui's pixels per point, so layout and hit-testing scale with it for free.
+//! Sampling is another matter: egui magnifies textures smoothly, so the skin's sprites are instead
+//! uploaded already blown up to the size they'll be drawn at, nearest-neighbour, and stay crisp.
+
+use eframe::{egui, epi};
+
+/// How much Ctrl+scrolling changes the scale by, each notch
+const STEP: f32 = 0.25;
+const MIN: f32 = 1.0;
+const MAX: f32 = 4.0;
+/// How far a Ctrl+scroll has to get before it counts as a notch. Trackpads send lots of little ones.
+const ZOOM_THRESHOLD: f32 = 1.15;
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+#[derive(Debug, Clone)]
+pub struct SkinScale {
+    /// Skin pixels per point. Whole numbers are Winamp's double size and beyond, anything in between
+    /// still gets nearest-neighbour sampling. Saved with the rest of the app's state.
+    pub factor: f32,
+    /// What egui's pixels per point was before it was scaled: the screen's own scale
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    native_pixels_per_point: Option<f32>,
+    /// The factor egui and the textures have been set up for, None until the first frame
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    applied: Option<f32>,
+    /// Ctrl+scrolling so far, towards the next notch
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    zoom: f32,
+}
+
+impl Default for SkinScale {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            native_pixels_per_point: None,
+            applied: None,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl SkinScale {
+    /// Ctrl+D switches between double size and normal, like Winamp. From any other scale it goes back to normal.
+    /// Ctrl+scroll zooms in and out a step at a time.
+    pub fn handle_input(&mut self, input: &egui::InputState) {
+        if input.modifiers.command && input.key_pressed(egui::Key::D) {
+            self.factor = if self.factor == 1.0 { 2.0 } else { 1.0 };
+        }
+        self.zoom *= input.zoom_delta();
+        if self.zoom > ZOOM_THRESHOLD || self.zoom < 1.0 / ZOOM_THRESHOLD {
+            let step = if self.zoom > 1.0 { STEP } else { -STEP };
+            self.factor = ((self.factor + step) / STEP).round() * STEP;
+            self.zoom = 1.0;
+        }
+        // This also tidies up whatever was restored from saved state, which could be anything.
+        self.factor = if self.factor.is_finite() { self.factor.clamp(MIN, MAX) } else { 1.0 };
+    }
+
+    /// Sets egui up for the current factor, resizing the window when it changes so what was on screen still fits.
+    /// Returns true when the skin's textures need uploading again at `texture_scale`.
+    pub fn apply(&mut self, ctx: &egui::CtxRef, frame: &mut epi::Frame<'_>) -> bool {
+        let pixels_per_point = ctx.pixels_per_point();
+        let native = *self.native_pixels_per_point.get_or_insert(pixels_per_point);
+        // The page zoom decides pixels per point in the browser, so the scale is left to that.
+        if cfg!(target_arch = "wasm32") {
+            return false;
+        }
+        let native = match self.applied {
+            Some(applied) if applied == self.factor => {
+                if (pixels_per_point - native * applied).abs() < 0.001 {
+                    return false;
+                }
+                // The screen's scale changed, from moving to another monitor say, and egui's gone back to it.
+                self.native_pixels_per_point = Some(pixels_per_point);
+                pixels_per_point
+            }
+            // The first time round the window's whatever size it opened at, so it's left alone.
+            Some(previous) => {
+                frame.set_window_size(ctx.input().screen_rect().size() * (self.factor / previous));
+                native
+            }
+            None => native,
+        };
+        ctx.set_pixels_per_point(native * self.factor);
+        self.applied = Some(self.factor);
+        true
+    }
+
+    /// Screen pixels for each pixel of the skin, which is what the textures are scaled up by.
+    pub fn texture_scale(&self) -> f32 {
+        self.native_pixels_per_point.unwrap_or(1.0) * self.applied.unwrap_or(1.0)
+    }
+}
@@ -1,12 +1,16 @@
-use std::{collections::{BTreeMap, HashMap}, fmt::Debug, fs::{self, File}, io::{self, Read}, path::PathBuf};
+use std::{collections::{BTreeMap, HashMap}, fmt::Debug, fs, io::{self, Cursor, Read, Seek}, path::{Path, PathBuf}};
 use eframe::egui::{Color32, Rect};
 use image::{DynamicImage, GenericImageView};
 use thiserror::Error;
 use zip::ZipArchive;
 use fixed_map::{Key, Map};
 
-use crate::skin_generated::get_skin_load_specs;
+use crate::skin_config::{PlaylistStyle, VisColors, WindowRegions};
+use crate::skin_generated::{get_skin_load_specs, SkinImage};
 
+/// Complete skin built into the binary. Anything a loaded skin lacks is taken from here.
+/// It's plain bevelled boxes in the standard layout, drawn by assets/make_base_skin.py; any complete skin can be dropped in its place.
+const BASE_SKIN: &[u8] = include_bytes!("../assets/base-skin.wsz");
 
 #[derive(Error, Debug)]
 pub enum SkinError {
@@ -17,20 +21,60 @@ pub enum SkinError {
     #[error("Image")]
     Image(#[from] image::ImageError),
     #[error("Expected file missing {0}")]
-    ExpectedFileMissing(String)
+    ExpectedFileMissing(String),
+    #[error("Sprite out of bounds {0}")]
+    SpriteOutOfBounds(String),
 
 }
 
+/// Something wrong with a skin that was papered over with parts of the base skin.
+#[derive(Error, Debug)]
+pub enum SkinWarning {
+    #[error("{0} is missing, using the base skin's")]
+    MissingFile(String),
+    #[error("{0} couldn't be read ({1}), using the base skin's")]
+    UnreadableFile(String, image::ImageError),
+    #[error("{1} is outside of {0}, using the base skin's")]
+    SpriteOutOfBounds(String, SkinImage),
+}
+
 pub struct WinampSkin {
-    pub images: Map<crate::skin_generated::SkinImage, LoadedImage>
+    pub images: Map<crate::skin_generated::SkinImage, LoadedImage>,
+    pub vis_colors: VisColors,
+    pub playlist_style: PlaylistStyle,
+    pub regions: WindowRegions,
+    /// Files which this skin didn't have (or were broken), so came from the base skin wholesale
+    pub borrowed_files: Vec<String>,
 }
 
+#[derive(Clone)]
 pub struct LoadedImage {
     pub pixels: Vec<Color32>,
     pub size: (usize, usize),
     pub image: crate::skin_generated::SkinImage,
 }
 
+impl LoadedImage {
+    /// A copy blown up by `scale`, nearest-neighbour, so it stays sharp drawn that much bigger.
+    pub fn scaled(&self, scale: f32) -> LoadedImage {
+        let (width, height) = self.size;
+        let size = (((width as f32 * scale).round() as usize).max(1), ((height as f32 * scale).round() as usize).max(1));
+        if size == self.size || self.pixels.is_empty() {
+            return self.clone();
+        }
+        let mut pixels = Vec::with_capacity(size.0 * size.1);
+        for y in 0..size.1 {
+            let row = (y * height / size.1) * width;
+            pixels.extend((0..size.0).map(|x| self.pixels[row + x * width / size.0]));
+        }
+        LoadedImage {
+            pixels,
+            size,
+            image: self.image,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct SliceMapping {
     region: Rect,
@@ -39,6 +83,8 @@ struct SliceMapping {
 
 pub struct FileLoadSpec<'a> {
     pub filename: &'a str,
+    /// If false, the skin can do without this file and the base skin's version is used instead.
+    pub required: bool,
     pub regions: Vec<RectLoadSpec>
 }
 
@@ -52,36 +98,188 @@ pub struct RectLoadSpec {
 
     //Texture((eframe::egui::Vec2, eframe::egui::TextureId))
 
-pub fn open_skin(path: &PathBuf) -> Result <WinampSkin, SkinError>{
-    let zipfile = fs::File::open(path)?;
-    let mut zip = zip::ZipArchive::new(zipfile)?;
+/// Where a skin's files come from.
+pub enum SkinSource<'a> {
+    /// A .wsz or .zip file
+    Archive(PathBuf),
+    /// An unpacked skin folder
+    Directory(PathBuf),
+    /// A .wsz or .zip already in memory, e.g. handed over by the web page
+    Bytes(&'a [u8]),
+}
+
+impl SkinSource<'_> {
+    /// A directory if the path is one, otherwise an archive.
+    pub fn from_path(path: PathBuf) -> SkinSource<'static> {
+        if path.is_dir() {
+            SkinSource::Directory(path)
+        } else {
+            SkinSource::Archive(path)
+        }
+    }
+
+    fn open(&self) -> Result<Box<dyn SkinFiles + '_>, SkinError> {
+        Ok(match self {
+            SkinSource::Archive(path) => Box::new(SkinArchive::open(fs::File::open(path)?)?),
+            SkinSource::Directory(path) => Box::new(SkinDirectory::open(path)?),
+            SkinSource::Bytes(bytes) => Box::new(SkinArchive::open(Cursor::new(*bytes))?),
+        })
+    }
+}
+
+/// The files of a skin, however they're stored.
+trait SkinFiles {
+    /// Read a file by its name, ignoring case and which folder it's in. None if it isn't there.
+    fn read_file(&mut self, filename: &str) -> Result<Option<Vec<u8>>, SkinError>;
+}
 
-    let mut zip_filename_case_map = HashMap::new();
-    for filename in zip.file_names() {
-        zip_filename_case_map.insert(filename.to_ascii_lowercase(), filename.to_string());
+/// Lowercased basename to the file it should be read from.
+/// Skins are often packed inside a folder, and sometimes have stray copies of files in others,
+/// so when a name turns up more than once the shallowest wins, then the first path in byte order.
+fn index_by_basename<T>(files: impl Iterator<Item = (String, T)>) -> HashMap<String, T> {
+    let mut best: HashMap<String, (usize, String, T)> = HashMap::new();
+    for (path, file) in files {
+        let path = path.replace('\\', "/");
+        let basename = path.rsplit('/').next().unwrap_or_default().to_ascii_lowercase();
+        let depth = path.matches('/').count();
+        let better = match best.get(&basename) {
+            Some((best_depth, best_path, _)) => (depth, &path) < (*best_depth, best_path),
+            None => true,
+        };
+        if better {
+            best.insert(basename, (depth, path, file));
+        }
     }
+    best.into_iter().map(|(basename, (_, _, file))| (basename, file)).collect()
+}
 
-    let load_specs = get_skin_load_specs();
+struct SkinArchive<R> {
+    zip: ZipArchive<R>,
+    /// Lowercased basename to the name in the archive
+    files: HashMap<String, String>,
+}
 
-    let mut map = Map::new();
-    for file_spec in load_specs {
-        let mut filename_recased = zip_filename_case_map.get(&file_spec.filename.to_lowercase()).ok_or(SkinError::ExpectedFileMissing(file_spec.filename.to_string()))?;
-        let mut file = zip.by_name(&filename_recased)?;
+impl<R: Read + Seek> SkinArchive<R> {
+    fn open(reader: R) -> Result<Self, SkinError> {
+        let zip = ZipArchive::new(reader)?;
+        let files = index_by_basename(zip.file_names().filter(|name| !name.ends_with('/')).map(|name| (name.to_string(), name.to_string())));
+        Ok(Self { zip, files })
+    }
+}
+
+impl<R: Read + Seek> SkinFiles for SkinArchive<R> {
+    fn read_file(&mut self, filename: &str) -> Result<Option<Vec<u8>>, SkinError> {
+        let name = match self.files.get(&filename.to_ascii_lowercase()) {
+            Some(name) => name,
+            None => return Ok(None),
+        };
+        let mut file = self.zip.by_name(name)?;
 
         let mut data: Vec<u8> = Default::default();
         file.read_to_end(&mut data)?;
-        let image_data = image::load_from_memory(&data)?;
+        Ok(Some(data))
+    }
+}
 
+struct SkinDirectory {
+    /// Lowercased basename to its path
+    files: HashMap<String, PathBuf>,
+}
 
-        let loaded_images = load_image_slices_from_data(image_data, &file_spec.regions)?;
-        for image in loaded_images {
-            map.insert(image.image, image);
+impl SkinDirectory {
+    fn open(path: &Path) -> Result<Self, SkinError> {
+        let mut found = vec![];
+        let mut to_visit = vec![(path.to_path_buf(), String::new())];
+        while let Some((dir, relative)) = to_visit.pop() {
+            for entry in fs::read_dir(&dir)? {
+                let entry = entry?;
+                let relative = format!("{}{}", relative, entry.file_name().to_string_lossy());
+                if entry.file_type()?.is_dir() {
+                    to_visit.push((entry.path(), relative + "/"));
+                } else {
+                    found.push((relative, entry.path()));
+                }
+            }
         }
+        Ok(Self { files: index_by_basename(found.into_iter()) })
     }
+}
 
-    Ok(WinampSkin {
-        images: map
-    })
+impl SkinFiles for SkinDirectory {
+    fn read_file(&mut self, filename: &str) -> Result<Option<Vec<u8>>, SkinError> {
+        match self.files.get(&filename.to_ascii_lowercase()) {
+            Some(path) => Ok(Some(fs::read(path)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Load a skin, filling in anything it lacks from the base skin.
+/// Returns the skin along with a list of everything that had to be filled in.
+pub fn open_skin(source: &SkinSource<'_>) -> Result <(WinampSkin, Vec<SkinWarning>), SkinError>{
+    let mut files = source.open()?;
+    load_skin(files.as_mut(), Some(base_skin()?))
+}
+
+/// The skin built into the binary, which has every sprite.
+pub fn base_skin() -> Result<WinampSkin, SkinError> {
+    let mut files = SkinSource::Bytes(BASE_SKIN).open()?;
+    let (skin, _) = load_skin(files.as_mut(), None)?;
+    Ok(skin)
+}
+
+/// Without a base skin to fall back on, every file is required and every sprite has to be there.
+fn load_skin(files: &mut dyn SkinFiles, base: Option<WinampSkin>) -> Result <(WinampSkin, Vec<SkinWarning>), SkinError>{
+    let strict = base.is_none();
+    let mut map = base.map(|skin| skin.images).unwrap_or_default();
+    let mut warnings = vec![];
+    let mut borrowed_files = vec![];
+    for file_spec in get_skin_load_specs() {
+        let required = file_spec.required || strict;
+        let data = match files.read_file(file_spec.filename)? {
+            Some(data) => data,
+            None if required => return Err(SkinError::ExpectedFileMissing(file_spec.filename.to_string())),
+            None => {
+                warnings.push(SkinWarning::MissingFile(file_spec.filename.to_string()));
+                borrowed_files.push(file_spec.filename.to_string());
+                continue;
+            }
+        };
+        let image_data = match image::load_from_memory(&data) {
+            Ok(image_data) => image_data,
+            Err(e) if required => return Err(e.into()),
+            Err(e) => {
+                warnings.push(SkinWarning::UnreadableFile(file_spec.filename.to_string(), e));
+                borrowed_files.push(file_spec.filename.to_string());
+                continue;
+            }
+        };
+
+        // Sprites which don't fit in an undersized bitmap keep the base skin's version.
+        for rect_spec in &file_spec.regions {
+            match load_image_slice(&image_data, rect_spec) {
+                Some(image) => { map.insert(image.image, image); },
+                None if strict => return Err(SkinError::SpriteOutOfBounds(rect_spec.image.to_string())),
+                None => warnings.push(SkinWarning::SpriteOutOfBounds(file_spec.filename.to_string(), rect_spec.image)),
+            }
+        }
+    }
+
+    // These are optional even for the base skin; without them everything is at Winamp's defaults.
+    let read_text = |files: &mut dyn SkinFiles, filename| -> Result<Option<String>, SkinError> {
+        Ok(files.read_file(filename)?.map(|data| String::from_utf8_lossy(&data).into_owned()))
+    };
+    let vis_colors = read_text(files, "VISCOLOR.TXT")?.map(|text| VisColors::parse(&text)).unwrap_or_default();
+    let playlist_style = read_text(files, "PLEDIT.TXT")?.map(|text| PlaylistStyle::parse(&text)).unwrap_or_default();
+    let regions = read_text(files, "REGION.TXT")?.map(|text| WindowRegions::parse(&text)).unwrap_or_default();
+
+    Ok((WinampSkin {
+        images: map,
+        vis_colors,
+        playlist_style,
+        regions,
+        borrowed_files,
+    }, warnings))
 }
 
 /// Generate named slice mappings for a 1d pattern - as many as there are names.
@@ -106,29 +304,26 @@ fn map_def(x0: u32, y0: u32, x1: u32, y1: u32, name: &str) -> SliceMapping {
 }
 
 
-fn load_image_slices_from_data(data: DynamicImage, rect_specs: &Vec<RectLoadSpec>) -> Result <Vec<LoadedImage>, SkinError>{
-    let mut result: Vec<LoadedImage> = vec![];
-
-    for rect_spec in rect_specs {
-        let data_cropped = data.crop_imm(rect_spec.top_left_x, rect_spec.top_left_y, rect_spec.bottom_right_x - rect_spec.top_left_x, rect_spec.bottom_right_y - rect_spec.top_left_y);
-        // double image size
-        //let data_cropped = data_cropped.resize(data_cropped.width()*2, data_cropped.height()*2, image::imageops::FilterType::Nearest);
-        let image_buffer = data_cropped.to_rgba8();
-        let size = (data_cropped.width() as usize, data_cropped.height() as usize);
-        let pixels = image_buffer.into_vec();
-        assert_eq!(size.0 * size.1 * 4, pixels.len());
-        let pixels: Vec<_> = pixels
-            .chunks_exact(4)
-            .map(|p| eframe::egui::Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
-            .collect();
-        
-        result.push(LoadedImage {
-            pixels,
-            size,
-            image: rect_spec.image,
-        });
+/// Cut one sprite out of a skin bitmap. None if the sprite doesn't fit inside the bitmap.
+fn load_image_slice(data: &DynamicImage, rect_spec: &RectLoadSpec) -> Option<LoadedImage> {
+    if rect_spec.bottom_right_x > data.width() || rect_spec.bottom_right_y > data.height() {
+        return None;
     }
-    Ok(result)
+    let data_cropped = data.crop_imm(rect_spec.top_left_x, rect_spec.top_left_y, rect_spec.bottom_right_x - rect_spec.top_left_x, rect_spec.bottom_right_y - rect_spec.top_left_y);
+    let image_buffer = data_cropped.to_rgba8();
+    let size = (data_cropped.width() as usize, data_cropped.height() as usize);
+    let pixels = image_buffer.into_vec();
+    assert_eq!(size.0 * size.1 * 4, pixels.len());
+    let pixels: Vec<_> = pixels
+        .chunks_exact(4)
+        .map(|p| eframe::egui::Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+        .collect();
+
+    Some(LoadedImage {
+        pixels,
+        size,
+        image: rect_spec.image,
+    })
 }
 
 // fn read_file(zip: &mut ZipArchive<File>, filename: &str) -> Result<Vec<u8>, SkinError> {
@@ -154,3 +349,106 @@ fn load_image_slices_from_data(data: DynamicImage, rect_specs: &Vec<RectLoadSpec
 
 //     Ok(LoadedImage::Pixels(pixels, size))
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::skin_generated::SkinImage::*;
+
+    /// A skin's files, by name.
+    struct MemorySkin(HashMap<String, Vec<u8>>);
+
+    impl SkinFiles for MemorySkin {
+        fn read_file(&mut self, filename: &str) -> Result<Option<Vec<u8>>, SkinError> {
+            Ok(self.0.get(filename).cloned())
+        }
+    }
+
+    /// The base skin's files, to be broken in various ways.
+    fn base_files() -> MemorySkin {
+        let mut archive = SkinArchive::open(Cursor::new(BASE_SKIN)).unwrap();
+        let names: Vec<String> = archive.zip.file_names().map(|name| name.to_string()).collect();
+        MemorySkin(names.into_iter().map(|name| {
+            let data = archive.read_file(&name).unwrap().unwrap();
+            (name, data)
+        }).collect())
+    }
+
+    fn solid_bmp(width: u32, height: u32, color: [u8; 3]) -> Vec<u8> {
+        let mut data = vec![];
+        DynamicImage::ImageRgb8(image::RgbImage::from_pixel(width, height, image::Rgb(color))).write_to(&mut data, image::ImageOutputFormat::Bmp).unwrap();
+        data
+    }
+
+    #[test]
+    fn shallowest_then_first_path_wins() {
+        let files = [
+            "Skin/extra/Main.bmp",
+            "Skin/MAIN.BMP",
+            "Skin/main.bmp",
+            "Other/main.BMP",
+            "deeper/still/Text.bmp",
+            "Backup\\TEXT.BMP",
+            "nested/cbuttons.bmp",
+            "CBUTTONS.BMP",
+        ];
+        let index = index_by_basename(files.iter().map(|path| (path.to_string(), *path)));
+        assert_eq!(index.len(), 3);
+        // Same depth, so byte order decides.
+        assert_eq!(index["main.bmp"], "Other/main.BMP");
+        // Backslashes count as folders too.
+        assert_eq!(index["text.bmp"], "Backup\\TEXT.BMP");
+        assert_eq!(index["cbuttons.bmp"], "CBUTTONS.BMP");
+
+        // Order of discovery doesn't matter.
+        let index = index_by_basename(files.iter().rev().map(|path| (path.to_string(), *path)));
+        assert_eq!(index["main.bmp"], "Other/main.BMP");
+        assert_eq!(index["text.bmp"], "Backup\\TEXT.BMP");
+        assert_eq!(index["cbuttons.bmp"], "CBUTTONS.BMP");
+
+        // Which puts upper case first.
+        let index = index_by_basename(["b/MAIN.BMP", "a/main.bmp", "A/Main.bmp"].iter().map(|path| (path.to_string(), *path)));
+        assert_eq!(index["main.bmp"], "A/Main.bmp");
+    }
+
+    #[test]
+    fn missing_pieces_come_from_the_base_skin() {
+        let base = base_skin().unwrap();
+        let mut files = base_files();
+        // Only the top row of buttons, and no digits at all.
+        files.0.insert("CBUTTONS.BMP".to_string(), solid_bmp(136, 18, [255, 0, 0]));
+        files.0.remove("NUMBERS.BMP");
+        files.0.insert("NUMS_EX.BMP".to_string(), b"not a bitmap".to_vec());
+
+        let (skin, warnings) = load_skin(&mut files, Some(base_skin().unwrap())).unwrap();
+        let red = Color32::from_rgb(255, 0, 0);
+        assert!(skin.images.get(ButtonPlay).unwrap().pixels.iter().all(|p| *p == red));
+        assert_eq!(skin.images.get(ButtonPlayPressed).unwrap().pixels, base.images.get(ButtonPlayPressed).unwrap().pixels);
+        assert_eq!(skin.images.get(NumbersDigit0).unwrap().pixels, base.images.get(NumbersDigit0).unwrap().pixels);
+        assert!(skin.images.get(NumbersExDigit0).is_some());
+        for spec in get_skin_load_specs() {
+            for rect in spec.regions {
+                assert!(skin.images.get(rect.image).is_some(), "{:?} missing", rect.image);
+            }
+        }
+
+        let warnings: Vec<String> = warnings.iter().map(|warning| warning.to_string()).collect();
+        assert!(warnings.contains(&"NUMBERS.BMP is missing, using the base skin's".to_string()), "{:?}", warnings);
+        assert!(warnings.iter().any(|warning| warning.starts_with("NUMS_EX.BMP couldn't be read")), "{:?}", warnings);
+        assert!(warnings.contains(&format!("{} is outside of CBUTTONS.BMP, using the base skin's", ButtonPlayPressed)), "{:?}", warnings);
+        assert!(!warnings.contains(&format!("{} is outside of CBUTTONS.BMP, using the base skin's", ButtonPlay)), "{:?}", warnings);
+        assert_eq!(skin.borrowed_files, vec!["NUMBERS.BMP", "NUMS_EX.BMP"]);
+    }
+
+    #[test]
+    fn required_files_have_to_be_there() {
+        let mut files = base_files();
+        files.0.remove("MAIN.BMP");
+        assert!(matches!(load_skin(&mut files, Some(base_skin().unwrap())), Err(SkinError::ExpectedFileMissing(name)) if name == "MAIN.BMP"));
+
+        // The base skin itself can't fall back on anything.
+        let mut files = base_files();
+        files.0.remove("NUMBERS.BMP");
+        assert!(matches!(load_skin(&mut files, None), Err(SkinError::ExpectedFileMissing(name)) if name == "NUMBERS.BMP"));
+    }
+}
@@ -0,0 +1,195 @@
+//! Packing the skin's sprites into a few big textures, rather than uploading each of the hundred-odd on its own.
+//!
+//! Sprites go in rows, tallest first, each with a one pixel border copied from its own edge pixels,
+//! so sampling right at the edge of one never picks up its neighbour.
+
+use eframe::egui::{Color32, Rect, TextureId, Vec2};
+use eframe::epi;
+use fixed_map::Map;
+
+use crate::app::LoadedTexture;
+use crate::skin::{LoadedImage, WinampSkin};
+use crate::skin_generated::SkinImage;
+
+/// Narrowest a page is made. Wider if a sprite needs it, which it can once the skin is scaled up.
+const MIN_PAGE_WIDTH: usize = 1024;
+const PADDING: usize = 1;
+
+/// The textures a skin's sprites were uploaded to. They stay allocated until `free` is called.
+pub struct SkinAtlas {
+    pages: Vec<TextureId>,
+}
+
+/// A page laid out but not uploaded yet.
+struct Page {
+    size: (usize, usize),
+    /// Top left of each sprite, not counting its border
+    placed: Vec<(usize, usize, usize)>,
+}
+
+impl SkinAtlas {
+    /// Uploads all of `skin`'s sprites scaled up by `scale`, and says where each one went.
+    /// The sprites keep their unscaled size in points.
+    pub fn upload(skin: &WinampSkin, scale: f32, allocator: &mut dyn epi::TextureAllocator) -> (Self, Map<SkinImage, LoadedTexture>) {
+        let sprites: Vec<(SkinImage, Vec2, LoadedImage)> = skin.images.iter()
+            .map(|(name, image)| (name, Vec2::new(image.size.0 as f32, image.size.1 as f32), image.scaled(scale)))
+            .collect();
+        let layout = lay_out(&sprites.iter().map(|(_, _, image)| image.size).collect::<Vec<_>>());
+
+        let mut pages = Vec::with_capacity(layout.len());
+        let mut textures = Map::new();
+        for page in &layout {
+            let mut pixels = vec![Color32::TRANSPARENT; page.size.0 * page.size.1];
+            for &(index, x, y) in &page.placed {
+                blit(&mut pixels, page.size.0, &sprites[index].2, x, y);
+            }
+            let texture = allocator.alloc_srgba_premultiplied(page.size, &pixels);
+            pages.push(texture);
+
+            let page_size = Vec2::new(page.size.0 as f32, page.size.1 as f32);
+            for &(index, x, y) in &page.placed {
+                let (name, size, image) = &sprites[index];
+                let min = Vec2::new(x as f32, y as f32);
+                let max = min + Vec2::new(image.size.0 as f32, image.size.1 as f32);
+                let uv = Rect::from_min_max((min / page_size).to_pos2(), (max / page_size).to_pos2());
+                textures.insert(*name, LoadedTexture { size: *size, texture, uv });
+            }
+        }
+        (Self { pages }, textures)
+    }
+
+    pub fn free(self, allocator: &mut dyn epi::TextureAllocator) {
+        for page in self.pages {
+            allocator.free(page);
+        }
+    }
+}
+
+/// Shelf packing: sorted tallest first, sprites go left to right until a row's full, then on to
+/// the next row, and a new page once the page is full. Pages are as tall as they need to be.
+fn lay_out(sizes: &[(usize, usize)]) -> Vec<Page> {
+    let widest = sizes.iter().map(|size| size.0 + 2 * PADDING).max().unwrap_or(0);
+    let tallest = sizes.iter().map(|size| size.1 + 2 * PADDING).max().unwrap_or(0);
+    let width = widest.max(MIN_PAGE_WIDTH);
+    let max_height = tallest.max(width);
+
+    let mut order: Vec<usize> = (0..sizes.len()).collect();
+    order.sort_by_key(|index| std::cmp::Reverse(sizes[*index].1));
+
+    let mut pages = Vec::new();
+    let mut page = Page { size: (width, 0), placed: Vec::new() };
+    let (mut x, mut y, mut row_height) = (0, 0, 0);
+    for index in order {
+        let (w, h) = (sizes[index].0 + 2 * PADDING, sizes[index].1 + 2 * PADDING);
+        if x + w > width {
+            x = 0;
+            y += row_height;
+            row_height = 0;
+        }
+        if y + h > max_height {
+            page.size.1 = y;
+            pages.push(std::mem::replace(&mut page, Page { size: (width, 0), placed: Vec::new() }));
+            x = 0;
+            y = 0;
+            row_height = 0;
+        }
+        page.placed.push((index, x + PADDING, y + PADDING));
+        x += w;
+        row_height = row_height.max(h);
+    }
+    if !page.placed.is_empty() {
+        page.size.1 = y + row_height;
+        pages.push(page);
+    }
+    pages
+}
+
+/// Copies `image` into the page with its top left at (`x`, `y`), and its edge pixels out into the border around it.
+fn blit(pixels: &mut [Color32], page_width: usize, image: &LoadedImage, x: usize, y: usize) {
+    let (width, height) = image.size;
+    if width == 0 || height == 0 {
+        return;
+    }
+    for row in 0..height + 2 * PADDING {
+        let source_row = row.saturating_sub(PADDING).min(height - 1);
+        let start = (y + row - PADDING) * page_width + x - PADDING;
+        for column in 0..width + 2 * PADDING {
+            let source_column = column.saturating_sub(PADDING).min(width - 1);
+            pixels[start + column] = image.pixels[source_row * width + source_column];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each sprite's rectangle on its page, border included, as (page, left, top, right, bottom).
+    fn bordered(sizes: &[(usize, usize)], pages: &[Page]) -> Vec<(usize, usize, usize, usize, usize)> {
+        let mut rects = Vec::new();
+        for (page_index, page) in pages.iter().enumerate() {
+            for &(index, x, y) in &page.placed {
+                let (width, height) = sizes[index];
+                rects.push((page_index, x - PADDING, y - PADDING, x + width + PADDING, y + height + PADDING));
+            }
+        }
+        rects
+    }
+
+    fn check(sizes: &[(usize, usize)]) -> Vec<Page> {
+        let pages = lay_out(sizes);
+        let rects = bordered(sizes, &pages);
+        assert_eq!(rects.len(), sizes.len());
+        let mut indices: Vec<usize> = pages.iter().flat_map(|page| page.placed.iter().map(|placed| placed.0)).collect();
+        indices.sort_unstable();
+        assert!(indices.iter().copied().eq(0..sizes.len()));
+        for &(page, _, _, right, bottom) in &rects {
+            assert!(right <= pages[page].size.0 && bottom <= pages[page].size.1);
+        }
+        for (i, a) in rects.iter().enumerate() {
+            for b in &rects[i + 1..] {
+                let overlap = a.0 == b.0 && a.1 < b.3 && b.1 < a.3 && a.2 < b.4 && b.2 < a.4;
+                assert!(!overlap, "{:?} overlaps {:?}", a, b);
+            }
+        }
+        pages
+    }
+
+    #[test]
+    fn sprites_dont_overlap() {
+        let sizes: Vec<(usize, usize)> = (0..200).map(|i| (1 + i * 37 % 275, 1 + i * 53 % 116)).collect();
+        let pages = check(&sizes);
+        assert!(pages.len() > 1);
+        assert!(pages.iter().all(|page| page.size.0 == MIN_PAGE_WIDTH && page.size.1 <= MIN_PAGE_WIDTH));
+        assert!(check(&[]).is_empty());
+    }
+
+    #[test]
+    fn wide_sprites_widen_the_page() {
+        let pages = check(&[(10, 10), (MIN_PAGE_WIDTH + 100, 20), (30, 5)]);
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].size.0, MIN_PAGE_WIDTH + 100 + 2 * PADDING);
+        // A sprite exactly as wide as a page still needs room for its border.
+        let pages = check(&[(MIN_PAGE_WIDTH, 4)]);
+        assert_eq!(pages[0].size, (MIN_PAGE_WIDTH + 2 * PADDING, 4 + 2 * PADDING));
+    }
+
+    #[test]
+    fn blit_copies_the_edges_into_the_border() {
+        let (a, b, c, d) = (Color32::RED, Color32::GREEN, Color32::BLUE, Color32::WHITE);
+        let image = LoadedImage { pixels: vec![a, b, c, d], size: (2, 2), image: SkinImage::ButtonPlay };
+        let page_width = 5;
+        let mut pixels = vec![Color32::TRANSPARENT; page_width * 5];
+        blit(&mut pixels, page_width, &image, 1, 1);
+        let t = Color32::TRANSPARENT;
+        #[rustfmt::skip]
+        let expected = vec![
+            a, a, b, b, t,
+            a, a, b, b, t,
+            c, c, d, d, t,
+            c, c, d, d, t,
+            t, t, t, t, t,
+        ];
+        assert_eq!(pixels, expected);
+    }
+}
@@ -0,0 +1,311 @@
+//! The text files that come with a skin: VISCOLOR.TXT, PLEDIT.TXT and REGION.TXT.
+//! Skins in the wild are full of typos, odd separators and missing entries, so like Winamp
+//! these parsers never fail - anything they can't make sense of is left at its default.
+
+use std::collections::HashMap;
+
+use eframe::egui::{Color32, Pos2};
+
+/// Number of colours in VISCOLOR.TXT
+pub const VIS_COLOR_COUNT: usize = 24;
+
+/// Colours for the visualizer, from VISCOLOR.TXT.
+/// 0 is the background, 1 the dots, 2-17 the analyzer from top to bottom,
+/// 18-22 the oscilloscope from brightest to dimmest and 23 the analyzer peaks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VisColors {
+    pub colors: [Color32; VIS_COLOR_COUNT],
+}
+
+impl Default for VisColors {
+    fn default() -> Self {
+        let rgb = [
+            (0, 0, 0), (24, 33, 41), (239, 49, 16), (206, 41, 16), (214, 90, 0), (214, 102, 0),
+            (214, 115, 0), (198, 123, 8), (222, 165, 24), (214, 181, 33), (189, 222, 41), (148, 222, 33),
+            (41, 206, 16), (50, 190, 16), (57, 181, 16), (49, 156, 8), (41, 148, 0), (24, 132, 8),
+            (255, 255, 255), (214, 214, 222), (181, 189, 198), (160, 170, 175), (148, 156, 165), (150, 150, 150),
+        ];
+        let mut colors = [Color32::BLACK; VIS_COLOR_COUNT];
+        for (color, (r, g, b)) in colors.iter_mut().zip(rgb.iter()) {
+            *color = Color32::from_rgb(*r, *g, *b);
+        }
+        Self { colors }
+    }
+}
+
+impl VisColors {
+    /// Each line that starts with three numbers is the next colour; anything after them
+    /// (usually a `// comment`) and any line that doesn't are ignored.
+    pub fn parse(text: &str) -> Self {
+        let mut vis_colors = Self::default();
+        let parsed = text.lines().filter_map(|line| {
+            let numbers = leading_numbers(line, 3);
+            match numbers[..] {
+                [r, g, b] => Some(Color32::from_rgb(clamp_u8(r), clamp_u8(g), clamp_u8(b))),
+                _ => None,
+            }
+        });
+        for (color, parsed) in vis_colors.colors.iter_mut().zip(parsed) {
+            *color = parsed;
+        }
+        vis_colors
+    }
+}
+
+/// Playlist editor colours and font, from the [Text] section of PLEDIT.TXT.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaylistStyle {
+    /// Text of entries other than the current one
+    pub normal: Color32,
+    /// Text of the entry that's playing
+    pub current: Color32,
+    pub normal_background: Color32,
+    pub selected_background: Color32,
+    /// Font family name. We can't load system fonts by name, but skins are free to ask.
+    pub font: String,
+}
+
+impl Default for PlaylistStyle {
+    fn default() -> Self {
+        Self {
+            normal: Color32::from_rgb(0x00, 0xff, 0x00),
+            current: Color32::from_rgb(0xff, 0xff, 0xff),
+            normal_background: Color32::from_rgb(0x00, 0x00, 0x00),
+            selected_background: Color32::from_rgb(0x00, 0x00, 0xff),
+            font: "Arial".to_string(),
+        }
+    }
+}
+
+impl PlaylistStyle {
+    pub fn parse(text: &str) -> Self {
+        let mut style = Self::default();
+        let ini = parse_ini(text);
+        let section = match ini.get("text") {
+            Some(section) => section,
+            None => return style,
+        };
+        let colors = [
+            ("normal", &mut style.normal),
+            ("current", &mut style.current),
+            ("normalbg", &mut style.normal_background),
+            ("selectedbg", &mut style.selected_background),
+        ];
+        for (key, color) in colors {
+            if let Some(parsed) = section.get(key).and_then(|value| parse_hex_color(value)) {
+                *color = parsed;
+            }
+        }
+        if let Some(font) = section.get("font").filter(|font| !font.is_empty()) {
+            style.font = font.clone();
+        }
+        style
+    }
+}
+
+/// Window shapes from REGION.TXT, as polygons in unscaled skin pixels.
+/// An empty list means the window is a plain rectangle.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowRegions {
+    pub main: Vec<Vec<Pos2>>,
+    pub main_shade: Vec<Vec<Pos2>>,
+    pub equalizer: Vec<Vec<Pos2>>,
+    pub equalizer_shade: Vec<Vec<Pos2>>,
+}
+
+impl WindowRegions {
+    pub fn parse(text: &str) -> Self {
+        let ini = parse_ini(text);
+        let polygons = |section: &str| ini.get(section).map(parse_polygons).unwrap_or_default();
+        Self {
+            main: polygons("normal"),
+            main_shade: polygons("windowshade"),
+            equalizer: polygons("equalizer"),
+            equalizer_shade: polygons("equalizerws"),
+        }
+    }
+}
+
+/// `NumPoints` is how many points each polygon has and `PointList` all of their x,y pairs one after another.
+/// If the list runs out early we keep the polygons that were complete.
+fn parse_polygons(section: &HashMap<String, String>) -> Vec<Vec<Pos2>> {
+    let counts = section.get("numpoints").map(|value| all_numbers(value)).unwrap_or_default();
+    let coordinates = section.get("pointlist").map(|value| all_numbers(value)).unwrap_or_default();
+    let mut points = coordinates.chunks_exact(2).map(|xy| Pos2::new(xy[0] as f32, xy[1] as f32));
+
+    let mut polygons = vec![];
+    for count in counts {
+        let polygon: Vec<Pos2> = points.by_ref().take(count.max(0) as usize).collect();
+        if polygon.len() < count as usize {
+            break;
+        }
+        // Fewer than three points doesn't enclose anything.
+        if polygon.len() >= 3 {
+            polygons.push(polygon);
+        }
+    }
+    polygons
+}
+
+/// Sections and keys lowercased, values trimmed. Where a key appears twice the first one counts,
+/// as with Windows' GetPrivateProfileString. Keys before any section header are dropped.
+pub(crate) fn parse_ini(text: &str) -> HashMap<String, HashMap<String, String>> {
+    let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut current = None;
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with("//") {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.split(']').next().unwrap_or_default().trim().to_ascii_lowercase();
+            sections.entry(name.clone()).or_default();
+            current = Some(name);
+        } else if let (Some(section), Some((key, value))) = (&current, line.split_once('=')) {
+            let entries = sections.entry(section.clone()).or_default();
+            entries.entry(key.trim().to_ascii_lowercase()).or_insert_with(|| value.trim().to_string());
+        }
+    }
+    sections
+}
+
+/// `#RRGGBB`, with or without the `#`. Anything after the sixth digit is ignored.
+fn parse_hex_color(value: &str) -> Option<Color32> {
+    let hex: String = value.trim().trim_start_matches('#').chars().take(6).collect();
+    if hex.len() < 6 {
+        return None;
+    }
+    let rgb = u32::from_str_radix(&hex, 16).ok()?;
+    Some(Color32::from_rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+/// Up to `count` integers from the start of the line, separated by anything that isn't a digit or a minus.
+/// Stops at the first thing that's neither a number nor a separator, so comments don't get picked up.
+fn leading_numbers(line: &str, count: usize) -> Vec<i64> {
+    let mut numbers = vec![];
+    let mut rest = line.trim_start();
+    while numbers.len() < count {
+        let end = rest.char_indices().find(|(i, c)| !(c.is_ascii_digit() || (*i == 0 && *c == '-'))).map(|(i, _)| i).unwrap_or(rest.len());
+        match rest[..end].parse() {
+            Ok(number) => numbers.push(number),
+            Err(_) => break,
+        }
+        rest = rest[end..].trim_start_matches(|c: char| c == ',' || c.is_whitespace());
+    }
+    numbers
+}
+
+/// Every integer in the value, whatever separates them.
+fn all_numbers(value: &str) -> Vec<i64> {
+    value
+        .split(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .filter_map(|number| number.parse().ok())
+        .collect()
+}
+
+fn clamp_u8(value: i64) -> u8 {
+    value.clamp(0, 255) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vis_colors_with_comments_and_short_lines() {
+        let text = "0,0,0, // color 0 = black\n\
+                    24, 33, 41 // color 1 = grey for dots\n\
+                    \n\
+                    // a line of its own\n\
+                    1,2 // too short, skipped\n\
+                    300,-5,128\n";
+        let colors = VisColors::parse(text).colors;
+        assert_eq!(colors[0], Color32::from_rgb(0, 0, 0));
+        assert_eq!(colors[1], Color32::from_rgb(24, 33, 41));
+        assert_eq!(colors[2], Color32::from_rgb(255, 0, 128));
+        // Everything the file didn't get to keeps the default.
+        assert_eq!(colors[3..], VisColors::default().colors[3..]);
+    }
+
+    #[test]
+    fn playlist_style() {
+        let text = "[Text]\r\n\
+                    Normal=#00FF00 ; comment after the value\r\n\
+                    Current=FFFFFF\r\n\
+                    NormalBG=#0a0B0c\r\n\
+                    SelectedBG=#12\r\n\
+                    Font=Tahoma\r\n\
+                    normal=#FF0000\r\n";
+        let style = PlaylistStyle::parse(text);
+        assert_eq!(style.normal, Color32::from_rgb(0x00, 0xff, 0x00));
+        assert_eq!(style.current, Color32::from_rgb(0xff, 0xff, 0xff));
+        assert_eq!(style.normal_background, Color32::from_rgb(0x0a, 0x0b, 0x0c));
+        assert_eq!(style.selected_background, PlaylistStyle::default().selected_background);
+        assert_eq!(style.font, "Tahoma");
+    }
+
+    #[test]
+    fn playlist_style_without_text_section() {
+        assert_eq!(PlaylistStyle::parse("Normal=#123456\n[Other]\nNormal=#123456"), PlaylistStyle::default());
+    }
+
+    #[test]
+    fn window_regions() {
+        let text = "[Normal]\n\
+                    NumPoints=4, 2 ,4\n\
+                    PointList=0,0, 275,0, 275,116, 0,116,  1,1, 2,2,  0,0, 10,0, 10,10, 0,10\n\
+                    [WindowShade]\n\
+                    NumPoints=4\n\
+                    PointList=0 0 275 0 275 14\n\
+                    [Equalizer]\n\
+                    NumPoints=3,4\n\
+                    PointList=0,0, 5,0, 5,5, 0,0, 1,0\n";
+        let regions = WindowRegions::parse(text);
+        let points = |xy: &[(f32, f32)]| xy.iter().map(|(x, y)| Pos2::new(*x, *y)).collect::<Vec<_>>();
+        // The two point polygon uses up its points but isn't kept.
+        assert_eq!(regions.main, vec![
+            points(&[(0.0, 0.0), (275.0, 0.0), (275.0, 116.0), (0.0, 116.0)]),
+            points(&[(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)]),
+        ]);
+        // Not enough points for the polygon it says there is.
+        assert!(regions.main_shade.is_empty());
+        assert_eq!(regions.equalizer, vec![points(&[(0.0, 0.0), (5.0, 0.0), (5.0, 5.0)])]);
+        assert!(regions.equalizer_shade.is_empty());
+    }
+
+    #[test]
+    fn ini() {
+        let text = "orphan=1\n\
+                    ; comment\n\
+                    [ Text ]\n\
+                    \tKey = Value with spaces \n\
+                    KEY=second\n\
+                    no equals sign\n\
+                    // another comment\n\
+                    [Empty]\n";
+        let ini = parse_ini(text);
+        assert_eq!(ini.len(), 2);
+        assert_eq!(ini["text"].len(), 1);
+        assert_eq!(ini["text"]["key"], "Value with spaces");
+        assert!(ini["empty"].is_empty());
+    }
+
+    #[test]
+    fn hex_colors() {
+        assert_eq!(parse_hex_color("#FF8000"), Some(Color32::from_rgb(0xff, 0x80, 0x00)));
+        assert_eq!(parse_hex_color(" ff8000 "), Some(Color32::from_rgb(0xff, 0x80, 0x00)));
+        assert_eq!(parse_hex_color("#FF800012"), Some(Color32::from_rgb(0xff, 0x80, 0x00)));
+        assert_eq!(parse_hex_color("#FF80"), None);
+        assert_eq!(parse_hex_color("#GG8000"), None);
+        assert_eq!(parse_hex_color(""), None);
+    }
+
+    #[test]
+    fn numbers_at_the_start_of_a_line() {
+        assert_eq!(leading_numbers("  1, 2 ,3,4", 3), [1, 2, 3]);
+        assert_eq!(leading_numbers("-1 20\t300", 3), [-1, 20, 300]);
+        assert_eq!(leading_numbers("1,2 // 3", 3), [1, 2]);
+        assert_eq!(leading_numbers("1-2", 3), [1, -2]);
+        assert!(leading_numbers("// 1,2,3", 3).is_empty());
+    }
+}
@@ -31,6 +31,102 @@ pub fn iter_VolumeSliderBar() -> std::vec::IntoIter<SkinImage> {
     ].into_iter()
 }
 
+pub fn iter_BalanceSliderBar() -> std::vec::IntoIter<SkinImage> {
+    vec![
+    SkinImage::BalanceSliderBar0,
+    SkinImage::BalanceSliderBar1,
+    SkinImage::BalanceSliderBar2,
+    SkinImage::BalanceSliderBar3,
+    SkinImage::BalanceSliderBar4,
+    SkinImage::BalanceSliderBar5,
+    SkinImage::BalanceSliderBar6,
+    SkinImage::BalanceSliderBar7,
+    SkinImage::BalanceSliderBar8,
+    SkinImage::BalanceSliderBar9,
+    SkinImage::BalanceSliderBar10,
+    SkinImage::BalanceSliderBar11,
+    SkinImage::BalanceSliderBar12,
+    SkinImage::BalanceSliderBar13,
+    SkinImage::BalanceSliderBar14,
+    SkinImage::BalanceSliderBar15,
+    SkinImage::BalanceSliderBar16,
+    SkinImage::BalanceSliderBar17,
+    SkinImage::BalanceSliderBar18,
+    SkinImage::BalanceSliderBar19,
+    SkinImage::BalanceSliderBar20,
+    SkinImage::BalanceSliderBar21,
+    SkinImage::BalanceSliderBar22,
+    SkinImage::BalanceSliderBar23,
+    SkinImage::BalanceSliderBar24,
+    SkinImage::BalanceSliderBar25,
+    SkinImage::BalanceSliderBar26,
+    SkinImage::BalanceSliderBar27,
+    ].into_iter()
+}
+
+pub fn iter_EqSliderBar() -> std::vec::IntoIter<SkinImage> {
+    vec![
+    SkinImage::EqSliderBar0,
+    SkinImage::EqSliderBar1,
+    SkinImage::EqSliderBar2,
+    SkinImage::EqSliderBar3,
+    SkinImage::EqSliderBar4,
+    SkinImage::EqSliderBar5,
+    SkinImage::EqSliderBar6,
+    SkinImage::EqSliderBar7,
+    SkinImage::EqSliderBar8,
+    SkinImage::EqSliderBar9,
+    SkinImage::EqSliderBar10,
+    SkinImage::EqSliderBar11,
+    SkinImage::EqSliderBar12,
+    SkinImage::EqSliderBar13,
+    SkinImage::EqSliderBar14,
+    SkinImage::EqSliderBar15,
+    SkinImage::EqSliderBar16,
+    SkinImage::EqSliderBar17,
+    SkinImage::EqSliderBar18,
+    SkinImage::EqSliderBar19,
+    SkinImage::EqSliderBar20,
+    SkinImage::EqSliderBar21,
+    SkinImage::EqSliderBar22,
+    SkinImage::EqSliderBar23,
+    SkinImage::EqSliderBar24,
+    SkinImage::EqSliderBar25,
+    SkinImage::EqSliderBar26,
+    SkinImage::EqSliderBar27,
+    ].into_iter()
+}
+
+pub fn iter_NumbersDigit() -> std::vec::IntoIter<SkinImage> {
+    vec![
+    SkinImage::NumbersDigit0,
+    SkinImage::NumbersDigit1,
+    SkinImage::NumbersDigit2,
+    SkinImage::NumbersDigit3,
+    SkinImage::NumbersDigit4,
+    SkinImage::NumbersDigit5,
+    SkinImage::NumbersDigit6,
+    SkinImage::NumbersDigit7,
+    SkinImage::NumbersDigit8,
+    SkinImage::NumbersDigit9,
+    ].into_iter()
+}
+
+pub fn iter_NumbersExDigit() -> std::vec::IntoIter<SkinImage> {
+    vec![
+    SkinImage::NumbersExDigit0,
+    SkinImage::NumbersExDigit1,
+    SkinImage::NumbersExDigit2,
+    SkinImage::NumbersExDigit3,
+    SkinImage::NumbersExDigit4,
+    SkinImage::NumbersExDigit5,
+    SkinImage::NumbersExDigit6,
+    SkinImage::NumbersExDigit7,
+    SkinImage::NumbersExDigit8,
+    SkinImage::NumbersExDigit9,
+    ].into_iter()
+}
+
 pub fn char_SmallFont(c: char) -> Option<SkinImage> {
     match c {
     'A' => Some(SkinImage::SmallFontA),
@@ -92,6 +188,11 @@ pub fn char_SmallFont(c: char) -> Option<SkinImage> {
     '=' => Some(SkinImage::SmallFontEquals),
     '$' => Some(SkinImage::SmallFontDollar),
     '#' => Some(SkinImage::SmallFontHash),
+    'Å' => Some(SkinImage::SmallFontARing),
+    'Ö' => Some(SkinImage::SmallFontODiaeresis),
+    'Ä' => Some(SkinImage::SmallFontADiaeresis),
+    '?' => Some(SkinImage::SmallFontQuestion),
+    '*' => Some(SkinImage::SmallFontAsterisk),
     _ => None
     }
 }
@@ -200,12 +301,263 @@ pub enum SkinImage {
     SmallFontEquals,
     SmallFontDollar,
     SmallFontHash,
+    SmallFontARing,
+    SmallFontODiaeresis,
+    SmallFontADiaeresis,
+    SmallFontQuestion,
+    SmallFontAsterisk,
+    SmallFontSpace,
+    TitleBarSelected,
+    TitleBar,
+    TitleBarEasterEggSelected,
+    TitleBarEasterEgg,
+    TitleBarOptions,
+    TitleBarOptionsPressed,
+    TitleBarMinimize,
+    TitleBarMinimizePressed,
+    TitleBarShade,
+    TitleBarShadePressed,
+    TitleBarUnshade,
+    TitleBarUnshadePressed,
+    TitleBarClose,
+    TitleBarClosePressed,
+    ClutterBar,
+    ClutterBarDisabled,
+    MainShadeSelected,
+    MainShade,
+    MainShadePositionBackground,
+    MainShadePositionThumbLeft,
+    MainShadePositionThumb,
+    MainShadePositionThumbRight,
+    PositionBar,
+    PositionBarThumb,
+    PositionBarThumbPressed,
+    BalanceSliderBar0,
+    BalanceSliderBar1,
+    BalanceSliderBar2,
+    BalanceSliderBar3,
+    BalanceSliderBar4,
+    BalanceSliderBar5,
+    BalanceSliderBar6,
+    BalanceSliderBar7,
+    BalanceSliderBar8,
+    BalanceSliderBar9,
+    BalanceSliderBar10,
+    BalanceSliderBar11,
+    BalanceSliderBar12,
+    BalanceSliderBar13,
+    BalanceSliderBar14,
+    BalanceSliderBar15,
+    BalanceSliderBar16,
+    BalanceSliderBar17,
+    BalanceSliderBar18,
+    BalanceSliderBar19,
+    BalanceSliderBar20,
+    BalanceSliderBar21,
+    BalanceSliderBar22,
+    BalanceSliderBar23,
+    BalanceSliderBar24,
+    BalanceSliderBar25,
+    BalanceSliderBar26,
+    BalanceSliderBar27,
+    BalanceSliderButton,
+    BalanceSliderButtonPressed,
+    Repeat,
+    RepeatPressed,
+    RepeatOn,
+    RepeatOnPressed,
+    Shuffle,
+    ShufflePressed,
+    ShuffleOn,
+    ShuffleOnPressed,
+    EqToggle,
+    EqTogglePressed,
+    EqToggleOn,
+    EqToggleOnPressed,
+    PlaylistToggle,
+    PlaylistTogglePressed,
+    PlaylistToggleOn,
+    PlaylistToggleOnPressed,
+    StereoOn,
+    Stereo,
+    MonoOn,
+    Mono,
+    IndicatorPlaying,
+    IndicatorPaused,
+    IndicatorStopped,
+    IndicatorNotWorking,
+    IndicatorWorking,
+    NumbersDigit0,
+    NumbersDigit1,
+    NumbersDigit2,
+    NumbersDigit3,
+    NumbersDigit4,
+    NumbersDigit5,
+    NumbersDigit6,
+    NumbersDigit7,
+    NumbersDigit8,
+    NumbersDigit9,
+    NumbersMinus,
+    NumbersNoMinus,
+    NumbersExDigit0,
+    NumbersExDigit1,
+    NumbersExDigit2,
+    NumbersExDigit3,
+    NumbersExDigit4,
+    NumbersExDigit5,
+    NumbersExDigit6,
+    NumbersExDigit7,
+    NumbersExDigit8,
+    NumbersExDigit9,
+    NumbersExBlank,
+    NumbersExMinus,
+    EqWindow,
+    EqTitleBarSelected,
+    EqTitleBar,
+    EqSliderThumb,
+    EqSliderThumbPressed,
+    EqClose,
+    EqClosePressed,
+    EqPresets,
+    EqPresetsPressed,
+    EqGraph,
+    EqGraphLineColors,
+    EqPreampLine,
+    EqOn,
+    EqOnPressed,
+    EqOnOn,
+    EqOnOnPressed,
+    EqAuto,
+    EqAutoPressed,
+    EqAutoOn,
+    EqAutoOnPressed,
+    EqSliderBar0,
+    EqSliderBar1,
+    EqSliderBar2,
+    EqSliderBar3,
+    EqSliderBar4,
+    EqSliderBar5,
+    EqSliderBar6,
+    EqSliderBar7,
+    EqSliderBar8,
+    EqSliderBar9,
+    EqSliderBar10,
+    EqSliderBar11,
+    EqSliderBar12,
+    EqSliderBar13,
+    EqSliderBar14,
+    EqSliderBar15,
+    EqSliderBar16,
+    EqSliderBar17,
+    EqSliderBar18,
+    EqSliderBar19,
+    EqSliderBar20,
+    EqSliderBar21,
+    EqSliderBar22,
+    EqSliderBar23,
+    EqSliderBar24,
+    EqSliderBar25,
+    EqSliderBar26,
+    EqSliderBar27,
+    EqShadeSelected,
+    EqShade,
+    EqUnshadePressed,
+    EqShadePressed,
+    EqShadeClose,
+    EqShadeClosePressed,
+    EqShadeVolumeThumbLeft,
+    EqShadeVolumeThumb,
+    EqShadeVolumeThumbRight,
+    EqShadeBalanceThumbLeft,
+    EqShadeBalanceThumb,
+    EqShadeBalanceThumbRight,
+    PlaylistTopLeftSelected,
+    PlaylistTopLeft,
+    PlaylistTitleSelected,
+    PlaylistTitle,
+    PlaylistTopTileSelected,
+    PlaylistTopTile,
+    PlaylistTopRightSelected,
+    PlaylistTopRight,
+    PlaylistLeftTile,
+    PlaylistRightTile,
+    PlaylistBottomLeft,
+    PlaylistBottomRight,
+    PlaylistBottomTile,
+    PlaylistVisualizer,
+    PlaylistScrollHandle,
+    PlaylistScrollHandlePressed,
+    PlaylistClosePressed,
+    PlaylistShadePressed,
+    PlaylistUnshadePressed,
+    PlaylistShadeLeft,
+    PlaylistShadeTile,
+    PlaylistShadeRightSelected,
+    PlaylistShadeRight,
+    PlaylistAddMenuBar,
+    PlaylistRemoveMenuBar,
+    PlaylistSelectMenuBar,
+    PlaylistMiscMenuBar,
+    PlaylistListMenuBar,
+    PlaylistAddUrl,
+    PlaylistAddDir,
+    PlaylistAddFile,
+    PlaylistAddUrlPressed,
+    PlaylistAddDirPressed,
+    PlaylistAddFilePressed,
+    PlaylistRemoveAll,
+    PlaylistRemoveCrop,
+    PlaylistRemoveSelected,
+    PlaylistRemoveMisc,
+    PlaylistRemoveAllPressed,
+    PlaylistRemoveCropPressed,
+    PlaylistRemoveSelectedPressed,
+    PlaylistRemoveMiscPressed,
+    PlaylistSelectInvert,
+    PlaylistSelectNone,
+    PlaylistSelectAll,
+    PlaylistSelectInvertPressed,
+    PlaylistSelectNonePressed,
+    PlaylistSelectAllPressed,
+    PlaylistMiscSort,
+    PlaylistMiscInfo,
+    PlaylistMiscOptions,
+    PlaylistMiscSortPressed,
+    PlaylistMiscInfoPressed,
+    PlaylistMiscOptionsPressed,
+    PlaylistListNew,
+    PlaylistListSave,
+    PlaylistListLoad,
+    PlaylistListNewPressed,
+    PlaylistListSavePressed,
+    PlaylistListLoadPressed,
+    GenTopLeftSelected,
+    GenTopLeftEndSelected,
+    GenTopCenterFillSelected,
+    GenTopRightEndSelected,
+    GenTopLeftRightFillSelected,
+    GenTopRightSelected,
+    GenTopLeft,
+    GenTopLeftEnd,
+    GenTopCenterFill,
+    GenTopRightEnd,
+    GenTopLeftRightFill,
+    GenTopRight,
+    GenBottomLeft,
+    GenBottomRight,
+    GenBottomFill,
+    GenMiddleLeft,
+    GenMiddleLeftBottom,
+    GenMiddleRight,
+    GenMiddleRightBottom,
+    GenClosePressed,
 }
 
 pub fn get_skin_load_specs() -> Vec<crate::skin::FileLoadSpec<'static>> {
     vec![
     crate::skin::FileLoadSpec {
     filename: "CBUTTONS.BMP",
+    required: true,
     regions: vec![
     crate::skin::RectLoadSpec {
     top_left_x: 0,
@@ -294,6 +646,7 @@ pub fn get_skin_load_specs() -> Vec<crate::skin::FileLoadSpec<'static>> {
     ]},
     crate::skin::FileLoadSpec {
     filename: "MAIN.BMP",
+    required: true,
     regions: vec![
     crate::skin::RectLoadSpec {
     top_left_x: 0,
@@ -305,6 +658,7 @@ pub fn get_skin_load_specs() -> Vec<crate::skin::FileLoadSpec<'static>> {
     ]},
     crate::skin::FileLoadSpec {
     filename: "VOLUME.BMP",
+    required: true,
     regions: vec![
     crate::skin::RectLoadSpec {
     top_left_x: 0,
@@ -519,6 +873,7 @@ pub fn get_skin_load_specs() -> Vec<crate::skin::FileLoadSpec<'static>> {
     ]},
     crate::skin::FileLoadSpec {
     filename: "TEXT.BMP",
+    required: true,
     regions: vec![
     crate::skin::RectLoadSpec {
     top_left_x: 0,
@@ -933,6 +1288,1816 @@ pub fn get_skin_load_specs() -> Vec<crate::skin::FileLoadSpec<'static>> {
     bottom_right_y: 12,
     image: SkinImage::SmallFontHash,
     },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 12,
+    bottom_right_x: 4,
+    bottom_right_y: 18,
+    image: SkinImage::SmallFontARing,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 5,
+    top_left_y: 12,
+    bottom_right_x: 9,
+    bottom_right_y: 18,
+    image: SkinImage::SmallFontODiaeresis,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 10,
+    top_left_y: 12,
+    bottom_right_x: 14,
+    bottom_right_y: 18,
+    image: SkinImage::SmallFontADiaeresis,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 15,
+    top_left_y: 12,
+    bottom_right_x: 19,
+    bottom_right_y: 18,
+    image: SkinImage::SmallFontQuestion,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 20,
+    top_left_y: 12,
+    bottom_right_x: 24,
+    bottom_right_y: 18,
+    image: SkinImage::SmallFontAsterisk,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 150,
+    top_left_y: 0,
+    bottom_right_x: 155,
+    bottom_right_y: 6,
+    image: SkinImage::SmallFontSpace,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "TITLEBAR.BMP",
+    required: true,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 0,
+    bottom_right_x: 302,
+    bottom_right_y: 14,
+    image: SkinImage::TitleBarSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 15,
+    bottom_right_x: 302,
+    bottom_right_y: 29,
+    image: SkinImage::TitleBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 57,
+    bottom_right_x: 302,
+    bottom_right_y: 71,
+    image: SkinImage::TitleBarEasterEggSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 72,
+    bottom_right_x: 302,
+    bottom_right_y: 86,
+    image: SkinImage::TitleBarEasterEgg,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 9,
+    bottom_right_y: 9,
+    image: SkinImage::TitleBarOptions,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 9,
+    bottom_right_x: 9,
+    bottom_right_y: 18,
+    image: SkinImage::TitleBarOptionsPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 0,
+    bottom_right_x: 18,
+    bottom_right_y: 9,
+    image: SkinImage::TitleBarMinimize,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 9,
+    bottom_right_x: 18,
+    bottom_right_y: 18,
+    image: SkinImage::TitleBarMinimizePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 18,
+    bottom_right_x: 9,
+    bottom_right_y: 27,
+    image: SkinImage::TitleBarShade,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 18,
+    bottom_right_x: 18,
+    bottom_right_y: 27,
+    image: SkinImage::TitleBarShadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 27,
+    bottom_right_x: 9,
+    bottom_right_y: 36,
+    image: SkinImage::TitleBarUnshade,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 27,
+    bottom_right_x: 18,
+    bottom_right_y: 36,
+    image: SkinImage::TitleBarUnshadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 18,
+    top_left_y: 0,
+    bottom_right_x: 27,
+    bottom_right_y: 9,
+    image: SkinImage::TitleBarClose,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 18,
+    top_left_y: 9,
+    bottom_right_x: 27,
+    bottom_right_y: 18,
+    image: SkinImage::TitleBarClosePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 304,
+    top_left_y: 0,
+    bottom_right_x: 312,
+    bottom_right_y: 43,
+    image: SkinImage::ClutterBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 312,
+    top_left_y: 0,
+    bottom_right_x: 320,
+    bottom_right_y: 43,
+    image: SkinImage::ClutterBarDisabled,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 29,
+    bottom_right_x: 302,
+    bottom_right_y: 43,
+    image: SkinImage::MainShadeSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 42,
+    bottom_right_x: 302,
+    bottom_right_y: 56,
+    image: SkinImage::MainShade,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 36,
+    bottom_right_x: 17,
+    bottom_right_y: 43,
+    image: SkinImage::MainShadePositionBackground,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 17,
+    top_left_y: 36,
+    bottom_right_x: 20,
+    bottom_right_y: 43,
+    image: SkinImage::MainShadePositionThumbLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 20,
+    top_left_y: 36,
+    bottom_right_x: 23,
+    bottom_right_y: 43,
+    image: SkinImage::MainShadePositionThumb,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 36,
+    bottom_right_x: 26,
+    bottom_right_y: 43,
+    image: SkinImage::MainShadePositionThumbRight,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "POSBAR.BMP",
+    required: true,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 248,
+    bottom_right_y: 10,
+    image: SkinImage::PositionBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 248,
+    top_left_y: 0,
+    bottom_right_x: 277,
+    bottom_right_y: 10,
+    image: SkinImage::PositionBarThumb,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 278,
+    top_left_y: 0,
+    bottom_right_x: 307,
+    bottom_right_y: 10,
+    image: SkinImage::PositionBarThumbPressed,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "BALANCE.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 0,
+    bottom_right_x: 47,
+    bottom_right_y: 13,
+    image: SkinImage::BalanceSliderBar0,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 15,
+    bottom_right_x: 47,
+    bottom_right_y: 28,
+    image: SkinImage::BalanceSliderBar1,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 30,
+    bottom_right_x: 47,
+    bottom_right_y: 43,
+    image: SkinImage::BalanceSliderBar2,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 45,
+    bottom_right_x: 47,
+    bottom_right_y: 58,
+    image: SkinImage::BalanceSliderBar3,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 60,
+    bottom_right_x: 47,
+    bottom_right_y: 73,
+    image: SkinImage::BalanceSliderBar4,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 75,
+    bottom_right_x: 47,
+    bottom_right_y: 88,
+    image: SkinImage::BalanceSliderBar5,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 90,
+    bottom_right_x: 47,
+    bottom_right_y: 103,
+    image: SkinImage::BalanceSliderBar6,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 105,
+    bottom_right_x: 47,
+    bottom_right_y: 118,
+    image: SkinImage::BalanceSliderBar7,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 120,
+    bottom_right_x: 47,
+    bottom_right_y: 133,
+    image: SkinImage::BalanceSliderBar8,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 135,
+    bottom_right_x: 47,
+    bottom_right_y: 148,
+    image: SkinImage::BalanceSliderBar9,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 150,
+    bottom_right_x: 47,
+    bottom_right_y: 163,
+    image: SkinImage::BalanceSliderBar10,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 165,
+    bottom_right_x: 47,
+    bottom_right_y: 178,
+    image: SkinImage::BalanceSliderBar11,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 180,
+    bottom_right_x: 47,
+    bottom_right_y: 193,
+    image: SkinImage::BalanceSliderBar12,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 195,
+    bottom_right_x: 47,
+    bottom_right_y: 208,
+    image: SkinImage::BalanceSliderBar13,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 210,
+    bottom_right_x: 47,
+    bottom_right_y: 223,
+    image: SkinImage::BalanceSliderBar14,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 225,
+    bottom_right_x: 47,
+    bottom_right_y: 238,
+    image: SkinImage::BalanceSliderBar15,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 240,
+    bottom_right_x: 47,
+    bottom_right_y: 253,
+    image: SkinImage::BalanceSliderBar16,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 255,
+    bottom_right_x: 47,
+    bottom_right_y: 268,
+    image: SkinImage::BalanceSliderBar17,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 270,
+    bottom_right_x: 47,
+    bottom_right_y: 283,
+    image: SkinImage::BalanceSliderBar18,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 285,
+    bottom_right_x: 47,
+    bottom_right_y: 298,
+    image: SkinImage::BalanceSliderBar19,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 300,
+    bottom_right_x: 47,
+    bottom_right_y: 313,
+    image: SkinImage::BalanceSliderBar20,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 315,
+    bottom_right_x: 47,
+    bottom_right_y: 328,
+    image: SkinImage::BalanceSliderBar21,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 330,
+    bottom_right_x: 47,
+    bottom_right_y: 343,
+    image: SkinImage::BalanceSliderBar22,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 345,
+    bottom_right_x: 47,
+    bottom_right_y: 358,
+    image: SkinImage::BalanceSliderBar23,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 360,
+    bottom_right_x: 47,
+    bottom_right_y: 373,
+    image: SkinImage::BalanceSliderBar24,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 375,
+    bottom_right_x: 47,
+    bottom_right_y: 388,
+    image: SkinImage::BalanceSliderBar25,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 390,
+    bottom_right_x: 47,
+    bottom_right_y: 403,
+    image: SkinImage::BalanceSliderBar26,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 405,
+    bottom_right_x: 47,
+    bottom_right_y: 418,
+    image: SkinImage::BalanceSliderBar27,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 15,
+    top_left_y: 422,
+    bottom_right_x: 29,
+    bottom_right_y: 433,
+    image: SkinImage::BalanceSliderButton,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 422,
+    bottom_right_x: 14,
+    bottom_right_y: 433,
+    image: SkinImage::BalanceSliderButtonPressed,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "SHUFREP.BMP",
+    required: true,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 28,
+    bottom_right_y: 15,
+    image: SkinImage::Repeat,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 15,
+    bottom_right_x: 28,
+    bottom_right_y: 30,
+    image: SkinImage::RepeatPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 30,
+    bottom_right_x: 28,
+    bottom_right_y: 45,
+    image: SkinImage::RepeatOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 45,
+    bottom_right_x: 28,
+    bottom_right_y: 60,
+    image: SkinImage::RepeatOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 0,
+    bottom_right_x: 75,
+    bottom_right_y: 15,
+    image: SkinImage::Shuffle,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 15,
+    bottom_right_x: 75,
+    bottom_right_y: 30,
+    image: SkinImage::ShufflePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 30,
+    bottom_right_x: 75,
+    bottom_right_y: 45,
+    image: SkinImage::ShuffleOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 45,
+    bottom_right_x: 75,
+    bottom_right_y: 60,
+    image: SkinImage::ShuffleOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 61,
+    bottom_right_x: 23,
+    bottom_right_y: 73,
+    image: SkinImage::EqToggle,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 46,
+    top_left_y: 61,
+    bottom_right_x: 69,
+    bottom_right_y: 73,
+    image: SkinImage::EqTogglePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 73,
+    bottom_right_x: 23,
+    bottom_right_y: 85,
+    image: SkinImage::EqToggleOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 46,
+    top_left_y: 73,
+    bottom_right_x: 69,
+    bottom_right_y: 85,
+    image: SkinImage::EqToggleOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 61,
+    bottom_right_x: 46,
+    bottom_right_y: 73,
+    image: SkinImage::PlaylistToggle,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 69,
+    top_left_y: 61,
+    bottom_right_x: 92,
+    bottom_right_y: 73,
+    image: SkinImage::PlaylistTogglePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 73,
+    bottom_right_x: 46,
+    bottom_right_y: 85,
+    image: SkinImage::PlaylistToggleOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 69,
+    top_left_y: 73,
+    bottom_right_x: 92,
+    bottom_right_y: 85,
+    image: SkinImage::PlaylistToggleOnPressed,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "MONOSTER.BMP",
+    required: true,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 29,
+    bottom_right_y: 12,
+    image: SkinImage::StereoOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 12,
+    bottom_right_x: 29,
+    bottom_right_y: 24,
+    image: SkinImage::Stereo,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 29,
+    top_left_y: 0,
+    bottom_right_x: 56,
+    bottom_right_y: 12,
+    image: SkinImage::MonoOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 29,
+    top_left_y: 12,
+    bottom_right_x: 56,
+    bottom_right_y: 24,
+    image: SkinImage::Mono,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "PLAYPAUS.BMP",
+    required: true,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 9,
+    bottom_right_y: 9,
+    image: SkinImage::IndicatorPlaying,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 0,
+    bottom_right_x: 18,
+    bottom_right_y: 9,
+    image: SkinImage::IndicatorPaused,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 18,
+    top_left_y: 0,
+    bottom_right_x: 27,
+    bottom_right_y: 9,
+    image: SkinImage::IndicatorStopped,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 36,
+    top_left_y: 0,
+    bottom_right_x: 39,
+    bottom_right_y: 9,
+    image: SkinImage::IndicatorNotWorking,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 39,
+    top_left_y: 0,
+    bottom_right_x: 42,
+    bottom_right_y: 9,
+    image: SkinImage::IndicatorWorking,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "NUMBERS.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 9,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit0,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 0,
+    bottom_right_x: 18,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit1,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 18,
+    top_left_y: 0,
+    bottom_right_x: 27,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit2,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 0,
+    bottom_right_x: 36,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit3,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 36,
+    top_left_y: 0,
+    bottom_right_x: 45,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit4,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 45,
+    top_left_y: 0,
+    bottom_right_x: 54,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit5,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 0,
+    bottom_right_x: 63,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit6,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 63,
+    top_left_y: 0,
+    bottom_right_x: 72,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit7,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 72,
+    top_left_y: 0,
+    bottom_right_x: 81,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit8,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 81,
+    top_left_y: 0,
+    bottom_right_x: 90,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersDigit9,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 20,
+    top_left_y: 6,
+    bottom_right_x: 25,
+    bottom_right_y: 7,
+    image: SkinImage::NumbersMinus,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 6,
+    bottom_right_x: 14,
+    bottom_right_y: 7,
+    image: SkinImage::NumbersNoMinus,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "NUMS_EX.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 9,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit0,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 9,
+    top_left_y: 0,
+    bottom_right_x: 18,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit1,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 18,
+    top_left_y: 0,
+    bottom_right_x: 27,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit2,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 27,
+    top_left_y: 0,
+    bottom_right_x: 36,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit3,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 36,
+    top_left_y: 0,
+    bottom_right_x: 45,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit4,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 45,
+    top_left_y: 0,
+    bottom_right_x: 54,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit5,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 0,
+    bottom_right_x: 63,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit6,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 63,
+    top_left_y: 0,
+    bottom_right_x: 72,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit7,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 72,
+    top_left_y: 0,
+    bottom_right_x: 81,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit8,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 81,
+    top_left_y: 0,
+    bottom_right_x: 90,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExDigit9,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 90,
+    top_left_y: 0,
+    bottom_right_x: 99,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExBlank,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 99,
+    top_left_y: 0,
+    bottom_right_x: 108,
+    bottom_right_y: 13,
+    image: SkinImage::NumbersExMinus,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "EQMAIN.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 275,
+    bottom_right_y: 116,
+    image: SkinImage::EqWindow,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 134,
+    bottom_right_x: 275,
+    bottom_right_y: 148,
+    image: SkinImage::EqTitleBarSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 149,
+    bottom_right_x: 275,
+    bottom_right_y: 163,
+    image: SkinImage::EqTitleBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 164,
+    bottom_right_x: 11,
+    bottom_right_y: 175,
+    image: SkinImage::EqSliderThumb,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 176,
+    bottom_right_x: 11,
+    bottom_right_y: 187,
+    image: SkinImage::EqSliderThumbPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 116,
+    bottom_right_x: 9,
+    bottom_right_y: 125,
+    image: SkinImage::EqClose,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 125,
+    bottom_right_x: 9,
+    bottom_right_y: 134,
+    image: SkinImage::EqClosePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 224,
+    top_left_y: 164,
+    bottom_right_x: 268,
+    bottom_right_y: 176,
+    image: SkinImage::EqPresets,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 224,
+    top_left_y: 176,
+    bottom_right_x: 268,
+    bottom_right_y: 188,
+    image: SkinImage::EqPresetsPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 294,
+    bottom_right_x: 113,
+    bottom_right_y: 313,
+    image: SkinImage::EqGraph,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 115,
+    top_left_y: 294,
+    bottom_right_x: 116,
+    bottom_right_y: 313,
+    image: SkinImage::EqGraphLineColors,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 314,
+    bottom_right_x: 113,
+    bottom_right_y: 315,
+    image: SkinImage::EqPreampLine,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 10,
+    top_left_y: 119,
+    bottom_right_x: 36,
+    bottom_right_y: 131,
+    image: SkinImage::EqOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 128,
+    top_left_y: 119,
+    bottom_right_x: 154,
+    bottom_right_y: 131,
+    image: SkinImage::EqOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 69,
+    top_left_y: 119,
+    bottom_right_x: 95,
+    bottom_right_y: 131,
+    image: SkinImage::EqOnOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 187,
+    top_left_y: 119,
+    bottom_right_x: 213,
+    bottom_right_y: 131,
+    image: SkinImage::EqOnOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 36,
+    top_left_y: 119,
+    bottom_right_x: 68,
+    bottom_right_y: 131,
+    image: SkinImage::EqAuto,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 154,
+    top_left_y: 119,
+    bottom_right_x: 186,
+    bottom_right_y: 131,
+    image: SkinImage::EqAutoPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 95,
+    top_left_y: 119,
+    bottom_right_x: 127,
+    bottom_right_y: 131,
+    image: SkinImage::EqAutoOn,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 213,
+    top_left_y: 119,
+    bottom_right_x: 245,
+    bottom_right_y: 131,
+    image: SkinImage::EqAutoOnPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 13,
+    top_left_y: 164,
+    bottom_right_x: 27,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar0,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 164,
+    bottom_right_x: 42,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar1,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 43,
+    top_left_y: 164,
+    bottom_right_x: 57,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar2,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 58,
+    top_left_y: 164,
+    bottom_right_x: 72,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar3,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 73,
+    top_left_y: 164,
+    bottom_right_x: 87,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar4,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 88,
+    top_left_y: 164,
+    bottom_right_x: 102,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar5,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 103,
+    top_left_y: 164,
+    bottom_right_x: 117,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar6,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 118,
+    top_left_y: 164,
+    bottom_right_x: 132,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar7,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 133,
+    top_left_y: 164,
+    bottom_right_x: 147,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar8,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 148,
+    top_left_y: 164,
+    bottom_right_x: 162,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar9,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 163,
+    top_left_y: 164,
+    bottom_right_x: 177,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar10,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 178,
+    top_left_y: 164,
+    bottom_right_x: 192,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar11,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 193,
+    top_left_y: 164,
+    bottom_right_x: 207,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar12,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 208,
+    top_left_y: 164,
+    bottom_right_x: 222,
+    bottom_right_y: 227,
+    image: SkinImage::EqSliderBar13,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 13,
+    top_left_y: 229,
+    bottom_right_x: 27,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar14,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 28,
+    top_left_y: 229,
+    bottom_right_x: 42,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar15,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 43,
+    top_left_y: 229,
+    bottom_right_x: 57,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar16,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 58,
+    top_left_y: 229,
+    bottom_right_x: 72,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar17,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 73,
+    top_left_y: 229,
+    bottom_right_x: 87,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar18,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 88,
+    top_left_y: 229,
+    bottom_right_x: 102,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar19,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 103,
+    top_left_y: 229,
+    bottom_right_x: 117,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar20,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 118,
+    top_left_y: 229,
+    bottom_right_x: 132,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar21,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 133,
+    top_left_y: 229,
+    bottom_right_x: 147,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar22,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 148,
+    top_left_y: 229,
+    bottom_right_x: 162,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar23,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 163,
+    top_left_y: 229,
+    bottom_right_x: 177,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar24,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 178,
+    top_left_y: 229,
+    bottom_right_x: 192,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar25,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 193,
+    top_left_y: 229,
+    bottom_right_x: 207,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar26,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 208,
+    top_left_y: 229,
+    bottom_right_x: 222,
+    bottom_right_y: 292,
+    image: SkinImage::EqSliderBar27,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "EQ_EX.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 275,
+    bottom_right_y: 14,
+    image: SkinImage::EqShadeSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 15,
+    bottom_right_x: 275,
+    bottom_right_y: 29,
+    image: SkinImage::EqShade,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 1,
+    top_left_y: 38,
+    bottom_right_x: 10,
+    bottom_right_y: 47,
+    image: SkinImage::EqUnshadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 1,
+    top_left_y: 47,
+    bottom_right_x: 10,
+    bottom_right_y: 56,
+    image: SkinImage::EqShadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 11,
+    top_left_y: 38,
+    bottom_right_x: 20,
+    bottom_right_y: 47,
+    image: SkinImage::EqShadeClose,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 11,
+    top_left_y: 47,
+    bottom_right_x: 20,
+    bottom_right_y: 56,
+    image: SkinImage::EqShadeClosePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 1,
+    top_left_y: 30,
+    bottom_right_x: 4,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeVolumeThumbLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 4,
+    top_left_y: 30,
+    bottom_right_x: 7,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeVolumeThumb,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 7,
+    top_left_y: 30,
+    bottom_right_x: 10,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeVolumeThumbRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 11,
+    top_left_y: 30,
+    bottom_right_x: 14,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeBalanceThumbLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 14,
+    top_left_y: 30,
+    bottom_right_x: 17,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeBalanceThumb,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 17,
+    top_left_y: 30,
+    bottom_right_x: 20,
+    bottom_right_y: 37,
+    image: SkinImage::EqShadeBalanceThumbRight,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "PLEDIT.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 25,
+    bottom_right_y: 20,
+    image: SkinImage::PlaylistTopLeftSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 21,
+    bottom_right_x: 25,
+    bottom_right_y: 41,
+    image: SkinImage::PlaylistTopLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 26,
+    top_left_y: 0,
+    bottom_right_x: 126,
+    bottom_right_y: 20,
+    image: SkinImage::PlaylistTitleSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 26,
+    top_left_y: 21,
+    bottom_right_x: 126,
+    bottom_right_y: 41,
+    image: SkinImage::PlaylistTitle,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 0,
+    bottom_right_x: 152,
+    bottom_right_y: 20,
+    image: SkinImage::PlaylistTopTileSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 21,
+    bottom_right_x: 152,
+    bottom_right_y: 41,
+    image: SkinImage::PlaylistTopTile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 153,
+    top_left_y: 0,
+    bottom_right_x: 178,
+    bottom_right_y: 20,
+    image: SkinImage::PlaylistTopRightSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 153,
+    top_left_y: 21,
+    bottom_right_x: 178,
+    bottom_right_y: 41,
+    image: SkinImage::PlaylistTopRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 42,
+    bottom_right_x: 12,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistLeftTile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 31,
+    top_left_y: 42,
+    bottom_right_x: 51,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistRightTile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 72,
+    bottom_right_x: 125,
+    bottom_right_y: 110,
+    image: SkinImage::PlaylistBottomLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 126,
+    top_left_y: 72,
+    bottom_right_x: 276,
+    bottom_right_y: 110,
+    image: SkinImage::PlaylistBottomRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 179,
+    top_left_y: 0,
+    bottom_right_x: 204,
+    bottom_right_y: 38,
+    image: SkinImage::PlaylistBottomTile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 205,
+    top_left_y: 0,
+    bottom_right_x: 280,
+    bottom_right_y: 38,
+    image: SkinImage::PlaylistVisualizer,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 52,
+    top_left_y: 53,
+    bottom_right_x: 60,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistScrollHandle,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 61,
+    top_left_y: 53,
+    bottom_right_x: 69,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistScrollHandlePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 52,
+    top_left_y: 42,
+    bottom_right_x: 61,
+    bottom_right_y: 51,
+    image: SkinImage::PlaylistClosePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 62,
+    top_left_y: 42,
+    bottom_right_x: 71,
+    bottom_right_y: 51,
+    image: SkinImage::PlaylistShadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 150,
+    top_left_y: 42,
+    bottom_right_x: 159,
+    bottom_right_y: 51,
+    image: SkinImage::PlaylistUnshadePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 72,
+    top_left_y: 42,
+    bottom_right_x: 97,
+    bottom_right_y: 56,
+    image: SkinImage::PlaylistShadeLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 72,
+    top_left_y: 57,
+    bottom_right_x: 97,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistShadeTile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 99,
+    top_left_y: 42,
+    bottom_right_x: 149,
+    bottom_right_y: 56,
+    image: SkinImage::PlaylistShadeRightSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 99,
+    top_left_y: 57,
+    bottom_right_x: 149,
+    bottom_right_y: 71,
+    image: SkinImage::PlaylistShadeRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 48,
+    top_left_y: 111,
+    bottom_right_x: 51,
+    bottom_right_y: 165,
+    image: SkinImage::PlaylistAddMenuBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 100,
+    top_left_y: 111,
+    bottom_right_x: 103,
+    bottom_right_y: 183,
+    image: SkinImage::PlaylistRemoveMenuBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 150,
+    top_left_y: 111,
+    bottom_right_x: 153,
+    bottom_right_y: 165,
+    image: SkinImage::PlaylistSelectMenuBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 200,
+    top_left_y: 111,
+    bottom_right_x: 203,
+    bottom_right_y: 165,
+    image: SkinImage::PlaylistMiscMenuBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 250,
+    top_left_y: 111,
+    bottom_right_x: 253,
+    bottom_right_y: 165,
+    image: SkinImage::PlaylistListMenuBar,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 111,
+    bottom_right_x: 22,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistAddUrl,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 130,
+    bottom_right_x: 22,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistAddDir,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 149,
+    bottom_right_x: 22,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistAddFile,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 111,
+    bottom_right_x: 45,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistAddUrlPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 130,
+    bottom_right_x: 45,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistAddDirPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 23,
+    top_left_y: 149,
+    bottom_right_x: 45,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistAddFilePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 111,
+    bottom_right_x: 76,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistRemoveAll,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 130,
+    bottom_right_x: 76,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistRemoveCrop,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 149,
+    bottom_right_x: 76,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistRemoveSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 54,
+    top_left_y: 168,
+    bottom_right_x: 76,
+    bottom_right_y: 186,
+    image: SkinImage::PlaylistRemoveMisc,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 77,
+    top_left_y: 111,
+    bottom_right_x: 99,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistRemoveAllPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 77,
+    top_left_y: 130,
+    bottom_right_x: 99,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistRemoveCropPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 77,
+    top_left_y: 149,
+    bottom_right_x: 99,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistRemoveSelectedPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 77,
+    top_left_y: 168,
+    bottom_right_x: 99,
+    bottom_right_y: 186,
+    image: SkinImage::PlaylistRemoveMiscPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 104,
+    top_left_y: 111,
+    bottom_right_x: 126,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistSelectInvert,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 104,
+    top_left_y: 130,
+    bottom_right_x: 126,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistSelectNone,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 104,
+    top_left_y: 149,
+    bottom_right_x: 126,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistSelectAll,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 111,
+    bottom_right_x: 149,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistSelectInvertPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 130,
+    bottom_right_x: 149,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistSelectNonePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 149,
+    bottom_right_x: 149,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistSelectAllPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 154,
+    top_left_y: 111,
+    bottom_right_x: 176,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistMiscSort,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 154,
+    top_left_y: 130,
+    bottom_right_x: 176,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistMiscInfo,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 154,
+    top_left_y: 149,
+    bottom_right_x: 176,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistMiscOptions,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 177,
+    top_left_y: 111,
+    bottom_right_x: 199,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistMiscSortPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 177,
+    top_left_y: 130,
+    bottom_right_x: 199,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistMiscInfoPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 177,
+    top_left_y: 149,
+    bottom_right_x: 199,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistMiscOptionsPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 204,
+    top_left_y: 111,
+    bottom_right_x: 226,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistListNew,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 204,
+    top_left_y: 130,
+    bottom_right_x: 226,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistListSave,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 204,
+    top_left_y: 149,
+    bottom_right_x: 226,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistListLoad,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 227,
+    top_left_y: 111,
+    bottom_right_x: 249,
+    bottom_right_y: 129,
+    image: SkinImage::PlaylistListNewPressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 227,
+    top_left_y: 130,
+    bottom_right_x: 249,
+    bottom_right_y: 148,
+    image: SkinImage::PlaylistListSavePressed,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 227,
+    top_left_y: 149,
+    bottom_right_x: 249,
+    bottom_right_y: 167,
+    image: SkinImage::PlaylistListLoadPressed,
+    },
+    ]},
+    crate::skin::FileLoadSpec {
+    filename: "GEN.BMP",
+    required: false,
+    regions: vec![
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 0,
+    bottom_right_x: 25,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopLeftSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 26,
+    top_left_y: 0,
+    bottom_right_x: 51,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopLeftEndSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 52,
+    top_left_y: 0,
+    bottom_right_x: 77,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopCenterFillSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 78,
+    top_left_y: 0,
+    bottom_right_x: 103,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopRightEndSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 104,
+    top_left_y: 0,
+    bottom_right_x: 129,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopLeftRightFillSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 130,
+    top_left_y: 0,
+    bottom_right_x: 155,
+    bottom_right_y: 20,
+    image: SkinImage::GenTopRightSelected,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 21,
+    bottom_right_x: 25,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 26,
+    top_left_y: 21,
+    bottom_right_x: 51,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopLeftEnd,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 52,
+    top_left_y: 21,
+    bottom_right_x: 77,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopCenterFill,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 78,
+    top_left_y: 21,
+    bottom_right_x: 103,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopRightEnd,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 104,
+    top_left_y: 21,
+    bottom_right_x: 129,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopLeftRightFill,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 130,
+    top_left_y: 21,
+    bottom_right_x: 155,
+    bottom_right_y: 41,
+    image: SkinImage::GenTopRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 42,
+    bottom_right_x: 125,
+    bottom_right_y: 56,
+    image: SkinImage::GenBottomLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 0,
+    top_left_y: 57,
+    bottom_right_x: 125,
+    bottom_right_y: 71,
+    image: SkinImage::GenBottomRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 72,
+    bottom_right_x: 152,
+    bottom_right_y: 86,
+    image: SkinImage::GenBottomFill,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 127,
+    top_left_y: 42,
+    bottom_right_x: 138,
+    bottom_right_y: 71,
+    image: SkinImage::GenMiddleLeft,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 158,
+    top_left_y: 42,
+    bottom_right_x: 169,
+    bottom_right_y: 66,
+    image: SkinImage::GenMiddleLeftBottom,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 139,
+    top_left_y: 42,
+    bottom_right_x: 147,
+    bottom_right_y: 71,
+    image: SkinImage::GenMiddleRight,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 170,
+    top_left_y: 42,
+    bottom_right_x: 178,
+    bottom_right_y: 66,
+    image: SkinImage::GenMiddleRightBottom,
+    },
+    crate::skin::RectLoadSpec {
+    top_left_x: 148,
+    top_left_y: 42,
+    bottom_right_x: 157,
+    bottom_right_y: 51,
+    image: SkinImage::GenClosePressed,
+    },
     ]},
     ]
 }
\ No newline at end of file
@@ -0,0 +1,149 @@
+//! APEv1 and v2 tags, found at the end of the file (before any ID3v1 tag).
+
+use std::{fs::File, io::{self, Read, Seek, SeekFrom}};
+
+use super::{read_tail, Picture, TrackInfo};
+
+const FOOTER_LENGTH: usize = 32;
+const PREAMBLE: &[u8] = b"APETAGEX";
+/// Set on items that hold binary data rather than text
+const ITEM_BINARY: u32 = 1 << 1;
+/// Set in the footer when there's also a header in front of the items
+const HAS_HEADER: u32 = 1 << 31;
+
+/// Looks for a tag ending at `end` (a position in the file). Returns what's in it and how many
+/// bytes of the file it takes up, header included.
+pub fn read(file: &mut File, end: u64) -> io::Result<(TrackInfo, u64)> {
+    let footer = match read_tail(file, end, 0, FOOTER_LENGTH)? {
+        Some(footer) if footer.starts_with(PREAMBLE) => footer,
+        _ => return Ok((TrackInfo::default(), 0)),
+    };
+    let le32 = |bytes: &[u8]| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    // The size covers the items and the footer, but not the header.
+    let size = le32(&footer[12..16]) as u64;
+    let item_count = le32(&footer[16..20]);
+    let flags = le32(&footer[20..24]);
+    // Both ends have to be inside the file, the items start `size` before its end.
+    let items_length = match size.checked_sub(FOOTER_LENGTH as u64) {
+        Some(length) if size <= end => length,
+        _ => return Ok((TrackInfo::default(), 0)),
+    };
+
+    let mut items = vec![0u8; items_length as usize];
+    file.seek(SeekFrom::Start(end - size))?;
+    file.read_exact(&mut items)?;
+    let header_length = if flags & HAS_HEADER != 0 { FOOTER_LENGTH as u64 } else { 0 };
+    Ok((parse_items(&items, item_count), size + header_length))
+}
+
+fn parse_items(items: &[u8], count: u32) -> TrackInfo {
+    let mut info = TrackInfo::default();
+    let mut position = 0;
+    for _ in 0..count {
+        let header = match items.get(position..position + 8) {
+            Some(header) => header,
+            None => break,
+        };
+        let value_length = u32::from_le_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        let flags = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        position += 8;
+        let key_end = match items[position..].iter().position(|byte| *byte == 0) {
+            Some(length) => position + length,
+            None => break,
+        };
+        let key = String::from_utf8_lossy(&items[position..key_end]).into_owned();
+        position = key_end + 1;
+        let value = match items.get(position..position + value_length) {
+            Some(value) => value,
+            None => break,
+        };
+        position += value_length;
+
+        if key.to_ascii_lowercase().starts_with("cover art") && flags & ITEM_BINARY != 0 {
+            // A file name, then the image.
+            if let Some(name_end) = value.iter().position(|byte| *byte == 0) {
+                let name = String::from_utf8_lossy(&value[..name_end]).to_ascii_lowercase();
+                let mime_type = if name.ends_with(".png") { "image/png" } else { "image/jpeg" };
+                info.pictures.push(Picture {
+                    mime_type: mime_type.to_string(),
+                    picture_type: if key.to_ascii_lowercase().contains("back") { 4 } else { 3 },
+                    description: String::new(),
+                    data: value[name_end + 1..].to_vec(),
+                });
+            }
+        } else if flags & ITEM_BINARY == 0 {
+            // Lists of values are separated by nulls; the first will do.
+            let value = String::from_utf8_lossy(value);
+            info.set(&key, value.split('\0').next().unwrap_or_default());
+        }
+    }
+    info
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IS_HEADER: u32 = 1 << 29;
+
+    fn item(key: &str, flags: u32, value: &[u8]) -> Vec<u8> {
+        [&(value.len() as u32).to_le_bytes()[..], &flags.to_le_bytes(), key.as_bytes(), &[0], value].concat()
+    }
+
+    /// Some audio, then a tag with `items`, then an ID3v1 tag's worth of bytes.
+    /// Returns the path and where the APE tag ends.
+    fn write_file(name: &str, items: &[Vec<u8>], with_header: bool) -> (std::path::PathBuf, u64) {
+        let items = items.concat();
+        let count = 3u32;
+        let size = (items.len() + FOOTER_LENGTH) as u32;
+        let flags = if with_header { HAS_HEADER } else { 0 };
+        let block = |flags: u32| [PREAMBLE, &2000u32.to_le_bytes(), &size.to_le_bytes(), &count.to_le_bytes(), &flags.to_le_bytes(), &[0; 8]].concat();
+        let header = if with_header { block(flags | IS_HEADER) } else { vec![] };
+        let tagged = [&b"audio audio audio"[..], &header, &items, &block(flags)].concat();
+        let end = tagged.len() as u64;
+        let path = std::env::temp_dir().join(format!("ape-test-{}-{}", name, std::process::id()));
+        std::fs::write(&path, [&tagged[..], &[0; 128]].concat()).unwrap();
+        (path, end)
+    }
+
+    fn items() -> Vec<Vec<u8>> {
+        vec![
+            item("Title", 0, b"Title\0Second value"),
+            item("Track", 0, b"5/10"),
+            item("Cover Art (Front)", ITEM_BINARY, b"cover.png\0image"),
+        ]
+    }
+
+    #[test]
+    fn footer_only() {
+        let (path, end) = write_file("footer", &items(), false);
+        let (info, length) = read(&mut File::open(&path).unwrap(), end).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.track_number, Some(5));
+        assert_eq!(info.pictures[0].mime_type, "image/png");
+        assert_eq!(info.pictures[0].picture_type, 3);
+        assert_eq!(info.pictures[0].data, b"image");
+        assert_eq!(length, (items().concat().len() + FOOTER_LENGTH) as u64);
+    }
+
+    #[test]
+    fn header_and_footer() {
+        let (path, end) = write_file("header", &items(), true);
+        let (info, length) = read(&mut File::open(&path).unwrap(), end).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.track_number, Some(5));
+        assert_eq!(length, (items().concat().len() + 2 * FOOTER_LENGTH) as u64);
+    }
+
+    #[test]
+    fn no_tag() {
+        let path = std::env::temp_dir().join(format!("ape-test-none-{}", std::process::id()));
+        std::fs::write(&path, [0u8; 200]).unwrap();
+        let (info, length) = read(&mut File::open(&path).unwrap(), 200).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(info, TrackInfo::default());
+        assert_eq!(length, 0);
+    }
+}
@@ -0,0 +1,75 @@
+//! ID3v1 and v1.1: fixed size Latin-1 fields in the last 128 bytes of the file.
+
+use super::{genre_name, latin1, parse_year, TrackInfo};
+
+pub const TAG_LENGTH: usize = 128;
+
+/// `tag` is the last 128 bytes of the file. None if there's no tag there.
+pub fn parse(tag: &[u8]) -> Option<TrackInfo> {
+    if tag.len() != TAG_LENGTH || !tag.starts_with(b"TAG") {
+        return None;
+    }
+    let field = |range: std::ops::Range<usize>| {
+        let text = latin1(&tag[range]);
+        let text = text.trim_end_matches(|c: char| c == '\0' || c.is_whitespace());
+        // Some writers pad with spaces and some stop at a null, with junk after it.
+        let text = text.split('\0').next().unwrap_or_default().trim();
+        Some(text.to_string()).filter(|text| !text.is_empty())
+    };
+    let comment = &tag[97..127];
+    // v1.1 steals the last two bytes of the comment for a zero and the track number.
+    let (comment, track_number) = if comment[28] == 0 && comment[29] != 0 { (field(97..125), Some(comment[29] as u32)) } else { (field(97..127), None) };
+
+    Some(TrackInfo {
+        title: field(3..33),
+        artist: field(33..63),
+        album: field(63..93),
+        year: field(93..97).and_then(|year| parse_year(&year)),
+        comment,
+        track_number,
+        // 255 means no genre.
+        genre: genre_name(tag[127] as usize),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(comment: &[u8], genre: u8) -> Vec<u8> {
+        let field = |text: &[u8], length: usize| {
+            let mut field = text.to_vec();
+            field.resize(length, 0);
+            field
+        };
+        [b"TAG".to_vec(), field(b"Title", 30), field(b"Artist   ", 30), field(b"Alb\xfcm\0junk", 30), b"1999".to_vec(), field(comment, 30), vec![genre]].concat()
+    }
+
+    #[test]
+    fn v1_1_track_number() {
+        let info = parse(&tag(&[&b"A comment"[..], &[0; 20], &[7]].concat(), 17)).unwrap();
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.artist.as_deref(), Some("Artist"));
+        assert_eq!(info.album.as_deref(), Some("Albüm"));
+        assert_eq!(info.year, Some(1999));
+        assert_eq!(info.comment.as_deref(), Some("A comment"));
+        assert_eq!(info.track_number, Some(7));
+        assert_eq!(info.genre.as_deref(), Some("Rock"));
+    }
+
+    #[test]
+    fn v1_0_comment_runs_to_the_end() {
+        let comment = b"thirty characters of comment!!";
+        let info = parse(&tag(comment, 255)).unwrap();
+        assert_eq!(info.comment.as_deref(), Some("thirty characters of comment!!"));
+        assert_eq!(info.track_number, None);
+        assert_eq!(info.genre, None);
+    }
+
+    #[test]
+    fn not_a_tag() {
+        assert!(parse(&[0; TAG_LENGTH]).is_none());
+        assert!(parse(&tag(b"", 0)[..100]).is_none());
+    }
+}
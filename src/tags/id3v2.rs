@@ -0,0 +1,312 @@
+//! ID3v2.2, 2.3 and 2.4, from the start of the file.
+
+use super::{genre_name, latin1, parse_track_number, parse_year, utf16, Picture, TrackInfo};
+
+const HEADER_LENGTH: usize = 10;
+const FLAG_UNSYNCHRONISATION: u8 = 0x80;
+const FLAG_EXTENDED_HEADER: u8 = 0x40;
+
+/// `tag` is the whole tag, header included. Frames we can't make sense of are skipped.
+pub fn parse(tag: &[u8]) -> TrackInfo {
+    let mut info = TrackInfo::default();
+    if tag.len() < HEADER_LENGTH || !tag.starts_with(b"ID3") {
+        return info;
+    }
+    let version = tag[3];
+    let flags = tag[5];
+    let mut body = tag[HEADER_LENGTH..].to_vec();
+    // Before 2.4 unsynchronisation applied to the whole tag, in 2.4 it's per frame.
+    if version < 4 && flags & FLAG_UNSYNCHRONISATION != 0 {
+        body = resynchronise(&body);
+    }
+    let mut position = 0;
+    if flags & FLAG_EXTENDED_HEADER != 0 && body.len() >= 4 {
+        position = match version {
+            3 => 4 + u32::from_be_bytes([body[0], body[1], body[2], body[3]]) as usize,
+            4 => syncsafe(&body[0..4]) as usize,
+            _ => 0,
+        };
+    }
+
+    let (id_length, header_length) = if version == 2 { (3, 6) } else { (4, 10) };
+    while position + header_length <= body.len() {
+        let header = &body[position..position + header_length];
+        // Padding
+        if header[0] == 0 {
+            break;
+        }
+        let id = String::from_utf8_lossy(&header[..id_length]).into_owned();
+        let size = match version {
+            2 => u32::from_be_bytes([0, header[3], header[4], header[5]]) as usize,
+            3 => u32::from_be_bytes([header[4], header[5], header[6], header[7]]) as usize,
+            _ => syncsafe(&header[4..8]) as usize,
+        };
+        let frame_flags = if version == 2 { 0 } else { u16::from_be_bytes([header[8], header[9]]) };
+        position += header_length;
+        let data = match body.get(position..position + size) {
+            Some(data) => data,
+            None => break,
+        };
+        position += size;
+        if let Some(data) = frame_data(data, version, frame_flags) {
+            read_frame(&mut info, &id, &data);
+        }
+    }
+    info
+}
+
+/// The frame's contents with any extra header fields and unsynchronisation taken off.
+/// None for compressed or encrypted frames, which we don't read.
+fn frame_data(data: &[u8], version: u8, flags: u16) -> Option<Vec<u8>> {
+    match version {
+        3 => {
+            if flags & 0x00c0 != 0 {
+                return None;
+            }
+            // Grouping identity
+            Some(data.get(if flags & 0x0020 != 0 { 1 } else { 0 }..)?.to_vec())
+        }
+        4 => {
+            if flags & 0x000c != 0 {
+                return None;
+            }
+            let mut skip = 0;
+            if flags & 0x0040 != 0 {
+                skip += 1;
+            }
+            // Data length indicator
+            if flags & 0x0001 != 0 {
+                skip += 4;
+            }
+            let data = data.get(skip..)?;
+            Some(if flags & 0x0002 != 0 { resynchronise(data) } else { data.to_vec() })
+        }
+        _ => Some(data.to_vec()),
+    }
+}
+
+fn read_frame(info: &mut TrackInfo, id: &str, data: &[u8]) {
+    match id {
+        "TIT2" | "TT2" => info.title = info.title.take().or_else(|| text_frame(data)),
+        "TPE1" | "TP1" => info.artist = info.artist.take().or_else(|| text_frame(data)),
+        "TALB" | "TAL" => info.album = info.album.take().or_else(|| text_frame(data)),
+        "TRCK" | "TRK" => info.track_number = info.track_number.or_else(|| parse_track_number(&text_frame(data)?)),
+        "TYER" | "TYE" | "TDRC" => info.year = info.year.or_else(|| parse_year(&text_frame(data)?)),
+        "TCON" | "TCO" => info.genre = info.genre.take().or_else(|| text_frame(data).map(|genre| content_type(&genre))),
+        "COMM" | "COM" => info.comment = info.comment.take().or_else(|| comment_frame(data)),
+        "APIC" => info.pictures.extend(picture_frame(data, false)),
+        "PIC" => info.pictures.extend(picture_frame(data, true)),
+        _ => (),
+    }
+}
+
+/// The first value of a text frame. 2.4 can have several, separated by nulls.
+fn text_frame(data: &[u8]) -> Option<String> {
+    let (encoding, text) = data.split_first()?;
+    let text = decode(*encoding, text);
+    let text = text.split('\0').next().unwrap_or_default().trim();
+    Some(text.to_string()).filter(|text| !text.is_empty())
+}
+
+fn comment_frame(data: &[u8]) -> Option<String> {
+    // Encoding, then a three letter language, then a description and the comment itself.
+    let encoding = *data.first()?;
+    let (_description, text) = split_terminated(data.get(4..)?, encoding);
+    let text = decode(encoding, text);
+    let text = text.trim_end_matches('\0').trim();
+    Some(text.to_string()).filter(|text| !text.is_empty())
+}
+
+/// `v2_2` frames have a three letter image format instead of a MIME type.
+fn picture_frame(data: &[u8], v2_2: bool) -> Option<Picture> {
+    let encoding = *data.first()?;
+    let (mime_type, rest) = if v2_2 {
+        let format = latin1(data.get(1..4)?).to_ascii_lowercase();
+        let mime_type = match format.as_str() {
+            "jpg" => "image/jpeg".to_string(),
+            "png" => "image/png".to_string(),
+            _ => format!("image/{}", format),
+        };
+        (mime_type, data.get(4..)?)
+    } else {
+        let (mime_type, rest) = split_terminated(data.get(1..)?, 0);
+        (latin1(mime_type), rest)
+    };
+    let (picture_type, rest) = rest.split_first()?;
+    let (description, image) = split_terminated(rest, encoding);
+    Some(Picture {
+        mime_type,
+        picture_type: *picture_type,
+        description: decode(encoding, description),
+        data: image.to_vec(),
+    })
+}
+
+/// TCON can be a name, an ID3v1 genre number in brackets like "(17)", or both as in "(17)Rock".
+fn content_type(value: &str) -> String {
+    let numbered = value.strip_prefix('(').and_then(|rest| rest.split_once(')'));
+    match numbered {
+        Some((_, name)) if !name.is_empty() => name.to_string(),
+        Some((number, _)) => number.parse().ok().and_then(genre_name).unwrap_or_else(|| value.to_string()),
+        // 2.4 allows the bare number.
+        None => value.parse().ok().and_then(genre_name).unwrap_or_else(|| value.to_string()),
+    }
+}
+
+fn decode(encoding: u8, text: &[u8]) -> String {
+    match encoding {
+        1 | 2 => utf16(text),
+        3 => String::from_utf8_lossy(text).into_owned(),
+        _ => latin1(text),
+    }
+}
+
+/// Splits at the end of a null terminated string, which is two zero bytes (on a two byte boundary) in UTF-16.
+fn split_terminated(data: &[u8], encoding: u8) -> (&[u8], &[u8]) {
+    if encoding == 1 || encoding == 2 {
+        let end = (0..data.len() / 2).map(|i| i * 2).find(|i| data[*i] == 0 && data[*i + 1] == 0);
+        match end {
+            Some(end) => (&data[..end], &data[end + 2..]),
+            None => (data, &[]),
+        }
+    } else {
+        match data.iter().position(|byte| *byte == 0) {
+            Some(end) => (&data[..end], &data[end + 1..]),
+            None => (data, &[]),
+        }
+    }
+}
+
+fn syncsafe(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |acc, byte| (acc << 7) | (*byte as u32 & 0x7f))
+}
+
+/// Undoes unsynchronisation, which put a zero after every 0xff.
+fn resynchronise(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = 0;
+    for byte in data {
+        if !(previous == 0xff && *byte == 0) {
+            out.push(*byte);
+        }
+        previous = *byte;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn syncsafe_bytes(value: usize) -> [u8; 4] {
+        [(value >> 21) as u8 & 0x7f, (value >> 14) as u8 & 0x7f, (value >> 7) as u8 & 0x7f, value as u8 & 0x7f]
+    }
+
+    fn frame(version: u8, id: &[u8; 4], flags: u16, data: &[u8]) -> Vec<u8> {
+        let size = if version == 4 { syncsafe_bytes(data.len()) } else { (data.len() as u32).to_be_bytes() };
+        [&id[..], &size, &flags.to_be_bytes(), data].concat()
+    }
+
+    fn tag(version: u8, flags: u8, body: &[u8]) -> Vec<u8> {
+        [&b"ID3"[..], &[version, 0, flags], &syncsafe_bytes(body.len()), body].concat()
+    }
+
+    /// What unsynchronisation does to data: a zero after every 0xff.
+    fn unsynchronise(data: &[u8]) -> Vec<u8> {
+        data.iter().flat_map(|byte| if *byte == 0xff { vec![0xff, 0] } else { vec![*byte] }).collect()
+    }
+
+    #[test]
+    fn syncsafe_sizes() {
+        assert_eq!(syncsafe(&[0, 0, 1, 0x7f]), 0xff);
+        assert_eq!(syncsafe(&[0x7f, 0x7f, 0x7f, 0x7f]), (1 << 28) - 1);
+        assert_eq!(syncsafe(&syncsafe_bytes(123_456)), 123_456);
+    }
+
+    #[test]
+    fn v2_3_text_frames() {
+        let body = [
+            frame(3, b"TIT2", 0, b"\0Title"),
+            // UTF-16 with a byte order mark
+            frame(3, b"TPE1", 0, b"\x01\xff\xfeA\0r\0t\0i\0s\0t\0"),
+            frame(3, b"TALB", 0, b"\0Alb\xfcm"),
+            frame(3, b"TRCK", 0, b"\x003/12"),
+            frame(3, b"TYER", 0, b"\x002001"),
+            frame(3, b"TCON", 0, b"\0(17)"),
+            frame(3, b"COMM", 0, b"\0engdescription\0Nice"),
+            // Compressed, so skipped
+            frame(3, b"TIT2", 0x0080, b"\0Other title"),
+            vec![0; 16],
+        ]
+        .concat();
+        let info = parse(&tag(3, 0, &body));
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.artist.as_deref(), Some("Artist"));
+        assert_eq!(info.album.as_deref(), Some("Albüm"));
+        assert_eq!(info.track_number, Some(3));
+        assert_eq!(info.year, Some(2001));
+        assert_eq!(info.genre.as_deref(), Some("Rock"));
+        assert_eq!(info.comment.as_deref(), Some("Nice"));
+    }
+
+    #[test]
+    fn v2_4_text_frames() {
+        // Long enough that reading the size as plain big endian would go wrong.
+        let album = "a".repeat(200);
+        let body = [
+            frame(4, b"TIT2", 0, "\x03Tïtle".as_bytes()),
+            // Several values, of which only the first is used
+            frame(4, b"TPE1", 0, b"\x03One\0Two"),
+            frame(4, b"TALB", 0, &[&b"\x03"[..], album.as_bytes()].concat()),
+            frame(4, b"TDRC", 0, b"\x032010-05-01"),
+            frame(4, b"TCON", 0, b"\x0317"),
+        ]
+        .concat();
+        let info = parse(&tag(4, 0, &body));
+        assert_eq!(info.title.as_deref(), Some("Tïtle"));
+        assert_eq!(info.artist.as_deref(), Some("One"));
+        assert_eq!(info.album.as_deref(), Some(album.as_str()));
+        assert_eq!(info.year, Some(2010));
+        assert_eq!(info.genre.as_deref(), Some("Rock"));
+    }
+
+    #[test]
+    fn unsynchronisation() {
+        let picture = [&b"\0image/jpeg\0\x03\0"[..], &[0xff, 0xd8, 0xff, 0x00, 0xff]].concat();
+
+        // Up to 2.3 the whole tag is unsynchronised, and frame sizes are from before.
+        let body = [frame(3, b"APIC", 0, &picture), frame(3, b"TIT2", 0, b"\0\xff")].concat();
+        let info = parse(&tag(3, FLAG_UNSYNCHRONISATION, &unsynchronise(&body)));
+        assert_eq!(info.pictures.len(), 1);
+        assert_eq!(info.pictures[0].mime_type, "image/jpeg");
+        assert_eq!(info.pictures[0].picture_type, 3);
+        assert_eq!(info.pictures[0].data, [0xff, 0xd8, 0xff, 0x00, 0xff]);
+        assert_eq!(info.title.as_deref(), Some("ÿ"));
+
+        // In 2.4 it's frame by frame, and frame sizes are from after.
+        let body = [frame(4, b"APIC", 0x0002, &unsynchronise(&picture)), frame(4, b"TIT2", 0, b"\0Title")].concat();
+        let info = parse(&tag(4, 0, &body));
+        assert_eq!(info.pictures[0].data, [0xff, 0xd8, 0xff, 0x00, 0xff]);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn v2_2_frames() {
+        let frame = |id: &[u8; 3], data: &[u8]| [&id[..], &(data.len() as u32).to_be_bytes()[1..], data].concat();
+        let body = [frame(b"TT2", b"\0Title"), frame(b"PIC", b"\0PNG\x04\0data")].concat();
+        let info = parse(&tag(2, 0, &body));
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.pictures[0].mime_type, "image/png");
+        assert_eq!(info.pictures[0].picture_type, 4);
+        assert_eq!(info.pictures[0].data, b"data");
+    }
+
+    #[test]
+    fn genres() {
+        assert_eq!(content_type("(17)"), "Rock");
+        assert_eq!(content_type("(17)Rock & Roll"), "Rock & Roll");
+        assert_eq!(content_type("17"), "Rock");
+        assert_eq!(content_type("Shoegaze"), "Shoegaze");
+        assert_eq!(content_type("(999)"), "(999)");
+    }
+}
@@ -0,0 +1,379 @@
+//! What's known about a track from its tags and stream headers, the same whatever the format.
+
+use std::{fs::File, io::{self, Read, Seek, SeekFrom}, path::{Path, PathBuf}, sync::mpsc::{self, Receiver, Sender}, thread, time::Duration};
+
+use thiserror::Error;
+
+use crate::audio::decoder::{self, Format};
+
+pub mod ape;
+pub mod id3v1;
+pub mod id3v2;
+pub mod mp4;
+pub mod vorbis;
+
+#[derive(Error, Debug)]
+pub enum TagError {
+    #[error("IO error")]
+    Io(#[from] io::Error),
+}
+
+/// An image stored in the file, usually the album cover.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Picture {
+    pub mime_type: String,
+    /// The ID3v2/FLAC picture type, e.g. 3 for the front cover
+    pub picture_type: u8,
+    pub description: String,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TrackInfo {
+    pub artist: Option<String>,
+    pub title: Option<String>,
+    pub album: Option<String>,
+    pub track_number: Option<u32>,
+    pub year: Option<u32>,
+    pub genre: Option<String>,
+    pub comment: Option<String>,
+    pub duration: Option<Duration>,
+    /// Average over the whole file, in kbps
+    pub bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub channels: Option<usize>,
+    pub pictures: Vec<Picture>,
+}
+
+impl TrackInfo {
+    /// Fills in whatever's missing from `other`, for when a file has more than one kind of tag.
+    fn merge(&mut self, other: TrackInfo) {
+        fn fill<T>(field: &mut Option<T>, other: Option<T>) {
+            if field.is_none() {
+                *field = other;
+            }
+        }
+        fill(&mut self.artist, other.artist);
+        fill(&mut self.title, other.title);
+        fill(&mut self.album, other.album);
+        fill(&mut self.track_number, other.track_number);
+        fill(&mut self.year, other.year);
+        fill(&mut self.genre, other.genre);
+        fill(&mut self.comment, other.comment);
+        fill(&mut self.duration, other.duration);
+        fill(&mut self.bitrate, other.bitrate);
+        fill(&mut self.sample_rate, other.sample_rate);
+        fill(&mut self.channels, other.channels);
+        self.pictures.extend(other.pictures);
+    }
+
+    /// Sets a field from a tag's key, for the formats where tags are free-form key/value pairs.
+    /// Empty values and keys we don't know are ignored.
+    fn set(&mut self, key: &str, value: &str) {
+        let value = value.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+        if value.is_empty() {
+            return;
+        }
+        let text = Some(value.to_string());
+        match key.to_ascii_uppercase().as_str() {
+            "ARTIST" => self.artist = self.artist.take().or(text),
+            "TITLE" => self.title = self.title.take().or(text),
+            "ALBUM" => self.album = self.album.take().or(text),
+            "TRACK" | "TRACKNUMBER" => self.track_number = self.track_number.or_else(|| parse_track_number(value)),
+            "YEAR" | "DATE" => self.year = self.year.or_else(|| parse_year(value)),
+            "GENRE" => self.genre = self.genre.take().or(text),
+            "COMMENT" | "DESCRIPTION" => self.comment = self.comment.take().or(text),
+            _ => (),
+        }
+    }
+}
+
+/// Reads every kind of tag the file's format can have, plus what the decoder knows about the stream.
+pub fn read_info(path: &Path) -> Result<TrackInfo, TagError> {
+    let mut file = File::open(path)?;
+    let file_size = file.metadata()?.len();
+    let mut header = [0u8; 12];
+    let header_length = read_up_to(&mut file, &mut header)?;
+    let header = &header[..header_length];
+
+    let mut info = TrackInfo::default();
+    // Bytes of the file that are tags rather than audio, to leave out of the bitrate.
+    let mut tag_bytes = 0;
+
+    if mp4::is_mp4(header) {
+        info = mp4::read(&mut file, file_size)?;
+    } else {
+        let id3v2_size = decoder::id3v2_size(header).unwrap_or(0);
+        if id3v2_size > 0 {
+            let mut tag = vec![0u8; id3v2_size as usize];
+            file.seek(SeekFrom::Start(0))?;
+            let length = read_up_to(&mut file, &mut tag)?;
+            info.merge(id3v2::parse(&tag[..length]));
+            tag_bytes += id3v2_size;
+        }
+
+        let mut stream_header = vec![0u8; 4096];
+        file.seek(SeekFrom::Start(id3v2_size))?;
+        let length = read_up_to(&mut file, &mut stream_header)?;
+        stream_header.truncate(length);
+        match decoder::sniff(&stream_header) {
+            Some(Format::Flac) => {
+                file.seek(SeekFrom::Start(id3v2_size))?;
+                let (flac_info, metadata_bytes) = vorbis::read_flac(&mut file)?;
+                info.merge(flac_info);
+                tag_bytes += metadata_bytes;
+            }
+            Some(Format::Vorbis) | Some(Format::Opus) => {
+                file.seek(SeekFrom::Start(0))?;
+                info.merge(vorbis::read_ogg(&mut file)?);
+            }
+            _ => {
+                // Tags at the end: ID3v1 is the very last 128 bytes, with APE just before it if both are there.
+                let (id3v1, id3v1_bytes) = match read_tail(&mut file, file_size, 0, id3v1::TAG_LENGTH)? {
+                    Some(tail) => match id3v1::parse(&tail) {
+                        Some(id3v1) => (Some(id3v1), id3v1::TAG_LENGTH as u64),
+                        None => (None, 0),
+                    },
+                    None => (None, 0),
+                };
+                let (ape, ape_bytes) = ape::read(&mut file, file_size - id3v1_bytes)?;
+                info.merge(ape);
+                if let Some(id3v1) = id3v1 {
+                    info.merge(id3v1);
+                }
+                tag_bytes += id3v1_bytes + ape_bytes;
+            }
+        }
+    }
+
+    // The decoder is the authority on the stream itself.
+    if let Ok(source) = decoder::open(path) {
+        let spec = source.spec();
+        info.sample_rate = Some(spec.sample_rate);
+        info.channels = Some(spec.channels);
+        info.duration = source.duration().or(info.duration);
+    }
+    if let Some(duration) = info.duration.filter(|duration| duration.as_secs_f64() > 0.0) {
+        let audio_bytes = file_size.saturating_sub(tag_bytes);
+        info.bitrate = Some((audio_bytes as f64 * 8.0 / duration.as_secs_f64() / 1000.0).round() as u32);
+    }
+    Ok(info)
+}
+
+/// Reads tags on a thread of its own, as opening every file in a big folder takes a while.
+/// Dropping it stops the thread once it's finished the file it's on.
+pub struct TagReader {
+    requests: Sender<PathBuf>,
+    results: Receiver<(PathBuf, Option<TrackInfo>)>,
+    /// Requested but not back yet
+    pending: usize,
+}
+
+impl TagReader {
+    pub fn new() -> Self {
+        let (requests, thread_requests) = mpsc::channel::<PathBuf>();
+        let (thread_results, results) = mpsc::channel();
+        thread::Builder::new()
+            .name("tags".to_string())
+            .spawn(move || {
+                for path in thread_requests {
+                    let info = read_info(&path).ok();
+                    if thread_results.send((path, info)).is_err() {
+                        return;
+                    }
+                }
+            })
+            .expect("failed to spawn tag thread");
+        Self { requests, results, pending: 0 }
+    }
+
+    pub fn request(&mut self, path: PathBuf) {
+        if self.requests.send(path).is_ok() {
+            self.pending += 1;
+        }
+    }
+
+    /// Whatever has been read since last time, without waiting. None for files that couldn't be read.
+    pub fn finished(&mut self) -> Vec<(PathBuf, Option<TrackInfo>)> {
+        let finished: Vec<_> = self.results.try_iter().collect();
+        self.pending -= finished.len();
+        finished
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.pending > 0
+    }
+}
+
+/// How tracks are named in the playlist and title display, like Winamp's: `%1` artist, `%2` title,
+/// `%3` album, `%4` year, `%5` comment, `%6` genre, `%7` file name, `%8` path, `%9` extension,
+/// `%0` track number and `%%` for a percent sign.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TitleFormat(pub String);
+
+impl Default for TitleFormat {
+    fn default() -> Self {
+        Self("%1 - %2".to_string())
+    }
+}
+
+impl TitleFormat {
+    /// Files without an artist or title are just named after the file, as the format would mostly come out empty.
+    pub fn format(&self, info: &TrackInfo, path: &Path) -> String {
+        let file_name = path.file_stem().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        if info.artist.is_none() && info.title.is_none() {
+            return file_name;
+        }
+        let text = |field: &Option<String>| field.clone().unwrap_or_default();
+        let number = |field: Option<u32>| field.map(|number| number.to_string()).unwrap_or_default();
+
+        let mut formatted = String::new();
+        let mut chars = self.0.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                formatted.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('1') => formatted.push_str(&text(&info.artist)),
+                Some('2') => formatted.push_str(&text(&info.title)),
+                Some('3') => formatted.push_str(&text(&info.album)),
+                Some('4') => formatted.push_str(&number(info.year)),
+                Some('5') => formatted.push_str(&text(&info.comment)),
+                Some('6') => formatted.push_str(&text(&info.genre)),
+                Some('7') => formatted.push_str(&file_name),
+                Some('8') => formatted.push_str(&path.parent().map(|parent| parent.display().to_string()).unwrap_or_default()),
+                Some('9') => formatted.push_str(&path.extension().map(|extension| extension.to_string_lossy().into_owned()).unwrap_or_default()),
+                Some('0') => formatted.push_str(&number(info.track_number)),
+                Some('%') => formatted.push('%'),
+                Some(other) => {
+                    formatted.push('%');
+                    formatted.push(other);
+                }
+                None => formatted.push('%'),
+            }
+        }
+        formatted
+    }
+}
+
+/// Winamp's list, which is ID3v1's with Winamp's own additions on the end.
+const GENRES: &[&str] = &[
+    "Blues", "Classic Rock", "Country", "Dance", "Disco", "Funk", "Grunge", "Hip-Hop", "Jazz", "Metal",
+    "New Age", "Oldies", "Other", "Pop", "R&B", "Rap", "Reggae", "Rock", "Techno", "Industrial",
+    "Alternative", "Ska", "Death Metal", "Pranks", "Soundtrack", "Euro-Techno", "Ambient", "Trip-Hop", "Vocal", "Jazz+Funk",
+    "Fusion", "Trance", "Classical", "Instrumental", "Acid", "House", "Game", "Sound Clip", "Gospel", "Noise",
+    "AlternRock", "Bass", "Soul", "Punk", "Space", "Meditative", "Instrumental Pop", "Instrumental Rock", "Ethnic", "Gothic",
+    "Darkwave", "Techno-Industrial", "Electronic", "Pop-Folk", "Eurodance", "Dream", "Southern Rock", "Comedy", "Cult", "Gangsta",
+    "Top 40", "Christian Rap", "Pop/Funk", "Jungle", "Native American", "Cabaret", "New Wave", "Psychedelic", "Rave", "Showtunes",
+    "Trailer", "Lo-Fi", "Tribal", "Acid Punk", "Acid Jazz", "Polka", "Retro", "Musical", "Rock & Roll", "Hard Rock",
+    "Folk", "Folk-Rock", "National Folk", "Swing", "Fast Fusion", "Bebob", "Latin", "Revival", "Celtic", "Bluegrass",
+    "Avantgarde", "Gothic Rock", "Progressive Rock", "Psychedelic Rock", "Symphonic Rock", "Slow Rock", "Big Band", "Chorus", "Easy Listening", "Acoustic",
+    "Humour", "Speech", "Chanson", "Opera", "Chamber Music", "Sonata", "Symphony", "Booty Bass", "Primus", "Porn Groove",
+    "Satire", "Slow Jam", "Club", "Tango", "Samba", "Folklore", "Ballad", "Power Ballad", "Rhythmic Soul", "Freestyle",
+    "Duet", "Punk Rock", "Drum Solo", "A Cappella", "Euro-House", "Dance Hall", "Goa", "Drum & Bass", "Club-House", "Hardcore",
+    "Terror", "Indie", "BritPop", "Negerpunk", "Polsk Punk", "Beat", "Christian Gangsta Rap", "Heavy Metal", "Black Metal", "Crossover",
+    "Contemporary Christian", "Christian Rock", "Merengue", "Salsa", "Thrash Metal", "Anime", "JPop", "Synthpop",
+];
+
+fn genre_name(index: usize) -> Option<String> {
+    GENRES.get(index).map(|genre| genre.to_string())
+}
+
+/// "3" or "3/12"
+fn parse_track_number(value: &str) -> Option<u32> {
+    value.split('/').next()?.trim().parse().ok().filter(|number| *number > 0)
+}
+
+/// The year from anything starting with one, like "2001" or "2001-05-02".
+fn parse_year(value: &str) -> Option<u32> {
+    let digits: String = value.trim().chars().take_while(|c| c.is_ascii_digit()).collect();
+    if digits.len() == 4 {
+        digits.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| *byte as char).collect()
+}
+
+/// UTF-16 with a byte order mark, or big endian without one.
+fn utf16(bytes: &[u8]) -> String {
+    let (bytes, little_endian) = match bytes {
+        [0xff, 0xfe, rest @ ..] => (rest, true),
+        [0xfe, 0xff, rest @ ..] => (rest, false),
+        _ => (bytes, false),
+    };
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| if little_endian { u16::from_le_bytes([pair[0], pair[1]]) } else { u16::from_be_bytes([pair[0], pair[1]]) })
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Fills as much of `buffer` as the file has, returning how much that was.
+fn read_up_to(file: &mut File, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match file.read(&mut buffer[filled..])? {
+            0 => break,
+            read => filled += read,
+        }
+    }
+    Ok(filled)
+}
+
+/// `length` bytes ending `from_end` bytes before the end of the file, if the file's that long.
+fn read_tail(file: &mut File, file_size: u64, from_end: u64, length: usize) -> io::Result<Option<Vec<u8>>> {
+    let start = match file_size.checked_sub(from_end + length as u64) {
+        Some(start) => start,
+        None => return Ok(None),
+    };
+    let mut tail = vec![0u8; length];
+    file.seek(SeekFrom::Start(start))?;
+    file.read_exact(&mut tail)?;
+    Ok(Some(tail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_format_codes() {
+        let info = TrackInfo {
+            artist: Some("Artist".to_string()),
+            title: Some("Title".to_string()),
+            album: Some("Album".to_string()),
+            year: Some(1997),
+            comment: Some("Comment".to_string()),
+            genre: Some("Rock".to_string()),
+            track_number: Some(7),
+            ..Default::default()
+        };
+        let path = Path::new("/music/album/07 song.flac");
+        let format = |format: &str| TitleFormat(format.to_string()).format(&info, path);
+        assert_eq!(format("%1|%2|%3|%4|%5|%6|%7|%8|%9|%0|%%"), "Artist|Title|Album|1997|Comment|Rock|07 song|/music/album|flac|7|%");
+        // Codes that don't mean anything are left alone.
+        assert_eq!(format("%x 100%"), "%x 100%");
+        assert_eq!(TitleFormat::default().format(&info, path), "Artist - Title");
+
+        // Missing fields come out empty, unless there's nothing to go on at all.
+        let title_only = TrackInfo { title: Some("Title".to_string()), ..Default::default() };
+        assert_eq!(TitleFormat::default().format(&title_only, path), " - Title");
+        assert_eq!(TitleFormat("%0. %2".to_string()).format(&title_only, path), ". Title");
+        assert_eq!(TitleFormat::default().format(&TrackInfo::default(), path), "07 song");
+    }
+
+    #[test]
+    fn numbers_in_text() {
+        assert_eq!(parse_track_number("3/12"), Some(3));
+        assert_eq!(parse_track_number(" 4 "), Some(4));
+        assert_eq!(parse_track_number("0"), None);
+        assert_eq!(parse_year("2001-05-02"), Some(2001));
+        assert_eq!(parse_year("01"), None);
+    }
+}
@@ -0,0 +1,246 @@
+//! MP4/M4A: iTunes-style tags in moov/udta/meta/ilst, and the stream details from the movie header
+//! and the first audio sample description. There's no MP4 decoder, so this is all we know about them.
+
+use std::{fs::File, io::{self, Read, Seek, SeekFrom}, time::Duration};
+
+use super::{genre_name, parse_year, Picture, TrackInfo};
+
+/// The type of a data atom holding a PNG; covers are JPEG otherwise.
+const DATA_PNG: u32 = 14;
+/// moov is read whole, but anything bigger than this isn't a real one.
+const MAX_MOOV: u64 = 64 * 1024 * 1024;
+
+/// An ftyp box first, from its first 12 bytes.
+pub fn is_mp4(header: &[u8]) -> bool {
+    header.len() >= 8 && &header[4..8] == b"ftyp"
+}
+
+pub fn read(file: &mut File, file_size: u64) -> io::Result<TrackInfo> {
+    let mut info = TrackInfo::default();
+    // Find moov among the top level boxes, which are too big to read (mdat) until we get there.
+    let mut position = 0;
+    while position + 8 <= file_size {
+        let mut header = [0u8; 16];
+        file.seek(SeekFrom::Start(position))?;
+        file.read_exact(&mut header[..8])?;
+        let (size, header_length) = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            0 => (file_size - position, 8),
+            1 => {
+                file.read_exact(&mut header[8..16])?;
+                let mut large = [0u8; 8];
+                large.copy_from_slice(&header[8..16]);
+                (u64::from_be_bytes(large), 16)
+            }
+            size => (size as u64, 8),
+        };
+        if size < header_length {
+            break;
+        }
+        if &header[4..8] == b"moov" {
+            if size > MAX_MOOV {
+                break;
+            }
+            let mut moov = vec![];
+            file.take(size - header_length).read_to_end(&mut moov)?;
+            read_moov(&moov, &mut info);
+            break;
+        }
+        position += size;
+    }
+    Ok(info)
+}
+
+fn read_moov(moov: &[u8], info: &mut TrackInfo) {
+    for (name, body) in boxes(moov) {
+        match name {
+            b"mvhd" => info.duration = movie_duration(body),
+            b"trak" if info.sample_rate.is_none() => read_sample_description(body, info),
+            b"udta" => {
+                for (name, body) in boxes(body) {
+                    // meta is a full box, with four bytes of version and flags before its children.
+                    if name == b"meta" && body.len() > 4 {
+                        for (name, body) in boxes(&body[4..]) {
+                            if name == b"ilst" {
+                                read_items(body, info);
+                            }
+                        }
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+fn movie_duration(mvhd: &[u8]) -> Option<Duration> {
+    let (timescale, duration, unknown) = match mvhd.first()? {
+        0 => (be32(mvhd.get(12..16)?) as u64, be32(mvhd.get(16..20)?) as u64, u32::MAX as u64),
+        _ => {
+            let duration = mvhd.get(24..32)?;
+            (be32(mvhd.get(20..24)?) as u64, (be32(&duration[..4]) as u64) << 32 | be32(&duration[4..]) as u64, u64::MAX)
+        }
+    };
+    // All ones is how an mvhd says it doesn't know.
+    if timescale == 0 || duration == unknown {
+        return None;
+    }
+    Duration::try_from_secs_f64(duration as f64 / timescale as f64).ok()
+}
+
+/// trak/mdia/minf/stbl/stsd, then the channel count and rate in an mp4a (or similar) sample entry.
+fn read_sample_description(trak: &[u8], info: &mut TrackInfo) {
+    let stsd = find_path(trak, &[b"mdia", b"minf", b"stbl", b"stsd"]);
+    // Version, flags and entry count, then the first entry's size and format.
+    let entry = match stsd.and_then(|stsd| stsd.get(8..)) {
+        Some(entry) if entry.len() >= 36 => entry,
+        _ => return,
+    };
+    let format = &entry[4..8];
+    if format != b"mp4a" && format != b"alac" {
+        return;
+    }
+    info.channels = Some(u16::from_be_bytes([entry[24], entry[25]]) as usize);
+    // 16.16 fixed point
+    info.sample_rate = Some(be32(&entry[32..36]) >> 16);
+}
+
+fn read_items(ilst: &[u8], info: &mut TrackInfo) {
+    for (name, body) in boxes(ilst) {
+        for (data_name, data) in boxes(body) {
+            if data_name != b"data" || data.len() < 8 {
+                continue;
+            }
+            let data_type = be32(&data[0..4]) & 0x00ff_ffff;
+            // Type, then four bytes of locale.
+            let value = &data[8..];
+            let text = || Some(String::from_utf8_lossy(value).trim().to_string()).filter(|text| !text.is_empty());
+            match name {
+                b"\xa9nam" => info.title = info.title.take().or_else(text),
+                b"\xa9ART" | b"aART" => info.artist = info.artist.take().or_else(text),
+                b"\xa9alb" => info.album = info.album.take().or_else(text),
+                b"\xa9day" => info.year = info.year.or_else(|| parse_year(&text()?)),
+                b"\xa9gen" => info.genre = info.genre.take().or_else(text),
+                b"\xa9cmt" => info.comment = info.comment.take().or_else(text),
+                // Two bytes of padding, the track number and the total.
+                b"trkn" if value.len() >= 4 => info.track_number = info.track_number.or(Some(u16::from_be_bytes([value[2], value[3]]) as u32).filter(|number| *number > 0)),
+                // An ID3v1 genre number, plus one.
+                b"gnre" if value.len() >= 2 => info.genre = info.genre.take().or_else(|| genre_name((u16::from_be_bytes([value[0], value[1]]) as usize).checked_sub(1)?)),
+                b"covr" => info.pictures.push(Picture {
+                    mime_type: if data_type == DATA_PNG { "image/png" } else { "image/jpeg" }.to_string(),
+                    picture_type: 3,
+                    description: String::new(),
+                    data: value.to_vec(),
+                }),
+                _ => (),
+            }
+        }
+    }
+}
+
+fn find_path<'a>(data: &'a [u8], path: &[&[u8; 4]]) -> Option<&'a [u8]> {
+    let (first, rest) = path.split_first()?;
+    let (_, body) = boxes(data).find(|(name, _)| name == *first)?;
+    if rest.is_empty() {
+        Some(body)
+    } else {
+        find_path(body, rest)
+    }
+}
+
+/// The boxes directly inside `data`, as (name, contents).
+fn boxes(data: &[u8]) -> impl Iterator<Item = (&[u8], &[u8])> {
+    let mut position = 0;
+    std::iter::from_fn(move || {
+        let header = data.get(position..position + 8)?;
+        let size = match be32(&header[0..4]) as usize {
+            0 => data.len() - position,
+            size => size,
+        };
+        let body = data.get(position + 8..position + size)?;
+        position += size;
+        Some((&header[4..8], body))
+    })
+}
+
+fn be32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mp4_box(name: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        [&(8 + body.len() as u32).to_be_bytes()[..], &name[..], body].concat()
+    }
+
+    /// An ilst item holding one data box of `data_type`.
+    fn item(name: &[u8; 4], data_type: u32, value: &[u8]) -> Vec<u8> {
+        mp4_box(name, &mp4_box(b"data", &[&data_type.to_be_bytes()[..], &[0; 4], value].concat()))
+    }
+
+    fn mvhd_v0(timescale: u32, duration: u32) -> Vec<u8> {
+        [&[0u8; 12][..], &timescale.to_be_bytes(), &duration.to_be_bytes(), &[0; 80]].concat()
+    }
+
+    fn mvhd_v1(timescale: u32, duration: u64) -> Vec<u8> {
+        [&[1u8, 0, 0, 0][..], &[0; 16], &timescale.to_be_bytes(), &duration.to_be_bytes(), &[0; 80]].concat()
+    }
+
+    #[test]
+    fn ilst_items() {
+        let ilst = [
+            item(b"\xa9nam", 1, b"Title"),
+            item(b"\xa9ART", 1, b"Artist"),
+            item(b"\xa9alb", 1, b"Album"),
+            item(b"\xa9day", 1, b"2004-03-02T00:00:00Z"),
+            item(b"\xa9cmt", 1, b" "),
+            // Padding, track 3 of 12, padding
+            item(b"trkn", 0, &[0, 0, 0, 3, 0, 12, 0, 0]),
+            item(b"gnre", 0, &[0, 18]),
+            item(b"covr", DATA_PNG, b"png data"),
+        ]
+        .concat();
+        let meta = mp4_box(b"meta", &[&[0u8; 4][..], &mp4_box(b"hdlr", &[0; 25]), &mp4_box(b"ilst", &ilst)].concat());
+        let moov = [mp4_box(b"mvhd", &mvhd_v0(1000, 90_500)), mp4_box(b"udta", &meta)].concat();
+
+        let mut info = TrackInfo::default();
+        read_moov(&moov, &mut info);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.artist.as_deref(), Some("Artist"));
+        assert_eq!(info.album.as_deref(), Some("Album"));
+        assert_eq!(info.year, Some(2004));
+        assert_eq!(info.comment, None);
+        assert_eq!(info.track_number, Some(3));
+        assert_eq!(info.genre.as_deref(), Some("Rock"));
+        assert_eq!(info.pictures[0].mime_type, "image/png");
+        assert_eq!(info.pictures[0].data, b"png data");
+        assert_eq!(info.duration, Some(Duration::from_millis(90_500)));
+    }
+
+    #[test]
+    fn track_number_zero_is_none() {
+        let mut info = TrackInfo::default();
+        read_items(&item(b"trkn", 0, &[0; 8]), &mut info);
+        assert_eq!(info.track_number, None);
+    }
+
+    #[test]
+    fn movie_durations() {
+        assert_eq!(movie_duration(&mvhd_v0(44100, 441_000)), Some(Duration::from_secs(10)));
+        assert_eq!(movie_duration(&mvhd_v1(44100, 441_000)), Some(Duration::from_secs(10)));
+        assert_eq!(movie_duration(&mvhd_v0(0, 441_000)), None);
+        // All ones means unknown.
+        assert_eq!(movie_duration(&mvhd_v0(1000, u32::MAX)), None);
+        assert_eq!(movie_duration(&mvhd_v1(1000, u64::MAX)), None);
+        // Too long for a Duration.
+        assert_eq!(movie_duration(&mvhd_v1(1, u64::MAX - 1)), None);
+        assert_eq!(movie_duration(&[1, 0, 0, 0]), None);
+    }
+
+    #[test]
+    fn ftyp_first() {
+        assert!(is_mp4(b"\0\0\0\x20ftypM4A "));
+        assert!(!is_mp4(b"\0\0\0\x20moov"));
+    }
+}
@@ -0,0 +1,247 @@
+//! Vorbis comments, in FLAC metadata blocks and in the second packet of Ogg Vorbis and Opus streams,
+//! along with FLAC's picture blocks.
+
+use std::{fs::File, io::{self, Read}};
+
+use super::{Picture, TrackInfo};
+
+const FLAC_VORBIS_COMMENT: u8 = 4;
+const FLAC_PICTURE: u8 = 6;
+/// Comment packets with big embedded pictures span a lot of pages, but not this many bytes.
+const MAX_COMMENT_PACKET: usize = 16 * 1024 * 1024;
+
+/// `file` is positioned at "fLaC". Returns the tags and how many bytes the metadata blocks take up.
+pub fn read_flac(file: &mut File) -> io::Result<(TrackInfo, u64)> {
+    let mut info = TrackInfo::default();
+    let mut marker = [0u8; 4];
+    file.read_exact(&mut marker)?;
+    let mut metadata_bytes = 4;
+    loop {
+        let mut header = [0u8; 4];
+        file.read_exact(&mut header)?;
+        let last = header[0] & 0x80 != 0;
+        let block_type = header[0] & 0x7f;
+        let length = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+        metadata_bytes += 4 + length;
+
+        let mut block = vec![];
+        file.take(length).read_to_end(&mut block)?;
+        match block_type {
+            FLAC_VORBIS_COMMENT => parse_comments(&block, &mut info),
+            FLAC_PICTURE => info.pictures.extend(parse_picture(&block)),
+            _ => (),
+        }
+        if last || block.len() < length as usize {
+            break;
+        }
+    }
+    Ok((info, metadata_bytes))
+}
+
+/// `file` is positioned at the first Ogg page. The comments are the second packet of the first stream.
+pub fn read_ogg(file: &mut File) -> io::Result<TrackInfo> {
+    let mut info = TrackInfo::default();
+    let mut packets_done = 0;
+    let mut packet = vec![];
+    let mut serial = None;
+    loop {
+        let mut header = [0u8; 27];
+        if file.read_exact(&mut header).is_err() || &header[0..4] != b"OggS" {
+            break;
+        }
+        let page_serial = u32::from_le_bytes([header[14], header[15], header[16], header[17]]);
+        let mut segments = vec![0u8; header[26] as usize];
+        file.read_exact(&mut segments)?;
+        let mut body = vec![];
+        file.take(segments.iter().map(|length| *length as u64).sum()).read_to_end(&mut body)?;
+        // Chained or multiplexed streams: only the first one matters.
+        if *serial.get_or_insert(page_serial) != page_serial {
+            continue;
+        }
+
+        let mut offset = 0;
+        for length in segments {
+            let length = length as usize;
+            if packets_done == 1 {
+                packet.extend_from_slice(body.get(offset..offset + length).unwrap_or_default());
+            }
+            offset += length;
+            // A segment shorter than 255 ends the packet.
+            if length < 255 {
+                packets_done += 1;
+                if packets_done == 2 {
+                    let comments = packet.strip_prefix(b"\x03vorbis").or_else(|| packet.strip_prefix(b"OpusTags")).unwrap_or_default();
+                    parse_comments(comments, &mut info);
+                    return Ok(info);
+                }
+            }
+        }
+        if packet.len() > MAX_COMMENT_PACKET {
+            break;
+        }
+    }
+    Ok(info)
+}
+
+/// Vendor string, then a count of `KEY=value` strings, all lengths little endian.
+fn parse_comments(data: &[u8], info: &mut TrackInfo) {
+    let mut position = 0;
+    let next = |position: &mut usize| -> Option<&[u8]> {
+        let length = data.get(*position..*position + 4)?;
+        let length = u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize;
+        let field = data.get(*position + 4..*position + 4 + length)?;
+        *position += 4 + length;
+        Some(field)
+    };
+    if next(&mut position).is_none() {
+        return;
+    }
+    let count = match data.get(position..position + 4) {
+        Some(count) => u32::from_le_bytes([count[0], count[1], count[2], count[3]]),
+        None => return,
+    };
+    position += 4;
+    for _ in 0..count {
+        let comment = match next(&mut position) {
+            Some(comment) => String::from_utf8_lossy(comment).into_owned(),
+            None => break,
+        };
+        if let Some((key, value)) = comment.split_once('=') {
+            if key.eq_ignore_ascii_case("METADATA_BLOCK_PICTURE") {
+                if let Some(block) = base64_decode(value) {
+                    info.pictures.extend(parse_picture(&block));
+                }
+            } else {
+                info.set(key, value);
+            }
+        }
+    }
+}
+
+/// A FLAC picture block: type, MIME type, description, dimensions, then the image. Big endian throughout.
+fn parse_picture(block: &[u8]) -> Option<Picture> {
+    let mut position = 0;
+    let u32_at = |position: &mut usize| -> Option<u32> {
+        let bytes = block.get(*position..*position + 4)?;
+        *position += 4;
+        Some(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    };
+    let picture_type = u32_at(&mut position)?;
+    let mime_length = u32_at(&mut position)? as usize;
+    let mime_type = String::from_utf8_lossy(block.get(position..position + mime_length)?).into_owned();
+    position += mime_length;
+    let description_length = u32_at(&mut position)? as usize;
+    let description = String::from_utf8_lossy(block.get(position..position + description_length)?).into_owned();
+    position += description_length;
+    // Width, height, colour depth and palette size
+    position += 16;
+    let data_length = u32_at(&mut position)? as usize;
+    let data = block.get(position..position + data_length)?.to_vec();
+    Some(Picture {
+        mime_type,
+        picture_type: picture_type.min(u8::MAX as u32) as u8,
+        description,
+        data,
+    })
+}
+
+fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+    for c in text.bytes().filter(|c| !c.is_ascii_whitespace() && *c != b'=') {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            _ => return None,
+        };
+        buffer = (buffer << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((buffer >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn comments(comments: &[&str]) -> Vec<u8> {
+        let field = |text: &str| [&(text.len() as u32).to_le_bytes()[..], text.as_bytes()].concat();
+        let mut data = field("vendor");
+        data.extend((comments.len() as u32).to_le_bytes());
+        for comment in comments {
+            data.extend(field(comment));
+        }
+        data
+    }
+
+    /// A front cover PNG of four bytes, described as "Cover".
+    fn picture_block() -> Vec<u8> {
+        [&3u32.to_be_bytes()[..], &9u32.to_be_bytes(), b"image/png", &5u32.to_be_bytes(), b"Cover", &[0; 16], &4u32.to_be_bytes(), &[1, 2, 3, 4]].concat()
+    }
+
+    #[test]
+    fn vorbis_comments() {
+        let mut info = TrackInfo::default();
+        parse_comments(&comments(&[
+            "TITLE=Title",
+            "artist=Artist",
+            "ARTIST=Second artist",
+            "TRACKNUMBER=4/9",
+            "DATE=1999-01-01",
+            "no equals sign",
+            "METADATA_BLOCK_PICTURE=AAAAAwAAAAlpbWFnZS9wbmcAAAAFQ292ZXIAAAAAAAAAAAAAAAAAAAAAAAAABAECAwQ=",
+        ]), &mut info);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.artist.as_deref(), Some("Artist"));
+        assert_eq!(info.track_number, Some(4));
+        assert_eq!(info.year, Some(1999));
+        assert_eq!(info.pictures, [parse_picture(&picture_block()).unwrap()]);
+
+        // A count larger than what's there stops at the end.
+        let mut info = TrackInfo::default();
+        let mut truncated = comments(&["TITLE=Title"]);
+        truncated[10] = 5;
+        parse_comments(&truncated, &mut info);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+    }
+
+    #[test]
+    fn flac_picture() {
+        let picture = parse_picture(&picture_block()).unwrap();
+        assert_eq!(picture.picture_type, 3);
+        assert_eq!(picture.mime_type, "image/png");
+        assert_eq!(picture.description, "Cover");
+        assert_eq!(picture.data, [1, 2, 3, 4]);
+        assert!(parse_picture(&picture_block()[..40]).is_none());
+    }
+
+    #[test]
+    fn flac_metadata_blocks() {
+        let block = |header: u8, body: &[u8]| [&[header][..], &(body.len() as u32).to_be_bytes()[1..], body].concat();
+        let comments = comments(&["TITLE=Title"]);
+        let data = [&b"fLaC"[..], &block(0, &[0; 34]), &block(FLAC_VORBIS_COMMENT, &comments), &block(0x80 | FLAC_PICTURE, &picture_block()), b"audio"].concat();
+        let path = std::env::temp_dir().join(format!("vorbis-test-{}.flac", std::process::id()));
+        std::fs::write(&path, &data).unwrap();
+        let (info, metadata_bytes) = read_flac(&mut File::open(&path).unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(info.title.as_deref(), Some("Title"));
+        assert_eq!(info.pictures.len(), 1);
+        assert_eq!(metadata_bytes, data.len() as u64 - 5);
+    }
+
+    #[test]
+    fn base64() {
+        assert_eq!(base64_decode("TWFu").unwrap(), b"Man");
+        assert_eq!(base64_decode("TWE=").unwrap(), b"Ma");
+        assert_eq!(base64_decode("TQ==\n").unwrap(), b"M");
+        assert!(base64_decode("T!==").is_none());
+    }
+}
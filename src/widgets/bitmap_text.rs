@@ -0,0 +1,66 @@
+use eframe::egui::{epaint::{Mesh, Shape}, Color32, Painter, Pos2, Rect, Ui, Vec2};
+use fixed_map::Map;
+
+use crate::app::LoadedTexture;
+use crate::skin_generated::{char_SmallFont, SkinImage};
+
+/// Every TEXT.BMP glyph takes up the same space, gap included.
+pub const GLYPH_SIZE: Vec2 = Vec2::new(5.0, 6.0);
+
+/// The TEXT.BMP glyph to draw for a character. The font only has upper case and a handful of
+/// symbols, so other characters are swapped for the closest thing it has. None draws a space.
+pub fn glyph_for(c: char) -> Option<SkinImage> {
+    let c = match c {
+        'À'..='Ã' | 'à'..='ã' => 'A',
+        'å' => 'Å',
+        'ä' => 'Ä',
+        'ö' => 'Ö',
+        'Ç' | 'ç' => 'C',
+        'È'..='Ë' | 'è'..='ë' => 'E',
+        'Ì'..='Ï' | 'ì'..='ï' => 'I',
+        'Ñ' | 'ñ' => 'N',
+        'Ò'..='Õ' | 'Ø' | 'ò'..='õ' | 'ø' => 'O',
+        'Ù'..='Ü' | 'ù'..='ü' => 'U',
+        'Ý' | 'ý' | 'ÿ' => 'Y',
+        '<' | '{' => '(',
+        '>' | '}' => ')',
+        '`' | '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '|' => '!',
+        '~' => '-',
+        '–' | '—' => '-',
+        ';' => ':',
+        c => c.to_ascii_uppercase(),
+    };
+    char_SmallFont(c)
+}
+
+/// Width in pixels that `text` takes up.
+pub fn text_width(text: &str) -> f32 {
+    text.chars().count() as f32 * GLYPH_SIZE.x
+}
+
+/// Paint `text` with its top left at `pos`, clipped to `clip`.
+pub fn paint_text(ui: &Ui, textures: &Map<SkinImage, LoadedTexture>, text: &str, pos: Pos2, clip: Rect) {
+    let painter = ui.painter().sub_region(clip);
+    let space = textures.get(SkinImage::SmallFontSpace);
+    for (i, c) in text.chars().enumerate() {
+        let cell = Rect::from_min_size(pos + Vec2::new(i as f32 * GLYPH_SIZE.x, 0.0), GLYPH_SIZE);
+        if !cell.intersects(clip) {
+            continue;
+        }
+        // Glyphs don't cover the gap to their right, so lay a blank cell under each one.
+        if let Some(space) = space {
+            paint_texture(&painter, space, cell.min);
+        }
+        if let Some(glyph) = glyph_for(c).and_then(|image| textures.get(image)) {
+            paint_texture(&painter, glyph, cell.min);
+        }
+    }
+}
+
+fn paint_texture(painter: &Painter, texture: &LoadedTexture, pos: Pos2) {
+    let mut mesh = Mesh::with_texture(texture.texture);
+    mesh.add_rect_with_uv(Rect::from_min_size(pos, texture.size), texture.uv, Color32::WHITE);
+    painter.add(Shape::mesh(mesh));
+}
@@ -18,9 +18,9 @@ pub struct MultiImageButton {
 impl MultiImageButton {
     pub fn new(texture: &LoadedTexture, hover_texture: &LoadedTexture, click_texture: &LoadedTexture) -> Self {
         Self {
-            image: egui::widgets::Image::new(texture.texture, texture.size.clone()),
-            hover_image: egui::widgets::Image::new(hover_texture.texture, hover_texture.size.clone()),
-            click_image: egui::widgets::Image::new(click_texture.texture, hover_texture.size.clone()),
+            image: texture.image(),
+            hover_image: hover_texture.image(),
+            click_image: click_texture.image(),
             sense: Sense::click(),
             frame: false,
             selected: false,
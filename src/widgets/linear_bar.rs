@@ -1,39 +1,50 @@
-use eframe::egui::{self, Color32, Rect, Response, Sense, TextureId, Ui, Vec2, Widget};
+use std::ops::RangeInclusive;
+
+use eframe::egui::{self, Pos2, Rect, Response, Sense, Ui, Widget};
 
 use crate::app::LoadedTexture;
 
 
-/// A clickable image within a frame.
+/// A slider drawn with a different bar image depending on its value, like the equalizer bands.
+/// Pressing anywhere on the bar jumps the handle there, and double-clicking resets it.
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
-#[derive(Clone, Debug)]
-pub struct LinearBar {
+#[derive(Debug)]
+pub struct LinearBar<'a> {
     bar_images: Vec<egui::widgets::Image>,
     handle_image: egui::widgets::Image,
     handle_image_clicked: egui::widgets::Image,
     sense: Sense,
     selected: bool,
     orientation: BarOrientation,
+    /// Space between either end of the bar and the furthest the handle goes
     handle_margin: f32,
-    value: f32,
+    value: &'a mut f32,
+    range: RangeInclusive<f32>,
+    reset_value: f32,
 }
 
 #[derive(Clone, Debug)]
 pub enum BarOrientation {
+    /// Lowest value at the bottom
     Vertical,
+    /// Lowest value on the left
     Horizontal
 }
 
-impl LinearBar {
-    pub fn new(orientation: BarOrientation, handle_margin: f32, bar_textures: Vec<&LoadedTexture>, hover_texture: &LoadedTexture, click_texture: &LoadedTexture, default_value: f32) -> Self {
+impl<'a> LinearBar<'a> {
+    pub fn new(value: &'a mut f32, range: RangeInclusive<f32>, orientation: BarOrientation, handle_margin: f32, bar_textures: Vec<&LoadedTexture>, hover_texture: &LoadedTexture, click_texture: &LoadedTexture) -> Self {
+        let reset_value = (range.start() + range.end()) / 2.0;
         Self {
-            bar_images: bar_textures.iter().map(|tex| egui::widgets::Image::new(tex.texture, tex.size.clone())).collect(),
-            handle_image: egui::widgets::Image::new(hover_texture.texture, hover_texture.size.clone()),
-            handle_image_clicked: egui::widgets::Image::new(click_texture.texture, hover_texture.size.clone()),
+            bar_images: bar_textures.iter().map(|tex| tex.image()).collect(),
+            handle_image: hover_texture.image(),
+            handle_image_clicked: click_texture.image(),
             sense: Sense::click_and_drag(),
             selected: false,
             orientation,
             handle_margin,
-            value: default_value,
+            value,
+            range,
+            reset_value,
         }
     }
 
@@ -49,9 +60,15 @@ impl LinearBar {
         self.sense = sense;
         self
     }
+
+    /// What double-clicking sets the value to. Default is the middle of the range.
+    pub fn reset_value(mut self, reset_value: f32) -> Self {
+        self.reset_value = reset_value;
+        self
+    }
 }
 
-impl Widget for LinearBar {
+impl<'a> Widget for LinearBar<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
         let Self {
             bar_images,
@@ -62,33 +79,61 @@ impl Widget for LinearBar {
             orientation,
             handle_margin,
             value,
+            range,
+            reset_value,
         } = self;
 
-        // pick the right bar image based on the value
-        let bar_image_index = (value * (bar_images.len() as f32 - 1.0)).round() as usize;
-        let bar_image = bar_images.get(bar_image_index).unwrap(); // double check the math here.
+        let bar_size = bar_images.first().unwrap().size();
+        let (rect, mut response) = ui.allocate_exact_size(bar_size, sense);
+        response.widget_info(|| egui::WidgetInfo::slider(*value as f64, ""));
 
-        let bar_size = bar_image.size();
-        let (rect, response) = ui.allocate_exact_size(bar_size, sense);
-        response.widget_info(|| egui::WidgetInfo::new(egui::WidgetType::DragValue));
+        // How far along the bar the handle's middle can go, as (start, end) in screen coordinates.
+        // For vertical bars the start is the bottom, so it's the larger y.
+        let handle_size = handle_image.size();
+        let (start, end) = match orientation {
+            BarOrientation::Vertical => (rect.max.y - handle_margin - handle_size.y / 2.0, rect.min.y + handle_margin + handle_size.y / 2.0),
+            BarOrientation::Horizontal => (rect.min.x + handle_margin + handle_size.x / 2.0, rect.max.x - handle_margin - handle_size.x / 2.0),
+        };
+        let (min, max) = (*range.start(), *range.end());
+        let old_value = *value;
+
+        if response.double_clicked() {
+            *value = reset_value;
+        } else if response.is_pointer_button_down_on() {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let along = match orientation {
+                    BarOrientation::Vertical => pointer.y,
+                    BarOrientation::Horizontal => pointer.x,
+                };
+                *value = egui::emath::remap_clamp(along, start..=end, min..=max);
+            }
+        }
+        *value = value.clamp(min.min(max), min.max(max));
+        if *value != old_value {
+            response.mark_changed();
+        }
 
         if ui.clip_rect().intersects(rect) {
-            // let image_to_show = {
-            //     if response.clicked() || response.is_pointer_button_down_on() {
-            //         click_image
-            //     }
-            //     else if selected {
-            //         click_image
-            //     }
-            //     else if response.hovered() {
-            //         hover_image
-            //     }
-            //     else {
-            //         image
-            //     }
-            // };
-
-            // image_to_show.paint_at(ui, rect);
+            // pick the right bar image based on the value
+            let normalized = if max == min { 0.0 } else { (*value - min) / (max - min) };
+            let bar_image_index = (normalized * (bar_images.len() as f32 - 1.0)).round() as usize;
+            let bar_image = bar_images.get(bar_image_index).unwrap(); // double check the math here.
+            bar_image.paint_at(ui, rect);
+
+            let handle_centre = egui::emath::lerp(start..=end, normalized);
+            let handle_centre = match orientation {
+                BarOrientation::Vertical => Pos2::new(rect.center().x, handle_centre),
+                BarOrientation::Horizontal => Pos2::new(handle_centre, rect.center().y),
+            };
+            // Snap to whole pixels, sprites don't look right in between.
+            let handle_rect = Rect::from_center_size(handle_centre, handle_size);
+            let handle_rect = Rect::from_min_size(handle_rect.min.round(), handle_size);
+            let image_to_show = if selected || response.is_pointer_button_down_on() {
+                handle_image_clicked
+            } else {
+                handle_image
+            };
+            image_to_show.paint_at(ui, handle_rect);
         }
 
         response
@@ -0,0 +1,77 @@
+use eframe::egui::{self, Response, Sense, Ui, Vec2, Widget};
+use fixed_map::Map;
+
+use crate::app::LoadedTexture;
+use crate::skin_generated::SkinImage;
+use crate::widgets::bitmap_text::{paint_text, text_width, GLYPH_SIZE};
+
+/// Put between the end of the text and its start coming round again.
+const SEPARATOR: &str = "  ***  ";
+/// Winamp moves the text along a few pixels at a time rather than smoothly.
+const STEP_PIXELS: f32 = 5.0;
+const STEP_SECONDS: f64 = 0.2;
+
+/// How far a marquee has scrolled. Lives in the app so it carries on between frames.
+#[derive(Debug, Default, Clone)]
+pub struct MarqueeState {
+    /// Pixels scrolled, from 0 up to the width of the text plus separator
+    offset: f32,
+    last_step: f64,
+}
+
+/// Text that scrolls to the left when it's too long for its box, like the song title in the main window.
+/// It can be dragged to scroll it by hand.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct Marquee<'a> {
+    text: &'a str,
+    state: &'a mut MarqueeState,
+    size: Vec2,
+    textures: &'a Map<SkinImage, LoadedTexture>,
+}
+
+impl<'a> Marquee<'a> {
+    /// `width` is in pixels. The height is always that of one line of text.
+    pub fn new(text: &'a str, width: f32, state: &'a mut MarqueeState, textures: &'a Map<SkinImage, LoadedTexture>) -> Self {
+        Self {
+            text,
+            state,
+            size: Vec2::new(width, GLYPH_SIZE.y),
+            textures,
+        }
+    }
+}
+
+impl<'a> Widget for Marquee<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { text, state, size, textures } = self;
+
+        let (rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, text));
+
+        if text_width(text) <= size.x {
+            state.offset = 0.0;
+            paint_text(ui, textures, text, rect.min, rect);
+            return response;
+        }
+
+        let looped = format!("{}{}", text, SEPARATOR);
+        let loop_width = text_width(&looped);
+        let now = ui.input().time;
+        if response.dragged() {
+            state.offset -= response.drag_delta().x;
+            state.last_step = now;
+        } else if now - state.last_step >= STEP_SECONDS {
+            state.offset += STEP_PIXELS;
+            state.last_step = now;
+        }
+        state.offset = state.offset.rem_euclid(loop_width);
+        ui.ctx().request_repaint();
+
+        // Two copies back to back, so the start follows on from the end.
+        let start = rect.min - Vec2::new(state.offset.round(), 0.0);
+        paint_text(ui, textures, &looped, start, rect);
+        paint_text(ui, textures, &looped, start + Vec2::new(loop_width, 0.0), rect);
+
+        response
+    }
+}
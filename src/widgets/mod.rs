@@ -1,3 +1,8 @@
+pub mod bitmap_text;
 pub mod button;
 pub mod linear_bar;
-pub mod slider;
\ No newline at end of file
+pub mod marquee;
+pub mod seek_bar;
+pub mod shade_slider;
+pub mod slider;
+pub mod time_display;
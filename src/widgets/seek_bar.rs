@@ -0,0 +1,97 @@
+use eframe::egui::{self, Image, Rect, Response, Sense, Ui, Vec2, Widget};
+
+use crate::app::LoadedTexture;
+
+pub struct SeekBarGraphics<T> {
+    pub bar: T,
+    pub thumb: T,
+    pub thumb_pressed: T,
+}
+
+impl<'a> From<SeekBarGraphics<&'a LoadedTexture>> for SeekBarGraphics<Image> {
+    fn from(textures: SeekBarGraphics<&'a LoadedTexture>) -> Self {
+        Self {
+            bar: textures.bar.image(),
+            thumb: textures.thumb.image(),
+            thumb_pressed: textures.thumb_pressed.image(),
+        }
+    }
+}
+
+/// What the seek bar is doing between frames.
+#[derive(Debug, Default, Clone)]
+pub struct SeekBarState {
+    /// Where the thumb is being dragged to, as a fraction of the track
+    dragging: Option<f32>,
+    /// Set when the thumb is let go, until it's picked up with `take_seek`
+    released: Option<f32>,
+}
+
+impl SeekBarState {
+    /// Where the thumb is being held, as a fraction of the track.
+    pub fn preview(&self) -> Option<f32> {
+        self.dragging
+    }
+
+    /// Where the thumb was let go, as a fraction of the track, if it was since last asked.
+    pub fn take_seek(&mut self) -> Option<f32> {
+        self.released.take()
+    }
+}
+
+/// The position bar in the main window. Like Winamp, dragging the thumb doesn't seek until it's let go.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct SeekBar<'a> {
+    state: &'a mut SeekBarState,
+    /// None when there's nothing to seek in, which hides the thumb
+    position: Option<f32>,
+    graphics: SeekBarGraphics<Image>,
+}
+
+impl<'a> SeekBar<'a> {
+    /// `position` is how far through the track playback is, from 0 to 1.
+    pub fn new(state: &'a mut SeekBarState, position: Option<f32>, graphics: SeekBarGraphics<&LoadedTexture>) -> Self {
+        Self {
+            state,
+            position,
+            graphics: graphics.into(),
+        }
+    }
+}
+
+impl<'a> Widget for SeekBar<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { state, position, graphics } = self;
+
+        let (rect, response) = ui.allocate_exact_size(graphics.bar.size(), Sense::click_and_drag());
+        response.widget_info(|| egui::WidgetInfo::new(egui::WidgetType::Slider));
+
+        let thumb_size = graphics.thumb.size();
+        let travel = (rect.width() - thumb_size.x).max(1.0);
+
+        if position.is_some() {
+            // The thumb's middle follows the pointer, wherever on the bar it was pressed.
+            if let Some(pointer) = response.interact_pointer_pos() {
+                if response.is_pointer_button_down_on() {
+                    state.dragging = Some(((pointer.x - rect.min.x - thumb_size.x / 2.0) / travel).clamp(0.0, 1.0));
+                }
+            }
+            if response.drag_released() || response.clicked() {
+                state.released = state.dragging.take();
+            }
+        } else {
+            state.dragging = None;
+        }
+
+        if ui.clip_rect().intersects(rect) {
+            graphics.bar.paint_at(ui, rect);
+            if let Some(position) = state.dragging.or(position) {
+                let thumb = if state.dragging.is_some() { &graphics.thumb_pressed } else { &graphics.thumb };
+                let thumb_rect = Rect::from_min_size(rect.min + Vec2::new((position.clamp(0.0, 1.0) * travel).round(), 0.0), thumb_size);
+                thumb.paint_at(ui, thumb_rect);
+            }
+        }
+
+        response
+    }
+}
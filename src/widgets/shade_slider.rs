@@ -0,0 +1,87 @@
+use std::ops::RangeInclusive;
+
+use eframe::egui::{self, Image, Rect, Response, Sense, Ui, Vec2, Widget};
+
+use crate::app::LoadedTexture;
+
+/// The thumb changes shape depending on which third of the slider it's in.
+pub struct ShadeSliderGraphics<T> {
+    pub thumb_left: T,
+    pub thumb: T,
+    pub thumb_right: T,
+}
+
+impl<'a> From<ShadeSliderGraphics<&'a LoadedTexture>> for ShadeSliderGraphics<Image> {
+    fn from(textures: ShadeSliderGraphics<&'a LoadedTexture>) -> Self {
+        Self {
+            thumb_left: textures.thumb_left.image(),
+            thumb: textures.thumb.image(),
+            thumb_right: textures.thumb_right.image(),
+        }
+    }
+}
+
+impl<T> ShadeSliderGraphics<T> {
+    /// The thumb for `fraction` of the way along, from 0 to 1.
+    pub fn thumb_at(&self, fraction: f32) -> &T {
+        if fraction < 1.0 / 3.0 {
+            &self.thumb_left
+        } else if fraction < 2.0 / 3.0 {
+            &self.thumb
+        } else {
+            &self.thumb_right
+        }
+    }
+}
+
+/// The tiny sliders in the shaded windows. There's no bar image, as the track is part of the window.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct ShadeSlider<'a> {
+    value: &'a mut f32,
+    range: RangeInclusive<f32>,
+    width: f32,
+    graphics: ShadeSliderGraphics<Image>,
+}
+
+impl<'a> ShadeSlider<'a> {
+    /// `width` is the length of the track, thumb included.
+    pub fn new(value: &'a mut f32, range: RangeInclusive<f32>, width: f32, graphics: ShadeSliderGraphics<&LoadedTexture>) -> Self {
+        Self {
+            value,
+            range,
+            width,
+            graphics: graphics.into(),
+        }
+    }
+}
+
+impl<'a> Widget for ShadeSlider<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self { value, range, width, graphics } = self;
+
+        let thumb_size = graphics.thumb.size();
+        let (rect, mut response) = ui.allocate_exact_size(Vec2::new(width, thumb_size.y), Sense::click_and_drag());
+        response.widget_info(|| egui::WidgetInfo::slider(*value as f64, ""));
+
+        let travel = (rect.width() - thumb_size.x).max(1.0);
+        let (min, max) = (*range.start(), *range.end());
+        let old_value = *value;
+        if response.is_pointer_button_down_on() {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let fraction = ((pointer.x - rect.min.x - thumb_size.x / 2.0) / travel).clamp(0.0, 1.0);
+                *value = egui::emath::lerp(min..=max, fraction);
+            }
+        }
+        if *value != old_value {
+            response.mark_changed();
+        }
+
+        if ui.clip_rect().intersects(rect) {
+            let fraction = if max == min { 0.0 } else { ((*value - min) / (max - min)).clamp(0.0, 1.0) };
+            let thumb_rect = Rect::from_min_size(rect.min + Vec2::new((fraction * travel).round(), 0.0), thumb_size);
+            graphics.thumb_at(fraction).paint_at(ui, thumb_rect);
+        }
+
+        response
+    }
+}
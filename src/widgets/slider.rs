@@ -40,9 +40,9 @@ pub struct SliderGraphics<T> {
 impl<'a> From<SliderGraphics<&'a LoadedTexture>> for SliderGraphics<eframe::egui::widgets::Image> {
     fn from(textures: SliderGraphics<&'a LoadedTexture>) -> Self {
         Self {
-            bar: textures.bar.iter().map(|tex| eframe::egui::widgets::Image::new(tex.texture, tex.size.clone())).collect(),
-            handle: eframe::egui::widgets::Image::new(textures.handle.texture, textures.handle.size.clone()),
-            handle_clicked: eframe::egui::widgets::Image::new(textures.handle_clicked.texture, textures.handle_clicked.size.clone()),
+            bar: textures.bar.iter().map(|tex| tex.image()).collect(),
+            handle: textures.handle.image(),
+            handle_clicked: textures.handle_clicked.image(),
         }
     }
 }
@@ -80,6 +80,8 @@ pub struct WinampSlider<'a> {
     text_color: Option<Color32>,
     min_decimals: usize,
     max_decimals: Option<usize>,
+    mirrored_frames: bool,
+    snap: Option<(f64, f64)>,
     image_bundle: SliderGraphics<eframe::egui::widgets::Image>
 }
 
@@ -170,6 +172,8 @@ impl<'a> WinampSlider<'a> {
             text_color: None,
             min_decimals: 0,
             max_decimals: None,
+            mirrored_frames: false,
+            snap: None,
             image_bundle: textures.into()
         }
     }
@@ -277,6 +281,19 @@ impl<'a> WinampSlider<'a> {
         self
     }
 
+    /// Pick `bar` frames by distance from the middle of the range rather than from the start,
+    /// so the first frame is the centre and the last is either end. This is how BALANCE.BMP is laid out.
+    pub fn mirrored_frames(mut self, mirrored_frames: bool) -> Self {
+        self.mirrored_frames = mirrored_frames;
+        self
+    }
+
+    /// Values set within `distance` of `value` become exactly `value`, e.g. to make it easy to centre a balance control.
+    pub fn snap(mut self, value: f64, distance: f64) -> Self {
+        self.snap = Some((value, distance));
+        self
+    }
+
     /// Helper: equivalent to `self.precision(0).smallest_positive(1.0)`.
     /// If you use one of the integer constructors (e.g. `Slider::i32`) this is called for you,
     /// but if you want to have a slider for picking integer values in an `Slider::f64`, use this.
@@ -301,6 +318,11 @@ impl<'a> WinampSlider<'a> {
             let end = *self.range.end();
             value = value.clamp(start.min(end), start.max(end));
         }
+        if let Some((snap_value, distance)) = self.snap {
+            if (value - snap_value).abs() <= distance {
+                value = snap_value;
+            }
+        }
         if let Some(max_decimals) = self.max_decimals {
             value = emath::round_to_decimals(value, max_decimals);
         }
@@ -394,13 +416,20 @@ impl<'a> WinampSlider<'a> {
         {
             let value = self.get_value();
 
-            let rail_image_index = (value * (self.image_bundle.bar.len() as f64 - 1.0)).round() as usize;
-            println!("index {}", rail_image_index);
+            let mut normalized = normalized_from_value(value, self.range(), &self.spec);
+            if self.mirrored_frames {
+                normalized = (normalized * 2.0 - 1.0).abs();
+            }
+            let rail_image_index = (normalized * (self.image_bundle.bar.len() as f64 - 1.0)).round() as usize;
             let rail_image = self.image_bundle.bar.get(rail_image_index).unwrap(); // double check the math here.
             rail_image.paint_at(ui, *rect);
             let marker_center_x = self.x_from_value(value, x_range);
 
-            let handle_image = self.image_bundle.handle;
+            let handle_image = if response.is_pointer_button_down_on() {
+                self.image_bundle.handle_clicked
+            } else {
+                self.image_bundle.handle
+            };
             let handle_image_rect = Rect::from_center_size(pos2(marker_center_x, rect.center().y), handle_image.size());
             handle_image.paint_at(ui, handle_image_rect);
         }
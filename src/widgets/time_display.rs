@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use eframe::egui::{self, Image, Pos2, Rect, Response, Sense, Ui, Vec2, Widget};
+
+use crate::app::LoadedTexture;
+
+/// Offsets of the four digits from the left of the display, mm:ss
+const DIGIT_OFFSETS: [f32; 4] = [12.0, 24.0, 42.0, 54.0];
+/// Height of a digit, which the minus sign is centred within
+const DIGIT_HEIGHT: f32 = 13.0;
+/// From the left of the minus sign to the right of the last digit
+const DISPLAY_SIZE: Vec2 = Vec2::new(63.0, DIGIT_HEIGHT);
+
+pub struct DigitGraphics<T> {
+    /// 0 to 9
+    pub digits: Vec<T>,
+    pub minus: T,
+    /// Drawn where the minus goes when showing elapsed time
+    pub no_minus: T,
+}
+
+impl<'a> From<DigitGraphics<&'a LoadedTexture>> for DigitGraphics<Image> {
+    fn from(textures: DigitGraphics<&'a LoadedTexture>) -> Self {
+        Self {
+            digits: textures.digits.iter().map(|tex| tex.image()).collect(),
+            minus: textures.minus.image(),
+            no_minus: textures.no_minus.image(),
+        }
+    }
+}
+
+/// The mm:ss time readout in the main window, drawn with the skin's digits.
+/// Clicking switches between elapsed and remaining time.
+#[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
+pub struct TimeDisplay<'a> {
+    show_remaining: &'a mut bool,
+    /// None when stopped, which leaves the display empty
+    position: Option<Duration>,
+    duration: Option<Duration>,
+    blink: bool,
+    graphics: DigitGraphics<Image>,
+}
+
+impl<'a> TimeDisplay<'a> {
+    pub fn new(show_remaining: &'a mut bool, graphics: DigitGraphics<&LoadedTexture>) -> Self {
+        Self {
+            show_remaining,
+            position: None,
+            duration: None,
+            blink: false,
+            graphics: graphics.into(),
+        }
+    }
+
+    /// Where playback is, and how long the track is if that's known. Remaining time needs the duration.
+    pub fn position(mut self, position: Duration, duration: Option<Duration>) -> Self {
+        self.position = Some(position);
+        self.duration = duration;
+        self
+    }
+
+    /// Flash the time on and off, as while paused.
+    pub fn blink(mut self, blink: bool) -> Self {
+        self.blink = blink;
+        self
+    }
+}
+
+impl<'a> Widget for TimeDisplay<'a> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let Self {
+            show_remaining,
+            position,
+            duration,
+            blink,
+            graphics,
+        } = self;
+
+        let (rect, mut response) = ui.allocate_exact_size(DISPLAY_SIZE, Sense::click());
+        response.widget_info(|| egui::WidgetInfo::new(egui::WidgetType::Label));
+        if response.clicked() {
+            *show_remaining = !*show_remaining;
+            response.mark_changed();
+        }
+
+        // On for half a second, off for half a second.
+        let blinked_off = blink && (ui.input().time * 2.0) as i64 % 2 == 1;
+        let position = match position {
+            Some(position) if !blinked_off => position,
+            _ => return response,
+        };
+        if !ui.clip_rect().intersects(rect) {
+            return response;
+        }
+
+        let (time, negative) = match duration {
+            Some(duration) if *show_remaining => (duration.saturating_sub(position), true),
+            _ => (position, false),
+        };
+        // Minutes don't roll over into hours, so stop at the biggest thing that fits.
+        let seconds = time.as_secs().min(99 * 60 + 59);
+        let digits = [seconds / 600, seconds / 60 % 10, seconds % 60 / 10, seconds % 10];
+
+        let sign = if negative { graphics.minus } else { graphics.no_minus };
+        let sign_offset = Vec2::new(0.0, ((DIGIT_HEIGHT - sign.size().y) / 2.0).floor());
+        sign.paint_at(ui, Rect::from_min_size(rect.min + sign_offset, sign.size()));
+
+        for (digit, x) in digits.iter().zip(DIGIT_OFFSETS.iter()) {
+            let image = &graphics.digits[*digit as usize];
+            image.paint_at(ui, Rect::from_min_size(Pos2::new(rect.min.x + x, rect.min.y), image.size()));
+        }
+
+        response
+    }
+}
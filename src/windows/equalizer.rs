@@ -0,0 +1,187 @@
+use eframe::egui::{self, Color32, Pos2, Rect, Sense, Ui, Vec2};
+use fixed_map::Map;
+
+use crate::app::LoadedTexture;
+use crate::audio::{EqSettings, EQ_RANGE_DB};
+use crate::skin_generated::{iter_EqSliderBar, SkinImage};
+use crate::widgets::button::MultiImageButton;
+use crate::widgets::linear_bar::{BarOrientation, LinearBar};
+use crate::widgets::shade_slider::{ShadeSlider, ShadeSliderGraphics};
+use crate::windows::layout::TITLE_BAR_HEIGHT;
+
+const GRAPH_POS: Vec2 = Vec2::new(86.0, 17.0);
+/// Middle row of the graph, where 0 dB is
+const GRAPH_ZERO_Y: f32 = 9.0;
+const PREAMP_POS: Vec2 = Vec2::new(21.0, 38.0);
+const FIRST_BAND_POS: Vec2 = Vec2::new(78.0, 38.0);
+const BAND_SPACING: f32 = 18.0;
+/// Where the volume and balance tracks are in the shaded window, and how long they are
+const SHADE_VOLUME_POS: Vec2 = Vec2::new(61.0, 4.0);
+const SHADE_VOLUME_WIDTH: f32 = 97.0;
+const SHADE_BALANCE_POS: Vec2 = Vec2::new(164.0, 4.0);
+const SHADE_BALANCE_WIDTH: f32 = 43.0;
+
+/// What happened in the equalizer window this frame.
+#[derive(Default)]
+pub struct EqualizerEvents {
+    /// The settings were changed and need passing on to the player.
+    pub changed: bool,
+    pub closed: bool,
+    /// The shade button was clicked.
+    pub shade: bool,
+    /// The volume or balance was changed in the shaded window.
+    pub volume_changed: bool,
+    pub balance_changed: bool,
+    /// The presets button, for hanging the presets menu off
+    pub presets: Option<egui::Response>,
+    /// For dragging the window around by
+    pub title_bar: Option<egui::Response>,
+}
+
+/// Draws the equalizer window with its top left at `origin`.
+/// `graph_colors` is the strip of EqGraphLineColors pixels, top to bottom.
+pub fn equalizer_window(ui: &mut Ui, origin: Pos2, focused: bool, settings: &mut EqSettings, auto: &mut bool, textures: &Map<SkinImage, LoadedTexture>, graph_colors: &[Color32]) -> EqualizerEvents {
+    use SkinImage::*;
+    let mut events = EqualizerEvents::default();
+    let texture = |image| textures.get(image).unwrap();
+    let put = |ui: &mut Ui, offset: Vec2, image| {
+        let texture = texture(image);
+        ui.put(Rect::from_min_size(origin + offset, texture.size), texture.image())
+    };
+
+    put(ui, Vec2::ZERO, EqWindow);
+    put(ui, Vec2::ZERO, if focused { EqTitleBarSelected } else { EqTitleBar });
+    // Everything left of the shade and close buttons
+    let title_bar = Rect::from_min_size(origin, Vec2::new(254.0, TITLE_BAR_HEIGHT));
+    events.title_bar = Some(ui.interact(title_bar, ui.id().with("equalizer title bar"), Sense::click_and_drag()));
+    // Only the pressed shade button is a sprite of its own; otherwise it's part of the title bar.
+    events.shade = pressed_button(ui, origin + Vec2::new(254.0, 3.0), "equalizer shade", texture(EqShadePressed)).clicked();
+
+    let button = |ui: &mut Ui, offset: Vec2, image, pressed| {
+        let texture = texture(image);
+        ui.put(Rect::from_min_size(origin + offset, texture.size), MultiImageButton::new(texture, texture, textures.get(pressed).unwrap()))
+    };
+    let (on, on_pressed) = if settings.enabled { (EqOnOn, EqOnOnPressed) } else { (EqOn, EqOnPressed) };
+    if button(ui, Vec2::new(14.0, 18.0), on, on_pressed).clicked() {
+        settings.enabled = !settings.enabled;
+        events.changed = true;
+    }
+    let (auto_image, auto_pressed) = if *auto { (EqAutoOn, EqAutoOnPressed) } else { (EqAuto, EqAutoPressed) };
+    if button(ui, Vec2::new(40.0, 18.0), auto_image, auto_pressed).clicked() {
+        *auto = !*auto;
+    }
+    events.presets = Some(button(ui, Vec2::new(217.0, 18.0), EqPresets, EqPresetsPressed));
+    events.closed = button(ui, Vec2::new(264.0, 3.0), EqClose, EqClosePressed).clicked();
+
+    let bar_textures = || iter_EqSliderBar().map(texture).collect::<Vec<_>>();
+    let slider = |ui: &mut Ui, offset: Vec2, value: &mut f32| {
+        let bar = LinearBar::new(value, -EQ_RANGE_DB..=EQ_RANGE_DB, BarOrientation::Vertical, 1.0, bar_textures(), texture(EqSliderThumb), texture(EqSliderThumbPressed))
+            .reset_value(0.0);
+        ui.put(Rect::from_min_size(origin + offset, texture(EqSliderBar0).size), bar).changed()
+    };
+    events.changed |= slider(ui, PREAMP_POS, &mut settings.preamp);
+    for (i, band) in settings.bands.iter_mut().enumerate() {
+        events.changed |= slider(ui, FIRST_BAND_POS + Vec2::new(i as f32 * BAND_SPACING, 0.0), band);
+    }
+
+    paint_graph(ui, origin + GRAPH_POS, settings, textures, graph_colors);
+    events
+}
+
+/// Draws the shaded equalizer with its top left at `origin`: just the volume and balance.
+pub fn equalizer_shade(ui: &mut Ui, origin: Pos2, focused: bool, volume: &mut f32, balance: &mut i32, textures: &Map<SkinImage, LoadedTexture>) -> EqualizerEvents {
+    use SkinImage::*;
+    let mut events = EqualizerEvents::default();
+    let texture = |image| textures.get(image).unwrap();
+    let shade = texture(if focused { EqShadeSelected } else { EqShade });
+    shade.image().paint_at(ui, Rect::from_min_size(origin, shade.size));
+
+    events.shade = pressed_button(ui, origin + Vec2::new(254.0, 3.0), "equalizer shade", texture(EqUnshadePressed)).clicked();
+    let close = texture(EqShadeClose);
+    let close_rect = Rect::from_min_size(origin + Vec2::new(264.0, 3.0), close.size);
+    events.closed = ui.put(close_rect, MultiImageButton::new(close, close, texture(EqShadeClosePressed))).clicked();
+
+    let volume_graphics = ShadeSliderGraphics {
+        thumb_left: texture(EqShadeVolumeThumbLeft),
+        thumb: texture(EqShadeVolumeThumb),
+        thumb_right: texture(EqShadeVolumeThumbRight),
+    };
+    let volume_rect = Rect::from_min_size(origin + SHADE_VOLUME_POS, Vec2::new(SHADE_VOLUME_WIDTH, volume_graphics.thumb.size.y));
+    events.volume_changed = ui.put(volume_rect, ShadeSlider::new(volume, 0.0..=1.0, SHADE_VOLUME_WIDTH, volume_graphics)).changed();
+
+    let balance_graphics = ShadeSliderGraphics {
+        thumb_left: texture(EqShadeBalanceThumbLeft),
+        thumb: texture(EqShadeBalanceThumb),
+        thumb_right: texture(EqShadeBalanceThumbRight),
+    };
+    let balance_rect = Rect::from_min_size(origin + SHADE_BALANCE_POS, Vec2::new(SHADE_BALANCE_WIDTH, balance_graphics.thumb.size.y));
+    let mut balance_value = *balance as f32;
+    if ui.put(balance_rect, ShadeSlider::new(&mut balance_value, -100.0..=100.0, SHADE_BALANCE_WIDTH, balance_graphics)).changed() {
+        *balance = balance_value.round() as i32;
+        events.balance_changed = true;
+    }
+
+    // Last, so the sliders get first go at the pointer.
+    let title_bar = Rect::from_min_size(origin, Vec2::new(254.0, TITLE_BAR_HEIGHT));
+    events.title_bar = Some(ui.interact(title_bar, ui.id().with("equalizer title bar"), Sense::click_and_drag()));
+    events
+}
+
+/// A 9×9 title bar button that's only drawn while held down.
+fn pressed_button(ui: &mut Ui, pos: Pos2, id: &str, pressed: &LoadedTexture) -> egui::Response {
+    let rect = Rect::from_min_size(pos, pressed.size);
+    let response = ui.interact(rect, ui.id().with(id), Sense::click());
+    if response.is_pointer_button_down_on() {
+        pressed.image().paint_at(ui, rect);
+    }
+    response
+}
+
+fn texture_of(textures: &Map<SkinImage, LoadedTexture>, image: SkinImage) -> &LoadedTexture {
+    textures.get(image).unwrap()
+}
+
+/// The response graph above the sliders: a smooth line through the band values, coloured by height,
+/// over a flat line for the preamp.
+fn paint_graph(ui: &mut Ui, origin: Pos2, settings: &EqSettings, textures: &Map<SkinImage, LoadedTexture>, graph_colors: &[Color32]) {
+    let background = texture_of(textures, SkinImage::EqGraph);
+    background.image().paint_at(ui, Rect::from_min_size(origin, background.size));
+    let width = background.size.x as usize;
+    let height = background.size.y;
+    let to_row = |gain: f32| (GRAPH_ZERO_Y - gain / EQ_RANGE_DB * GRAPH_ZERO_Y).round().clamp(0.0, height - 1.0);
+
+    let preamp_line = texture_of(textures, SkinImage::EqPreampLine);
+    let preamp_rect = Rect::from_min_size(origin + Vec2::new(0.0, to_row(settings.preamp)), preamp_line.size);
+    preamp_line.image().paint_at(ui, preamp_rect);
+
+    let mut previous_row = None;
+    for x in 0..width {
+        let position = x as f32 / (width - 1) as f32 * (settings.bands.len() - 1) as f32;
+        let row = to_row(catmull_rom(&settings.bands, position));
+        // Fill in between neighbouring columns so steep parts of the curve don't break up.
+        let (top, bottom) = match previous_row {
+            Some(previous) if previous < row => (previous + 1.0, row),
+            Some(previous) if previous > row => (row, previous - 1.0),
+            _ => (row, row),
+        };
+        for y in top as usize..=bottom as usize {
+            let color = graph_colors.get(y).copied().unwrap_or(Color32::WHITE);
+            let pixel = Rect::from_min_size(origin + Vec2::new(x as f32, y as f32), Vec2::splat(1.0));
+            ui.painter().rect_filled(pixel, 0.0, color);
+        }
+        previous_row = Some(row);
+    }
+}
+
+/// Smooth curve through `points` (evenly spaced), at `position` between 0 and `points.len() - 1`.
+fn catmull_rom(points: &[f32], position: f32) -> f32 {
+    let last = points.len() as isize - 1;
+    let i = (position.floor() as isize).clamp(0, last);
+    let t = position - i as f32;
+    let point = |index: isize| points[index.clamp(0, last) as usize];
+    let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t * t)
+}
@@ -0,0 +1,123 @@
+//! Where the skinned windows sit, moving them by their title bars, and which of them has focus.
+//!
+//! The equalizer and playlist start out docked: stacked under the main window, and moving with it.
+//! Dragging one away undocks it; dropping it back where it would dock docks it again.
+//! Any of them can be shaded down to a strip the height of a title bar, by double-clicking its title bar.
+
+use eframe::egui::{Pos2, Rect, Response, Vec2};
+
+/// Height of the main and equalizer title bars, and of any shaded window.
+pub const TITLE_BAR_HEIGHT: f32 = 14.0;
+/// How close to its docked spot a window has to be dropped to snap back into it
+const DOCK_DISTANCE: f32 = 10.0;
+/// How much of a window has to stay on the canvas, so there's always something to drag it back by
+const MIN_VISIBLE: Vec2 = Vec2::new(25.0, TITLE_BAR_HEIGHT);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SkinWindow {
+    Main,
+    Equalizer,
+    Playlist,
+}
+
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+#[derive(Debug, Clone)]
+pub struct WindowLayout {
+    pub main: Pos2,
+    /// None while docked under the main window
+    equalizer: Option<Pos2>,
+    /// None while docked under the equalizer, or the main window if the equalizer isn't there
+    playlist: Option<Pos2>,
+    /// Showing just the title bar
+    pub main_shaded: bool,
+    pub equalizer_shaded: bool,
+    pub playlist_shaded: bool,
+    /// Gets the lit up title bar. Winamp's windows take focus from each other when clicked.
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    focused: Option<SkinWindow>,
+}
+
+impl Default for WindowLayout {
+    fn default() -> Self {
+        Self {
+            main: Pos2::ZERO,
+            equalizer: None,
+            playlist: None,
+            main_shaded: false,
+            equalizer_shaded: false,
+            playlist_shaded: false,
+            focused: Some(SkinWindow::Main),
+        }
+    }
+}
+
+impl WindowLayout {
+    pub fn equalizer_origin(&self, main_height: f32) -> Pos2 {
+        self.equalizer.unwrap_or(self.main + Vec2::new(0.0, main_height))
+    }
+
+    /// `equalizer_height` is None when the equalizer is hidden, so there's nothing to dock under.
+    pub fn playlist_origin(&self, main_height: f32, equalizer_height: Option<f32>) -> Pos2 {
+        self.playlist.unwrap_or_else(|| self.playlist_docked_origin(main_height, equalizer_height))
+    }
+
+    fn playlist_docked_origin(&self, main_height: f32, equalizer_height: Option<f32>) -> Pos2 {
+        match equalizer_height {
+            Some(height) if self.equalizer.is_none() => self.main + Vec2::new(0.0, main_height + height),
+            _ => self.main + Vec2::new(0.0, main_height),
+        }
+    }
+
+    pub fn toggle_shade(&mut self, window: SkinWindow) {
+        let shaded = match window {
+            SkinWindow::Main => &mut self.main_shaded,
+            SkinWindow::Equalizer => &mut self.equalizer_shaded,
+            SkinWindow::Playlist => &mut self.playlist_shaded,
+        };
+        *shaded = !*shaded;
+    }
+
+    pub fn is_focused(&self, window: SkinWindow) -> bool {
+        self.focused == Some(window)
+    }
+
+    /// Moves `window` along with its title bar being dragged, keeping it on `canvas`.
+    /// `origin` is where the window was drawn this frame. Double-clicking the title bar shades or unshades it.
+    pub fn drag(&mut self, window: SkinWindow, title_bar: &Response, origin: Pos2, main_height: f32, equalizer_height: Option<f32>, canvas: Rect) {
+        if title_bar.drag_started() {
+            self.focused = Some(window);
+        }
+        if title_bar.double_clicked() {
+            self.toggle_shade(window);
+        }
+        if !title_bar.dragged() && !title_bar.drag_released() {
+            return;
+        }
+        let size = Vec2::new(title_bar.rect.right() - origin.x, title_bar.rect.bottom() - origin.y);
+        let min = canvas.min - size + MIN_VISIBLE;
+        let max = canvas.max - MIN_VISIBLE;
+        let moved = (origin + title_bar.drag_delta()).clamp(min, max);
+        let docked_origin = match window {
+            SkinWindow::Main => None,
+            SkinWindow::Equalizer => Some(self.main + Vec2::new(0.0, main_height)),
+            SkinWindow::Playlist => Some(self.playlist_docked_origin(main_height, equalizer_height)),
+        };
+        // Dropped close enough to where it docks
+        let docked = title_bar.drag_released() && docked_origin.is_some_and(|docked| docked.distance(moved) < DOCK_DISTANCE);
+        let position = if docked { None } else { Some(moved) };
+        match window {
+            SkinWindow::Main => self.main = moved,
+            SkinWindow::Equalizer => self.equalizer = position,
+            SkinWindow::Playlist => self.playlist = position,
+        }
+    }
+
+    /// Hands focus to whichever window was clicked in, or to none of them for a click on the empty canvas.
+    /// `windows` goes bottom to top, in the order they're drawn.
+    pub fn click_focus(&mut self, pointer_pressed: Option<Pos2>, windows: &[(SkinWindow, Rect)]) {
+        if let Some(pos) = pointer_pressed {
+            self.focused = windows.iter().rev().find(|(_, rect)| rect.contains(pos)).map(|(window, _)| *window);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod equalizer;
+pub mod layout;
+pub mod playlist;
+pub mod presets;
@@ -0,0 +1,610 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use eframe::egui::{self, Align2, Pos2, Rect, Sense, TextStyle, Ui, Vec2};
+use fixed_map::Map;
+
+use crate::app::LoadedTexture;
+use crate::playlist::{Location, Playlist, PlaylistEntry};
+use crate::skin_config::PlaylistStyle;
+use crate::skin_generated::SkinImage;
+use crate::tags::{self, TitleFormat, TrackInfo};
+use crate::widgets::bitmap_text::{paint_text, text_width, GLYPH_SIZE};
+use crate::windows::layout::TITLE_BAR_HEIGHT;
+
+const TOP_HEIGHT: f32 = 20.0;
+const BOTTOM_HEIGHT: f32 = 38.0;
+const LEFT_WIDTH: f32 = 12.0;
+const RIGHT_WIDTH: f32 = 20.0;
+/// The window is resized a whole tile at a time.
+const TILE_SIZE: Vec2 = Vec2::new(25.0, 29.0);
+const MIN_SIZE: Vec2 = Vec2::new(275.0, 116.0);
+const ROW_HEIGHT: f32 = 13.0;
+/// Scrollbar groove, from the window's right edge
+const SCROLLBAR_X: f32 = 15.0;
+/// Menu buttons, from the top of the bottom bar. LIST is measured from the right edge.
+const MENU_BUTTON_Y: f32 = 8.0;
+const ADD_BUTTON_X: f32 = 14.0;
+const REMOVE_BUTTON_X: f32 = 43.0;
+const SELECT_BUTTON_X: f32 = 72.0;
+const MISC_BUTTON_X: f32 = 101.0;
+const LIST_BUTTON_X: f32 = 44.0;
+const MENU_BUTTON_SIZE: Vec2 = Vec2::new(22.0, 18.0);
+/// Total length readout, from the left of the bottom right corner
+const RUNNING_TIME_POS: Vec2 = Vec2::new(7.0, 10.0);
+/// Height of the shaded window, the same as the other windows' title bars
+const SHADE_HEIGHT: f32 = TITLE_BAR_HEIGHT;
+/// Where the current track's title starts in the shaded window
+const SHADE_TEXT_POS: Vec2 = Vec2::new(5.0, 4.0);
+/// Space between the end of the track's length and the right edge, for the buttons
+const SHADE_TIME_RIGHT: f32 = 27.0;
+
+/// The popup menus along the bottom of the window.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Menu {
+    Add,
+    Remove,
+    Select,
+    Misc,
+    List,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum MenuItem {
+    AddUrl,
+    AddDirectory,
+    AddFile,
+    RemoveAll,
+    Crop,
+    RemoveSelected,
+    RemoveMissing,
+    InvertSelection,
+    SelectNone,
+    SelectAll,
+    Sort,
+    Info,
+    Options,
+    New,
+    Save,
+    Load,
+}
+
+impl Menu {
+    /// The strip down the menu's left edge.
+    fn bar(self) -> SkinImage {
+        match self {
+            Menu::Add => SkinImage::PlaylistAddMenuBar,
+            Menu::Remove => SkinImage::PlaylistRemoveMenuBar,
+            Menu::Select => SkinImage::PlaylistSelectMenuBar,
+            Menu::Misc => SkinImage::PlaylistMiscMenuBar,
+            Menu::List => SkinImage::PlaylistListMenuBar,
+        }
+    }
+
+    /// Top to bottom, with their images and pressed images.
+    fn items(self) -> &'static [(MenuItem, SkinImage, SkinImage)] {
+        use SkinImage::*;
+        match self {
+            Menu::Add => &[
+                (MenuItem::AddUrl, PlaylistAddUrl, PlaylistAddUrlPressed),
+                (MenuItem::AddDirectory, PlaylistAddDir, PlaylistAddDirPressed),
+                (MenuItem::AddFile, PlaylistAddFile, PlaylistAddFilePressed),
+            ],
+            Menu::Remove => &[
+                (MenuItem::RemoveMissing, PlaylistRemoveMisc, PlaylistRemoveMiscPressed),
+                (MenuItem::RemoveAll, PlaylistRemoveAll, PlaylistRemoveAllPressed),
+                (MenuItem::Crop, PlaylistRemoveCrop, PlaylistRemoveCropPressed),
+                (MenuItem::RemoveSelected, PlaylistRemoveSelected, PlaylistRemoveSelectedPressed),
+            ],
+            Menu::Select => &[
+                (MenuItem::InvertSelection, PlaylistSelectInvert, PlaylistSelectInvertPressed),
+                (MenuItem::SelectNone, PlaylistSelectNone, PlaylistSelectNonePressed),
+                (MenuItem::SelectAll, PlaylistSelectAll, PlaylistSelectAllPressed),
+            ],
+            Menu::Misc => &[
+                (MenuItem::Sort, PlaylistMiscSort, PlaylistMiscSortPressed),
+                (MenuItem::Info, PlaylistMiscInfo, PlaylistMiscInfoPressed),
+                (MenuItem::Options, PlaylistMiscOptions, PlaylistMiscOptionsPressed),
+            ],
+            Menu::List => &[
+                (MenuItem::New, PlaylistListNew, PlaylistListNewPressed),
+                (MenuItem::Save, PlaylistListSave, PlaylistListSavePressed),
+                (MenuItem::Load, PlaylistListLoad, PlaylistListLoadPressed),
+            ],
+        }
+    }
+}
+
+/// Everything about the playlist editor that isn't the playlist itself.
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "persistence", serde(default))]
+#[derive(Debug, Clone, Default)]
+pub struct PlaylistWindowState {
+    /// Tiles added to the smallest size, across and down
+    pub extra_tiles: (u32, u32),
+    /// First visible row
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    scroll: usize,
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    menu: Option<Menu>,
+    /// Where the resize handle was grabbed, relative to the bottom right corner
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    resize_grab: Option<Vec2>,
+    /// Where shift-click selects from
+    #[cfg_attr(feature = "persistence", serde(skip))]
+    anchor: Option<usize>,
+}
+
+impl PlaylistWindowState {
+    pub fn size(&self) -> Vec2 {
+        MIN_SIZE + Vec2::new(self.extra_tiles.0 as f32 * TILE_SIZE.x, self.extra_tiles.1 as f32 * TILE_SIZE.y)
+    }
+}
+
+/// What happened in the playlist editor this frame.
+#[derive(Default)]
+pub struct PlaylistEvents {
+    /// An entry was double-clicked.
+    pub play: Option<usize>,
+    pub closed: bool,
+    /// The shade button was clicked.
+    pub shade: bool,
+    /// For dragging the window around by
+    pub title_bar: Option<egui::Response>,
+}
+
+/// What the playlist editor works on.
+pub struct PlaylistEditor<'a> {
+    pub playlist: &'a mut Playlist,
+    pub state: &'a mut PlaylistWindowState,
+    /// Where menu items that need more than a click are passed on to
+    pub dialog: &'a mut PlaylistDialog,
+}
+
+/// Draws the playlist editor with its top left at `origin`.
+pub fn playlist_window(
+    ui: &mut Ui,
+    origin: Pos2,
+    focused: bool,
+    editor: PlaylistEditor<'_>,
+    textures: &Map<SkinImage, LoadedTexture>,
+    style: &PlaylistStyle,
+) -> PlaylistEvents {
+    use SkinImage::*;
+    let PlaylistEditor { playlist, state, dialog } = editor;
+    let mut events = PlaylistEvents::default();
+    let size = state.size();
+    let window = Rect::from_min_size(origin, size);
+    let texture = |image| textures.get(image).unwrap();
+    let paint = |ui: &mut Ui, pos: Pos2, image| {
+        let texture = texture(image);
+        texture.image().paint_at(ui, Rect::from_min_size(pos, texture.size));
+    };
+
+    let (top_tile, top_left, title, top_right) = if focused {
+        (PlaylistTopTileSelected, PlaylistTopLeftSelected, PlaylistTitleSelected, PlaylistTopRightSelected)
+    } else {
+        (PlaylistTopTile, PlaylistTopLeft, PlaylistTitle, PlaylistTopRight)
+    };
+
+    // Frame: tiles all the way along, then the fixed pieces over the top of them.
+    let mut x = 0.0;
+    while x < size.x {
+        paint(ui, origin + Vec2::new(x, 0.0), top_tile);
+        paint(ui, origin + Vec2::new(x, size.y - BOTTOM_HEIGHT), PlaylistBottomTile);
+        x += TILE_SIZE.x;
+    }
+    let mut y = TOP_HEIGHT;
+    while y < size.y - BOTTOM_HEIGHT {
+        paint(ui, origin + Vec2::new(0.0, y), PlaylistLeftTile);
+        paint(ui, origin + Vec2::new(size.x - RIGHT_WIDTH, y), PlaylistRightTile);
+        y += TILE_SIZE.y;
+    }
+    paint(ui, origin, top_left);
+    paint(ui, origin + Vec2::new(((size.x - texture(title).size.x) / 2.0).round(), 0.0), title);
+    paint(ui, origin + Vec2::new(size.x - texture(top_right).size.x, 0.0), top_right);
+    let bottom = origin + Vec2::new(0.0, size.y - BOTTOM_HEIGHT);
+    let bottom_right = bottom + Vec2::new(size.x - texture(PlaylistBottomRight).size.x, 0.0);
+    paint(ui, bottom, PlaylistBottomLeft);
+    paint(ui, bottom_right, PlaylistBottomRight);
+    // The visualizer only fits once the window's been widened.
+    let visualizer_x = bottom_right.x - texture(PlaylistVisualizer).size.x;
+    if visualizer_x >= bottom.x + texture(PlaylistBottomLeft).size.x {
+        paint(ui, Pos2::new(visualizer_x, bottom.y), PlaylistVisualizer);
+    }
+
+    // Everything left of the shade and close buttons
+    let title_bar = Rect::from_min_size(origin, Vec2::new(size.x - 20.0, TOP_HEIGHT));
+    events.title_bar = Some(ui.interact(title_bar, ui.id().with("playlist title bar"), Sense::click_and_drag()));
+    title_buttons(ui, origin, size.x, PlaylistShadePressed, &mut events, textures);
+
+    let total: Duration = playlist.entries().iter().filter_map(|entry| entry.duration).sum();
+    let selected: Duration = playlist.selected().filter_map(|index| playlist.entries()[index].duration).sum();
+    let running_time = format!("{}/{}", format_duration(selected), format_duration(total));
+    paint_text(ui, textures, &running_time, bottom_right + RUNNING_TIME_POS, window);
+
+    let list_rect = Rect::from_min_max(origin + Vec2::new(LEFT_WIDTH, TOP_HEIGHT), origin + size - Vec2::new(RIGHT_WIDTH, BOTTOM_HEIGHT));
+    let visible_rows = (list_rect.height() / ROW_HEIGHT).floor() as usize;
+    let max_scroll = playlist.len().saturating_sub(visible_rows);
+
+    let list = ui.interact(list_rect, ui.id().with("playlist tracks"), Sense::click());
+    if list.hovered() {
+        let scroll_delta = ui.input().scroll_delta.y;
+        if scroll_delta != 0.0 {
+            let rows = (scroll_delta.abs() / ROW_HEIGHT).ceil() as usize;
+            state.scroll = if scroll_delta > 0.0 { state.scroll.saturating_sub(rows) } else { state.scroll + rows };
+        }
+    }
+    // An open menu covers the bottom of the list, and clicks on it are for the menu.
+    if state.menu.is_none() && (list.clicked() || list.double_clicked()) {
+        let row = list.interact_pointer_pos().map(|pos| state.scroll + ((pos.y - list_rect.min.y) / ROW_HEIGHT) as usize);
+        match row.filter(|row| *row < playlist.len()) {
+            Some(row) => {
+                let modifiers = ui.input().modifiers;
+                if modifiers.shift {
+                    let anchor = state.anchor.unwrap_or(row);
+                    playlist.select_all(false);
+                    for index in anchor.min(row)..=anchor.max(row) {
+                        playlist.select(index, true);
+                    }
+                } else if modifiers.command {
+                    playlist.select(row, !playlist.entries()[row].selected);
+                    state.anchor = Some(row);
+                } else {
+                    playlist.select_all(false);
+                    playlist.select(row, true);
+                    state.anchor = Some(row);
+                }
+                if list.double_clicked() {
+                    events.play = Some(row);
+                }
+            }
+            None => playlist.select_all(false),
+        }
+    }
+
+    let scrollbar_rect = Rect::from_min_size(Pos2::new(origin.x + size.x - SCROLLBAR_X, list_rect.min.y), Vec2::new(texture(PlaylistScrollHandle).size.x, list_rect.height()));
+    let scrollbar = ui.interact(scrollbar_rect, ui.id().with("playlist scrollbar"), Sense::drag());
+    let handle_height = texture(PlaylistScrollHandle).size.y;
+    let handle_travel = scrollbar_rect.height() - handle_height;
+    if let (true, Some(pointer)) = (scrollbar.is_pointer_button_down_on(), scrollbar.interact_pointer_pos()) {
+        let fraction = ((pointer.y - scrollbar_rect.min.y - handle_height / 2.0) / handle_travel).clamp(0.0, 1.0);
+        state.scroll = (fraction * max_scroll as f32).round() as usize;
+    }
+    state.scroll = state.scroll.min(max_scroll);
+    let handle_y = if max_scroll == 0 { 0.0 } else { (state.scroll as f32 / max_scroll as f32 * handle_travel).round() };
+    let handle = if scrollbar.is_pointer_button_down_on() { PlaylistScrollHandlePressed } else { PlaylistScrollHandle };
+    paint(ui, scrollbar_rect.min + Vec2::new(0.0, handle_y), handle);
+
+    paint_tracks(ui, list_rect, playlist, state.scroll, style);
+
+    // Resizing from the bottom right corner, in whole tiles.
+    let grip_rect = Rect::from_min_max(window.max - Vec2::splat(RIGHT_WIDTH), window.max);
+    let grip = ui.interact(grip_rect, ui.id().with("playlist resize"), Sense::drag());
+    if grip.drag_started() {
+        state.resize_grab = grip.interact_pointer_pos().map(|pointer| window.max - pointer);
+    }
+    if let (Some(grab), Some(pointer)) = (state.resize_grab, grip.interact_pointer_pos()) {
+        let wanted = pointer + grab - origin - MIN_SIZE;
+        state.extra_tiles = ((wanted.x / TILE_SIZE.x).round().max(0.0) as u32, (wanted.y / TILE_SIZE.y).round().max(0.0) as u32);
+    }
+    if !grip.dragged() {
+        state.resize_grab = None;
+    }
+
+    let menus = [
+        (Menu::Add, bottom + Vec2::new(ADD_BUTTON_X, MENU_BUTTON_Y)),
+        (Menu::Remove, bottom + Vec2::new(REMOVE_BUTTON_X, MENU_BUTTON_Y)),
+        (Menu::Select, bottom + Vec2::new(SELECT_BUTTON_X, MENU_BUTTON_Y)),
+        (Menu::Misc, bottom + Vec2::new(MISC_BUTTON_X, MENU_BUTTON_Y)),
+        (Menu::List, bottom + Vec2::new(size.x - LIST_BUTTON_X, MENU_BUTTON_Y)),
+    ];
+    let mut menu_clicked = false;
+    for (menu, pos) in menus.iter() {
+        let button = ui.interact(Rect::from_min_size(*pos, MENU_BUTTON_SIZE), ui.id().with(menu), Sense::click());
+        if button.clicked() {
+            state.menu = if state.menu == Some(*menu) { None } else { Some(*menu) };
+            menu_clicked = true;
+        }
+    }
+    if let Some((menu, pos)) = state.menu.and_then(|open| menus.iter().find(|(menu, _)| *menu == open)) {
+        // Menus grow upwards, with their last item over the button that opened them.
+        let items = menu.items();
+        let top = pos.y + MENU_BUTTON_SIZE.y - items.len() as f32 * MENU_BUTTON_SIZE.y;
+        let bar = texture(menu.bar());
+        paint(ui, Pos2::new(pos.x - bar.size.x, top), menu.bar());
+        for (i, (item, image, pressed)) in items.iter().enumerate() {
+            let rect = Rect::from_min_size(Pos2::new(pos.x, top + i as f32 * MENU_BUTTON_SIZE.y), MENU_BUTTON_SIZE);
+            let response = ui.interact(rect, ui.id().with(item), Sense::click());
+            paint(ui, rect.min, if response.hovered() { *pressed } else { *image });
+            if response.clicked() {
+                run_menu_item(*item, playlist, dialog);
+                state.menu = None;
+                menu_clicked = true;
+            }
+        }
+    }
+    if !menu_clicked && ui.input().pointer.any_click() {
+        state.menu = None;
+    }
+
+    events
+}
+
+fn paint_tracks(ui: &Ui, list_rect: Rect, playlist: &Playlist, scroll: usize, style: &PlaylistStyle) {
+    let painter = ui.painter().sub_region(list_rect);
+    painter.rect_filled(list_rect, 0.0, style.normal_background);
+    // The skin asks for a system font by name (`style.font`), but egui only draws with fonts it's handed
+    // the data for and has no way to find one installed by name, so it's egui's own at about the size Winamp uses.
+    let text_style = TextStyle::Small;
+    for (row, (index, entry)) in playlist.entries().iter().enumerate().skip(scroll).enumerate() {
+        let row_rect = Rect::from_min_size(list_rect.min + Vec2::new(0.0, row as f32 * ROW_HEIGHT), Vec2::new(list_rect.width(), ROW_HEIGHT));
+        if row_rect.min.y >= list_rect.max.y {
+            break;
+        }
+        if entry.selected {
+            painter.rect_filled(row_rect, 0.0, style.selected_background);
+        }
+        let color = if playlist.current() == Some(index) { style.current } else { style.normal };
+
+        // The length goes on the right, and the title is cut off before it.
+        let length_rect = match entry.duration {
+            Some(duration) => painter.text(row_rect.right_center() - Vec2::new(2.0, 0.0), Align2::RIGHT_CENTER, format_duration(duration), text_style, color),
+            None => Rect::from_min_max(row_rect.right_top(), row_rect.right_bottom()),
+        };
+        let title_clip = Rect::from_min_max(row_rect.min, Pos2::new(length_rect.min.x - 4.0, row_rect.max.y));
+        painter.sub_region(title_clip).text(
+            row_rect.left_center() + Vec2::new(2.0, 0.0),
+            Align2::LEFT_CENTER,
+            format!("{}. {}", index + 1, entry.display_title()),
+            text_style,
+            color,
+        );
+    }
+}
+
+/// m:ss, or h:mm:ss once there are hours.
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    if seconds >= 3600 {
+        format!("{}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60)
+    } else {
+        format!("{}:{:02}", seconds / 60, seconds % 60)
+    }
+}
+
+/// Draws the shaded playlist editor with its top left at `origin`: the current track and how long it is.
+pub fn playlist_shade(ui: &mut Ui, origin: Pos2, focused: bool, playlist: &Playlist, state: &PlaylistWindowState, textures: &Map<SkinImage, LoadedTexture>) -> PlaylistEvents {
+    use SkinImage::*;
+    let mut events = PlaylistEvents::default();
+    let width = state.size().x;
+    let texture = |image| textures.get(image).unwrap();
+    let paint = |ui: &mut Ui, pos: Pos2, image| {
+        let texture = texture(image);
+        texture.image().paint_at(ui, Rect::from_min_size(pos, texture.size));
+    };
+
+    let right = if focused { PlaylistShadeRightSelected } else { PlaylistShadeRight };
+    let right_x = width - texture(right).size.x;
+    let mut x = texture(PlaylistShadeLeft).size.x;
+    while x < right_x {
+        paint(ui, origin + Vec2::new(x, 0.0), PlaylistShadeTile);
+        x += texture(PlaylistShadeTile).size.x;
+    }
+    paint(ui, origin, PlaylistShadeLeft);
+    paint(ui, origin + Vec2::new(right_x, 0.0), right);
+
+    let title_bar = Rect::from_min_size(origin, Vec2::new(width - 20.0, SHADE_HEIGHT));
+    events.title_bar = Some(ui.interact(title_bar, ui.id().with("playlist title bar"), Sense::click_and_drag()));
+    title_buttons(ui, origin, width, PlaylistUnshadePressed, &mut events, textures);
+
+    if let Some(index) = playlist.current() {
+        let entry = &playlist.entries()[index];
+        let time = entry.duration.map(format_duration).unwrap_or_default();
+        let time_x = width - SHADE_TIME_RIGHT - text_width(&time);
+        let text_rect = Rect::from_min_max(origin + Vec2::new(SHADE_TEXT_POS.x, 0.0), origin + Vec2::new(time_x - GLYPH_SIZE.x, SHADE_HEIGHT));
+        let title = format!("{}. {}", index + 1, entry.display_title());
+        paint_text(ui, textures, &title, origin + SHADE_TEXT_POS, text_rect);
+        let time_pos = origin + Vec2::new(time_x, SHADE_TEXT_POS.y);
+        paint_text(ui, textures, &time, time_pos, Rect::from_min_size(origin, Vec2::new(width, SHADE_HEIGHT)));
+    }
+    events
+}
+
+/// The shade and close buttons at the right of the title bar, which are only drawn while pressed.
+fn title_buttons(ui: &mut Ui, origin: Pos2, width: f32, shade_pressed: SkinImage, events: &mut PlaylistEvents, textures: &Map<SkinImage, LoadedTexture>) {
+    let mut button = |x: f32, id: &str, pressed: SkinImage| {
+        let rect = Rect::from_min_size(origin + Vec2::new(width - x, 3.0), Vec2::splat(9.0));
+        let response = ui.interact(rect, ui.id().with(id), Sense::click());
+        if response.is_pointer_button_down_on() {
+            let texture = textures.get(pressed).unwrap();
+            texture.image().paint_at(ui, rect);
+        }
+        response.clicked()
+    };
+    events.shade = button(20.0, "playlist shade", shade_pressed);
+    events.closed = button(11.0, "playlist close", SkinImage::PlaylistClosePressed);
+}
+
+fn run_menu_item(item: MenuItem, playlist: &mut Playlist, dialog: &mut PlaylistDialog) {
+    match item {
+        MenuItem::RemoveAll | MenuItem::New => playlist.clear(),
+        MenuItem::Crop => playlist.crop(),
+        MenuItem::RemoveSelected => playlist.remove_selected(),
+        MenuItem::RemoveMissing => playlist.remove_missing(),
+        MenuItem::InvertSelection => playlist.invert_selection(),
+        MenuItem::SelectNone => playlist.select_all(false),
+        MenuItem::SelectAll => playlist.select_all(true),
+        MenuItem::AddUrl => dialog.open(PlaylistPrompt::AddUrl),
+        MenuItem::AddDirectory => dialog.open(PlaylistPrompt::AddDirectory),
+        MenuItem::AddFile => dialog.open(PlaylistPrompt::AddFile),
+        MenuItem::Sort => dialog.open(PlaylistPrompt::Sort),
+        MenuItem::Info => dialog.open(PlaylistPrompt::Info),
+        MenuItem::Save => dialog.open(PlaylistPrompt::Save),
+        MenuItem::Load => dialog.open(PlaylistPrompt::Load),
+        MenuItem::Options => dialog.open(PlaylistPrompt::Options),
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PlaylistPrompt {
+    AddUrl,
+    AddDirectory,
+    AddFile,
+    Sort,
+    Info,
+    Save,
+    Load,
+    Options,
+}
+
+/// The plain egui windows behind the menu items that need a path typed in, or more choices.
+#[derive(Debug, Default)]
+pub struct PlaylistDialog {
+    prompt: Option<PlaylistPrompt>,
+    text: String,
+    /// Result of the last add, load or save, when it didn't work
+    message: Option<String>,
+    /// Tags of the track the info window is showing, so they're only read once
+    info: Option<(Location, TrackInfo)>,
+}
+
+impl PlaylistDialog {
+    pub fn open(&mut self, prompt: PlaylistPrompt) {
+        self.prompt = Some(prompt);
+        self.message = None;
+    }
+
+    /// Opens `prompt` showing why `text` didn't work, ready to try again.
+    pub fn open_with_error(&mut self, prompt: PlaylistPrompt, text: String, message: String) {
+        self.prompt = Some(prompt);
+        self.text = text;
+        self.message = Some(message);
+    }
+
+    pub fn show(&mut self, ctx: &egui::CtxRef, playlist: &mut Playlist, title_format: &mut TitleFormat) {
+        let prompt = match self.prompt {
+            Some(prompt) => prompt,
+            None => return,
+        };
+        let title = match prompt {
+            PlaylistPrompt::AddUrl => "Add URL",
+            PlaylistPrompt::AddDirectory => "Add folder",
+            PlaylistPrompt::AddFile => "Add file",
+            PlaylistPrompt::Sort => "Sort playlist",
+            PlaylistPrompt::Info => "Track info",
+            PlaylistPrompt::Save => "Save playlist",
+            PlaylistPrompt::Load => "Load playlist",
+            PlaylistPrompt::Options => "Playlist options",
+        };
+        let mut open = true;
+        let mut done = false;
+        egui::Window::new(title).open(&mut open).collapsible(false).show(ctx, |ui| {
+            match prompt {
+                PlaylistPrompt::Sort => {
+                    if ui.button("Sort by title").clicked() {
+                        playlist.sort_by_key(|entry| entry.display_title().to_lowercase());
+                        done = true;
+                    }
+                    if ui.button("Sort by file name").clicked() {
+                        playlist.sort_by_key(|entry| file_name(entry).to_lowercase());
+                        done = true;
+                    }
+                    if ui.button("Sort by path and file name").clicked() {
+                        playlist.sort_by_key(|entry| entry.location.to_string().to_lowercase());
+                        done = true;
+                    }
+                    if ui.button("Reverse list").clicked() {
+                        playlist.reverse();
+                        done = true;
+                    }
+                }
+                PlaylistPrompt::Info => match playlist.selected().next().map(|index| &playlist.entries()[index]) {
+                    Some(entry) => {
+                        ui.label(format!("Title: {}", entry.display_title()));
+                        ui.label(format!("Location: {}", entry.location));
+                        ui.label(format!("Length: {}", entry.duration.map(format_duration).unwrap_or_else(|| "unknown".to_string())));
+                        if self.info.as_ref().is_none_or(|(location, _)| *location != entry.location) {
+                            let info = entry.location.path().and_then(|path| tags::read_info(path).ok()).unwrap_or_default();
+                            self.info = Some((entry.location.clone(), info));
+                        }
+                        if let Some((_, info)) = &self.info {
+                            show_track_info(ui, info);
+                        }
+                    }
+                    None => {
+                        ui.label("Select a track first.");
+                    }
+                },
+                PlaylistPrompt::Options => {
+                    ui.horizontal(|ui| {
+                        ui.label("Title format:");
+                        // Every track gets renamed, as they're read again in the background.
+                        if ui.text_edit_singleline(&mut title_format.0).changed() {
+                            playlist.forget_tags();
+                        }
+                    });
+                    ui.label("%1 artist, %2 title, %3 album, %4 year, %5 comment, %6 genre,");
+                    ui.label("%7 file name, %8 path, %9 extension, %0 track number");
+                }
+                _ => {
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.text);
+                        if ui.button("OK").clicked() {
+                            let result = match prompt {
+                                PlaylistPrompt::AddUrl => {
+                                    playlist.add(PlaylistEntry::new(Location::Url(self.text.trim().to_string())));
+                                    Ok(())
+                                }
+                                PlaylistPrompt::AddDirectory => playlist.add_directory(&PathBuf::from(&self.text)).map(|_| ()),
+                                PlaylistPrompt::AddFile => playlist.add_file(&PathBuf::from(&self.text)).map(|_| ()),
+                                PlaylistPrompt::Save => playlist.save(&PathBuf::from(&self.text)),
+                                PlaylistPrompt::Load => Playlist::load(&PathBuf::from(&self.text)).map(|loaded| *playlist = loaded),
+                                PlaylistPrompt::Sort | PlaylistPrompt::Info | PlaylistPrompt::Options => Ok(()),
+                            };
+                            match result {
+                                Ok(()) => done = true,
+                                Err(e) => self.message = Some(e.to_string()),
+                            }
+                        }
+                    });
+                    if let Some(message) = &self.message {
+                        ui.label(message);
+                    }
+                }
+            }
+        });
+        if !open || done {
+            self.prompt = None;
+        }
+    }
+}
+
+fn show_track_info(ui: &mut Ui, info: &TrackInfo) {
+    let fields = [
+        ("Artist", info.artist.clone()),
+        ("Album", info.album.clone()),
+        ("Track", info.track_number.map(|number| number.to_string())),
+        ("Year", info.year.map(|year| year.to_string())),
+        ("Genre", info.genre.clone()),
+        ("Comment", info.comment.clone()),
+        ("Bitrate", info.bitrate.map(|bitrate| format!("{} kbps", bitrate))),
+        ("Sample rate", info.sample_rate.map(|rate| format!("{} Hz", rate))),
+        ("Channels", info.channels.map(|channels| channels.to_string())),
+    ];
+    for (name, value) in fields.iter() {
+        if let Some(value) = value {
+            ui.label(format!("{}: {}", name, value));
+        }
+    }
+    if !info.pictures.is_empty() {
+        ui.label(format!("Pictures: {}", info.pictures.len()));
+    }
+}
+
+fn file_name(entry: &PlaylistEntry) -> String {
+    match &entry.location {
+        Location::Path(path) => path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+        Location::Url(url) => url.clone(),
+    }
+}
@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use eframe::egui;
+
+use crate::audio::EqSettings;
+use crate::eq_presets::{write_eqf, EqPreset, PresetStore};
+
+/// The Load/Save/Delete menus behind the equalizer's presets button.
+#[derive(Default)]
+pub struct PresetsWindow {
+    pub open: bool,
+    /// Name to save the current settings under
+    name: String,
+    /// .EQF or winamp.q1 to import from or export to
+    path: String,
+    /// Result of the last import or export
+    message: Option<String>,
+}
+
+impl PresetsWindow {
+    /// Returns true if a preset was loaded into `settings`.
+    pub fn show(&mut self, ctx: &egui::CtxRef, store: &mut PresetStore, settings: &mut EqSettings) -> bool {
+        let mut loaded = false;
+        let mut open = self.open;
+        egui::Window::new("Equalizer presets").open(&mut open).collapsible(false).show(ctx, |ui| {
+            ui.collapsing("Load", |ui| {
+                for preset in store.presets() {
+                    if ui.button(&preset.name).clicked() {
+                        preset.apply(settings);
+                        loaded = true;
+                    }
+                }
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.path);
+                    if ui.button("From .EQF/.q1").clicked() {
+                        self.message = Some(match store.import(&PathBuf::from(&self.path)) {
+                            Ok(presets) => {
+                                // A single preset file is loaded straight away, a library just adds to the list.
+                                if let [preset] = &presets[..] {
+                                    preset.apply(settings);
+                                    loaded = true;
+                                }
+                                format!("Imported {} presets", presets.len())
+                            }
+                            Err(e) => e.to_string(),
+                        });
+                    }
+                });
+            });
+
+            ui.collapsing("Save", |ui| {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.name);
+                    if ui.add(egui::Button::new("Save").enabled(!self.name.is_empty())).clicked() {
+                        store.save(EqPreset::new(&self.name, settings));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.path);
+                    if ui.button("To .EQF").clicked() {
+                        let name = if self.name.is_empty() { "Preset" } else { &self.name };
+                        let data = write_eqf(&[EqPreset::new(name, settings)]);
+                        self.message = Some(match std::fs::write(&self.path, data) {
+                            Ok(()) => format!("Saved {}", self.path),
+                            Err(e) => e.to_string(),
+                        });
+                    }
+                    if ui.button("All to .q1").clicked() {
+                        self.message = Some(match store.export(&PathBuf::from(&self.path)) {
+                            Ok(()) => format!("Saved {}", self.path),
+                            Err(e) => e.to_string(),
+                        });
+                    }
+                });
+            });
+
+            ui.collapsing("Delete", |ui| {
+                let mut delete = None;
+                for preset in store.presets() {
+                    if ui.button(&preset.name).clicked() {
+                        delete = Some(preset.name.clone());
+                    }
+                }
+                if let Some(name) = delete {
+                    store.delete(&name);
+                }
+            });
+
+            if let Some(message) = &self.message {
+                ui.label(message);
+            }
+        });
+        self.open = open;
+        loaded
+    }
+}